@@ -37,11 +37,37 @@ export interface ServiceConfig {
         needlessMut?: LintLevel
         needlessTryTable?: LintLevel
         uselessCatch?: LintLevel
+        unusedSuppression?: LintLevel
+        constFold?: LintLevel
+        trap?: LintLevel
+        segmentBounds?: LintLevel
+        nameAnnotation?: LintLevel
+        codeMetadata?: LintLevel
     }
     inlayHint: {
         types?: boolean
         ending?: boolean
         index?: boolean
+        codeOffset?: boolean
+    }
+    imports?: Record<string, string>
+    features: {
+        preset?: 'mvp' | '1.0' | '2.0' | '3.0' | 'latest'
+        signExtension?: boolean
+        nonTrappingFloatToInt?: boolean
+        bulkMemory?: boolean
+        referenceTypes?: boolean
+        simd?: boolean
+        tailCall?: boolean
+        functionReferences?: boolean
+        gc?: boolean
+        exceptionHandling?: boolean
+        memory64?: boolean
+        multiMemory?: boolean
+        relaxedSimd?: boolean
+        threads?: boolean
+        stackSwitching?: boolean
+        wideArithmetic?: boolean
     }
 }
 "#;
@@ -176,6 +202,11 @@ impl LanguageService {
             .and_then(|params| self.inner.code_action(params).serialize(&self.serializer))
     }
 
+    #[wasm_bindgen(js_name = "fixAll")]
+    pub fn fix_all(&self, uri: String) -> Option<String> {
+        self.inner.fix_all(&uri)
+    }
+
     #[wasm_bindgen(js_name = "codeLens", unchecked_return_type = "lsp.CodeLens[] | null")]
     pub fn code_lens(
         &self,
@@ -238,6 +269,20 @@ impl LanguageService {
         serde_wasm_bindgen::from_value(params)
             .and_then(|params| self.inner.pull_diagnostics(params).serialize(&self.serializer))
     }
+    #[wasm_bindgen(
+        js_name = "pullWorkspaceDiagnostics",
+        unchecked_return_type = "lsp.WorkspaceDiagnosticReport"
+    )]
+    pub fn pull_workspace_diagnostics(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "lsp.WorkspaceDiagnosticParams")] params: JsValue,
+    ) -> Result<JsValue, Error> {
+        serde_wasm_bindgen::from_value(params).and_then(|params| {
+            self.inner
+                .pull_workspace_diagnostics(params)
+                .serialize(&self.serializer)
+        })
+    }
     #[wasm_bindgen(
         js_name = "publishDiagnostics",
         unchecked_return_type = "lsp.PublishDiagnosticsParams"
@@ -267,6 +312,15 @@ impl LanguageService {
             .and_then(|params| self.inner.document_symbol(params).serialize(&self.serializer))
     }
 
+    #[wasm_bindgen(js_name = "workspaceSymbol", unchecked_return_type = "lsp.WorkspaceSymbol[] | null")]
+    pub fn workspace_symbol(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "lsp.WorkspaceSymbolParams")] params: JsValue,
+    ) -> Result<JsValue, Error> {
+        serde_wasm_bindgen::from_value(params)
+            .and_then(|params| self.inner.workspace_symbol(params).serialize(&self.serializer))
+    }
+
     #[wasm_bindgen(js_name = "executeCommand")]
     pub fn execute_command(
         &self,
@@ -0,0 +1,202 @@
+use similar::TextDiff;
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+use wat_formatter::config::FormatOptions;
+
+const HELP: &str = "\
+Usage: watfmt [options] [files or globs]...
+
+Format files in place. Directories are searched recursively for `.wat` and `.wast` files.
+If there're no files or file is `-`, read from stdin and write to stdout.
+
+Options:
+      --check                  Don't write files, but exit with non-zero code if any file isn't formatted
+      --diff                   Don't write files, but print diff of changes
+  -c, --config <file>          Use this configuration file instead of discovering
+      --stdin-filepath <path>  Path for discovering configuration when reading from stdin
+  -h, --help                   Print help
+  -v, --version                Print version";
+
+fn main() -> ExitCode {
+    let mut check = false;
+    let mut diff = false;
+    let mut config_file = None;
+    let mut stdin_filepath = None;
+    let mut patterns = vec![];
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match &*arg {
+            "-v" | "-V" | "--version" => {
+                println!("watfmt v{}", env!("CARGO_PKG_VERSION"));
+                return ExitCode::SUCCESS;
+            }
+            "-h" | "--help" => {
+                println!("{HELP}");
+                return ExitCode::SUCCESS;
+            }
+            "--check" => check = true,
+            "--diff" => diff = true,
+            "-c" | "--config" => match args.next() {
+                Some(file) => config_file = Some(PathBuf::from(file)),
+                None => return fatal("missing value for `--config`"),
+            },
+            "--stdin-filepath" => match args.next() {
+                Some(path) => stdin_filepath = Some(PathBuf::from(path)),
+                None => return fatal("missing value for `--stdin-filepath`"),
+            },
+            _ if arg.starts_with('-') && arg.len() > 1 => return fatal(&format!("unknown option `{arg}`")),
+            _ => patterns.push(arg),
+        }
+    }
+
+    let mut options_resolver = OptionsResolver::default();
+    if let Some(file) = config_file {
+        match wat_project::load_config_file(&file) {
+            Ok(config) => options_resolver.fixed = Some((file, config)),
+            Err(err) => return fatal(&err.to_string()),
+        }
+    }
+
+    if patterns.is_empty() || patterns == ["-"] {
+        let mut text = String::new();
+        if let Err(err) = io::stdin().read_to_string(&mut text) {
+            return fatal(&format!("failed to read stdin: {err}"));
+        }
+        let path = stdin_filepath.unwrap_or_else(|| PathBuf::from("<stdin>"));
+        let options = match options_resolver.resolve(&path) {
+            Ok(options) => options,
+            Err(err) => return fatal(&err.to_string()),
+        };
+        let formatted = match format(&path, &text, &options) {
+            Ok(formatted) => formatted,
+            Err(message) => return fatal(&message),
+        };
+        if diff {
+            print_diff(&path, &text, &formatted);
+        }
+        if check {
+            if formatted != text {
+                eprintln!("would reformat `{}`", path.display());
+                return ExitCode::FAILURE;
+            }
+        } else if !diff {
+            print!("{formatted}");
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let files = match wat_project::collect_files(&patterns) {
+        Ok(files) if files.is_empty() => return fatal("no input files"),
+        Ok(files) => files,
+        Err(err) => return fatal(&err.to_string()),
+    };
+    let mut has_error = false;
+    let mut unformatted = 0;
+    files.iter().for_each(|file| {
+        let result = fs::read_to_string(file)
+            .map_err(|err| format!("failed to read `{}`: {err}", file.display()))
+            .and_then(|text| {
+                let options = options_resolver.resolve(file).map_err(|err| err.to_string())?;
+                format(file, &text, &options).map(|formatted| (text, formatted))
+            });
+        let (text, formatted) = match result {
+            Ok(result) => result,
+            Err(message) => {
+                eprintln!("error: {message}");
+                has_error = true;
+                return;
+            }
+        };
+        if formatted == text {
+            return;
+        }
+        unformatted += 1;
+        if diff {
+            print_diff(file, &text, &formatted);
+        }
+        if check {
+            eprintln!("would reformat `{}`", file.display());
+        } else if !diff && let Err(err) = fs::write(file, formatted) {
+            eprintln!("error: failed to write `{}`: {err}", file.display());
+            has_error = true;
+        }
+    });
+
+    if has_error {
+        ExitCode::from(2)
+    } else if check && unformatted > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn fatal(message: &str) -> ExitCode {
+    eprintln!("error: {message}");
+    ExitCode::from(2)
+}
+
+#[derive(Default)]
+/// Resolve format options for each file, with discovered configuration files cached.
+struct OptionsResolver {
+    /// Configuration file specified via command line, which is used instead of discovered ones.
+    fixed: Option<(PathBuf, serde_json::Value)>,
+    configs: wat_project::ConfigResolver,
+}
+
+impl OptionsResolver {
+    fn resolve(&mut self, path: &Path) -> Result<FormatOptions, wat_project::Error> {
+        let config = if let Some((file, config)) = &self.fixed {
+            let mut resolved = serde_json::Value::Null;
+            wat_project::apply_config(&mut resolved, config, file, path);
+            resolved
+        } else if let Some(config) = self.configs.resolve(path)? {
+            config
+        } else {
+            return Ok(FormatOptions::default());
+        };
+        parse_options(path, config)
+    }
+}
+
+/// Format options are read from the `format` section of configuration,
+/// which contains both layout options and language options.
+fn parse_options(path: &Path, mut config: serde_json::Value) -> Result<FormatOptions, wat_project::Error> {
+    let options = match config.get_mut("format") {
+        Some(format) => serde_json::from_value::<FormatOptions>(format.take()).map_err(|err| {
+            wat_project::Error::new(format!("invalid format options for `{}`: {err}", path.display()))
+        })?,
+        None => FormatOptions::default(),
+    };
+    if options.layout.indent_width == 0 {
+        return Err(wat_project::Error::new(format!(
+            "invalid format options for `{}`: `indentWidth` must be greater than 0",
+            path.display()
+        )));
+    }
+    Ok(options)
+}
+
+fn format(path: &Path, text: &str, options: &FormatOptions) -> Result<String, String> {
+    let (green, errors) = wat_parser::parse(text);
+    if let Some(error) = errors.first() {
+        let offset = usize::from(error.range.start());
+        let line = text[..offset].matches('\n').count() + 1;
+        let column = text[..offset].rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+        return Err(format!(
+            "failed to parse `{}` at {line}:{column}: {}",
+            path.display(),
+            error.message
+        ));
+    }
+    Ok(wat_formatter::format(&green, options))
+}
+
+fn print_diff(path: &Path, old: &str, new: &str) {
+    let path = path.display().to_string();
+    print!("{}", TextDiff::from_lines(old, new).unified_diff().header(&path, &path));
+}
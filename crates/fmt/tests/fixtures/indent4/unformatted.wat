@@ -0,0 +1 @@
+(module (func $f (param i32) (result i32) local.get 0))
@@ -0,0 +1 @@
+(module (func $f (param i32
@@ -0,0 +1,111 @@
+use assert_cmd::Command;
+use predicates::str::contains;
+use std::fs;
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+fn watfmt() -> Command {
+    let mut command = Command::new(assert_cmd::cargo_bin!("watfmt"));
+    command.current_dir(FIXTURES);
+    command
+}
+
+#[test]
+fn stdin() {
+    watfmt()
+        .write_stdin("(module (func $f (param i32) (result i32) local.get 0))\n")
+        .assert()
+        .success()
+        .stdout("(module\n  (func $f (param i32) (result i32)\n    local.get 0))\n");
+}
+
+#[test]
+fn stdin_filepath() {
+    watfmt()
+        .args(["--stdin-filepath", "indent4/stdin.wat"])
+        .write_stdin("(module (func $f (param i32) (result i32) local.get 0))\n")
+        .assert()
+        .success()
+        .stdout("(module\n    (func $f (param i32) (result i32)\n        local.get 0))\n");
+}
+
+#[test]
+fn check() {
+    watfmt()
+        .args(["--check", "formatted.wat"])
+        .assert()
+        .success()
+        .stderr("");
+    watfmt()
+        .args(["--check", "formatted.wat", "unformatted.wat"])
+        .assert()
+        .code(1)
+        .stderr("would reformat `unformatted.wat`\n");
+}
+
+#[test]
+fn diff() {
+    watfmt().args(["--diff", "unformatted.wat"]).assert().success().stdout(
+        "\
+--- unformatted.wat
++++ unformatted.wat
+@@ -1 +1,3 @@
+-(module (func $f (param i32) (result i32) local.get 0))
++(module
++  (func $f (param i32) (result i32)
++    local.get 0))
+",
+    );
+}
+
+#[test]
+fn discover_toml() {
+    watfmt()
+        .args(["--diff", "indent4/unformatted.wat"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "+    (func $f (param i32) (result i32)\n+        local.get 0))\n",
+        ));
+}
+
+#[test]
+fn discover_json() {
+    watfmt()
+        .args(["--diff", "json/unformatted.wat"])
+        .assert()
+        .success()
+        .stdout(contains("+    local.get 0\n+  )\n+)\n"));
+}
+
+#[test]
+fn explicit_config() {
+    watfmt()
+        .args(["--config", "indent4/wat.toml", "--diff", "json/unformatted.wat"])
+        .assert()
+        .success()
+        .stdout(contains("+        local.get 0))\n"));
+}
+
+#[test]
+fn in_place() {
+    let dir = std::env::temp_dir().join(format!("watfmt-in-place-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("test.wat");
+    fs::write(&file, "(module (func $f (param i32) (result i32) local.get 0))\n").unwrap();
+    watfmt().arg(&file).assert().success();
+    assert_eq!(
+        fs::read_to_string(&file).unwrap(),
+        "(module\n  (func $f (param i32) (result i32)\n    local.get 0))\n"
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn syntax_error() {
+    watfmt()
+        .args(["--check", "invalid.wat"])
+        .assert()
+        .code(2)
+        .stderr("error: failed to parse `invalid.wat` at 2:1: expected `)`\n");
+}
@@ -292,6 +292,225 @@ pub(crate) fn format_block_loop<'a>(block_loop: AmberNode<'a>, ctx: &'a Ctx<'a>)
     Doc::slice(docs.into_bump_slice())
 }
 
+pub(crate) fn format_block_try<'a>(block_try: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    let mut docs = BumpVec::with_capacity_in(2, &ctx.bump);
+    let mut trivias = BumpVec::new_in(&ctx.bump);
+    if let Some(l_paren) = block_try.tokens_by_kind(L_PAREN).next() {
+        docs.push(Doc::char('('));
+        ctx.format_trivias_after_token(l_paren, block_try, &mut trivias);
+    }
+    if let Some(keyword) = block_try.tokens_by_kind(KEYWORD).next() {
+        docs.append(&mut trivias);
+        docs.push(Doc::text("try"));
+        ctx.format_trivias_after_token(keyword, block_try, &mut trivias);
+    }
+    if let Some(ident) = block_try.tokens_by_kind(IDENT).next() {
+        if trivias.is_empty() {
+            docs.push(Doc::space());
+        } else {
+            docs.append(&mut trivias);
+        }
+        docs.push(Doc::text(ident.text()));
+        ctx.format_trivias_after_token(ident, block_try, &mut trivias);
+    }
+    if let Some(type_use) = block_try.children_by_kind(TYPE_USE).next() {
+        if trivias.is_empty() {
+            docs.push(Doc::space());
+        } else {
+            docs.append(&mut trivias);
+        }
+        docs.push(format_type_use(type_use, ctx));
+        ctx.format_trivias_after_node(type_use, block_try, &mut trivias);
+    }
+    block_try
+        .children()
+        .filter(|child| {
+            matches!(
+                child.kind(),
+                BLOCK_TRY_DO | BLOCK_TRY_CATCH | BLOCK_TRY_CATCH_ALL | BLOCK_TRY_DELEGATE
+            )
+        })
+        .for_each(|child| {
+            if trivias.is_empty() && (child.kind() != BLOCK_TRY_DO || child.tokens_by_kind(L_PAREN).next().is_some()) {
+                docs.push(Doc::hard_line());
+            } else {
+                docs.append(&mut trivias);
+            }
+            match child.kind() {
+                BLOCK_TRY_DO => docs.push(format_block_try_do(child, ctx)),
+                BLOCK_TRY_CATCH => docs.push(format_block_try_catch(child, ctx)),
+                BLOCK_TRY_CATCH_ALL => docs.push(format_block_try_catch_all(child, ctx)),
+                _ => docs.push(format_block_try_delegate(child, ctx)),
+            }
+            ctx.format_trivias_after_node(child, block_try, &mut trivias);
+        });
+    docs.push(Doc::slice(trivias.into_bump_slice()).nest(ctx.indent_width));
+    trivias = BumpVec::new_in(&ctx.bump);
+    if block_try.tokens_by_kind(R_PAREN).next().is_some() {
+        Doc::slice(ctx.bump.alloc_slice_fill_iter([
+            Doc::slice(docs.into_bump_slice()).nest(ctx.indent_width),
+            ctx.format_right_paren(block_try),
+        ]))
+        .group()
+    } else {
+        if let Some(keyword) = block_try.tokens_by_kind(KEYWORD).find(|token| token.text() == "end") {
+            docs.push(Doc::hard_line());
+            docs.push(Doc::text("end"));
+            ctx.format_trivias_after_token(keyword, block_try, &mut trivias);
+        }
+        if let Some(ident) = block_try.tokens_by_kind(IDENT).nth(1) {
+            if trivias.is_empty() {
+                docs.push(Doc::space());
+            } else {
+                docs.append(&mut trivias);
+            }
+            docs.push(Doc::text(ident.text()));
+        }
+        Doc::slice(docs.into_bump_slice())
+    }
+}
+
+pub(crate) fn format_block_try_catch<'a>(block_try_catch: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    let mut docs = BumpVec::with_capacity_in(2, &ctx.bump);
+    let mut trivias = BumpVec::new_in(&ctx.bump);
+    if let Some(l_paren) = block_try_catch.tokens_by_kind(L_PAREN).next() {
+        docs.push(Doc::char('('));
+        ctx.format_trivias_after_token(l_paren, block_try_catch, &mut trivias);
+    }
+    if let Some(keyword) = block_try_catch.tokens_by_kind(KEYWORD).next() {
+        docs.append(&mut trivias);
+        docs.push(Doc::text("catch"));
+        ctx.format_trivias_after_token(keyword, block_try_catch, &mut trivias);
+    }
+    if let Some(tag_index) = block_try_catch.children_by_kind(INDEX).next() {
+        if trivias.is_empty() {
+            docs.push(Doc::space());
+        } else {
+            docs.append(&mut trivias);
+        }
+        docs.push(format_index(tag_index));
+        ctx.format_trivias_after_node(tag_index, block_try_catch, &mut trivias);
+    }
+    block_try_catch.children_by_kind(Instr::can_cast).for_each(|instr| {
+        if trivias.is_empty() {
+            docs.push(Doc::hard_line());
+        } else {
+            docs.append(&mut trivias);
+        }
+        docs.push(format_instr(instr, ctx));
+        ctx.format_trivias_after_node(instr, block_try_catch, &mut trivias);
+    });
+    docs.append(&mut trivias);
+    let doc = Doc::slice(docs.into_bump_slice()).nest(ctx.indent_width);
+    if block_try_catch.tokens_by_kind(R_PAREN).next().is_some() {
+        Doc::slice(
+            ctx.bump
+                .alloc_slice_fill_iter([doc, ctx.format_right_paren_after_instr(block_try_catch)]),
+        )
+    } else {
+        doc
+    }
+}
+
+pub(crate) fn format_block_try_catch_all<'a>(block_try_catch_all: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    let mut docs = BumpVec::with_capacity_in(2, &ctx.bump);
+    let mut trivias = BumpVec::new_in(&ctx.bump);
+    if let Some(l_paren) = block_try_catch_all.tokens_by_kind(L_PAREN).next() {
+        docs.push(Doc::char('('));
+        ctx.format_trivias_after_token(l_paren, block_try_catch_all, &mut trivias);
+    }
+    if let Some(keyword) = block_try_catch_all.tokens_by_kind(KEYWORD).next() {
+        docs.append(&mut trivias);
+        docs.push(Doc::text("catch_all"));
+        ctx.format_trivias_after_token(keyword, block_try_catch_all, &mut trivias);
+    }
+    block_try_catch_all.children_by_kind(Instr::can_cast).for_each(|instr| {
+        if trivias.is_empty() {
+            docs.push(Doc::hard_line());
+        } else {
+            docs.append(&mut trivias);
+        }
+        docs.push(format_instr(instr, ctx));
+        ctx.format_trivias_after_node(instr, block_try_catch_all, &mut trivias);
+    });
+    docs.append(&mut trivias);
+    let doc = Doc::slice(docs.into_bump_slice()).nest(ctx.indent_width);
+    if block_try_catch_all.tokens_by_kind(R_PAREN).next().is_some() {
+        Doc::slice(
+            ctx.bump
+                .alloc_slice_fill_iter([doc, ctx.format_right_paren_after_instr(block_try_catch_all)]),
+        )
+    } else {
+        doc
+    }
+}
+
+pub(crate) fn format_block_try_delegate<'a>(block_try_delegate: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    let mut docs = BumpVec::with_capacity_in(2, &ctx.bump);
+    let mut trivias = BumpVec::new_in(&ctx.bump);
+    if let Some(l_paren) = block_try_delegate.tokens_by_kind(L_PAREN).next() {
+        docs.push(Doc::char('('));
+        ctx.format_trivias_after_token(l_paren, block_try_delegate, &mut trivias);
+    }
+    if let Some(keyword) = block_try_delegate.tokens_by_kind(KEYWORD).next() {
+        docs.append(&mut trivias);
+        docs.push(Doc::text("delegate"));
+        ctx.format_trivias_after_token(keyword, block_try_delegate, &mut trivias);
+    }
+    if let Some(label_index) = block_try_delegate.children_by_kind(INDEX).next() {
+        if trivias.is_empty() {
+            docs.push(Doc::space());
+        } else {
+            docs.append(&mut trivias);
+        }
+        docs.push(format_index(label_index));
+        ctx.format_trivias_after_node(label_index, block_try_delegate, &mut trivias);
+    }
+    docs.append(&mut trivias);
+    let doc = Doc::slice(docs.into_bump_slice()).nest(ctx.indent_width);
+    if block_try_delegate.tokens_by_kind(R_PAREN).next().is_some() {
+        Doc::slice(
+            ctx.bump
+                .alloc_slice_fill_iter([doc, ctx.format_right_paren_on_same_line(block_try_delegate)]),
+        )
+    } else {
+        doc
+    }
+}
+
+pub(crate) fn format_block_try_do<'a>(block_try_do: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    let mut docs = BumpVec::with_capacity_in(2, &ctx.bump);
+    let mut trivias = BumpVec::new_in(&ctx.bump);
+    if let Some(l_paren) = block_try_do.tokens_by_kind(L_PAREN).next() {
+        docs.push(Doc::char('('));
+        ctx.format_trivias_after_token(l_paren, block_try_do, &mut trivias);
+    }
+    if let Some(keyword) = block_try_do.tokens_by_kind(KEYWORD).next() {
+        docs.append(&mut trivias);
+        docs.push(Doc::text("do"));
+        ctx.format_trivias_after_token(keyword, block_try_do, &mut trivias);
+    }
+    block_try_do.children_by_kind(Instr::can_cast).for_each(|instr| {
+        if trivias.is_empty() {
+            docs.push(Doc::hard_line());
+        } else {
+            docs.append(&mut trivias);
+        }
+        docs.push(format_instr(instr, ctx));
+        ctx.format_trivias_after_node(instr, block_try_do, &mut trivias);
+    });
+    docs.append(&mut trivias);
+    let doc = Doc::slice(docs.into_bump_slice()).nest(ctx.indent_width);
+    if block_try_do.tokens_by_kind(R_PAREN).next().is_some() {
+        Doc::slice(
+            ctx.bump
+                .alloc_slice_fill_iter([doc, ctx.format_right_paren_after_instr(block_try_do)]),
+        )
+    } else {
+        doc
+    }
+}
+
 pub(crate) fn format_block_try_table<'a>(block_try_table: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
     let mut docs = BumpVec::with_capacity_in(2, &ctx.bump);
     let mut trivias = BumpVec::new_in(&ctx.bump);
@@ -457,6 +676,7 @@ pub(crate) fn format_instr<'a>(instr: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a
         BLOCK_LOOP => format_block_loop(instr, ctx),
         BLOCK_IF => format_block_if(instr, ctx),
         BLOCK_TRY_TABLE => format_block_try_table(instr, ctx),
+        BLOCK_TRY => format_block_try(instr, ctx),
         _ => Doc::nil(),
     }
 }
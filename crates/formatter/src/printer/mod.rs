@@ -1,4 +1,4 @@
-use self::{instr::*, module::*, ty::*};
+use self::{instr::*, module::*, script::*, ty::*};
 use crate::config::{FormatOptions, LanguageOptions, MultiLine, WrapBefore};
 use bumpalo::{Bump, collections::Vec as BumpVec};
 use std::iter;
@@ -7,6 +7,7 @@ use wat_syntax::{AmberNode, AmberToken, NodeOrToken, SyntaxKind, ast::*};
 
 mod instr;
 mod module;
+mod script;
 mod ty;
 
 pub(super) struct Ctx<'a> {
@@ -189,6 +190,11 @@ pub(crate) fn format_node<'a>(node: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a>
         SyntaxKind::BLOCK_IF_THEN => format_block_if_then(node, ctx),
         SyntaxKind::BLOCK_IF_ELSE => format_block_if_else(node, ctx),
         SyntaxKind::BLOCK_TRY_TABLE => format_block_try_table(node, ctx),
+        SyntaxKind::BLOCK_TRY => format_block_try(node, ctx),
+        SyntaxKind::BLOCK_TRY_DO => format_block_try_do(node, ctx),
+        SyntaxKind::BLOCK_TRY_CATCH => format_block_try_catch(node, ctx),
+        SyntaxKind::BLOCK_TRY_CATCH_ALL => format_block_try_catch_all(node, ctx),
+        SyntaxKind::BLOCK_TRY_DELEGATE => format_block_try_delegate(node, ctx),
         SyntaxKind::CATCH => format_catch(node, ctx),
         SyntaxKind::CATCH_ALL => format_catch_all(node, ctx),
         SyntaxKind::MEM_ARG => format_mem_arg(node, ctx),
@@ -220,6 +226,8 @@ pub(crate) fn format_node<'a>(node: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a>
         SyntaxKind::TABLE_USE => format_table_use(node, ctx),
         SyntaxKind::DATA => format_data(node, ctx),
         SyntaxKind::MODULE => format_module(node, ctx),
+        SyntaxKind::MODULE_BINARY => format_module_binary(node, ctx),
+        SyntaxKind::MODULE_QUOTE => format_module_quote(node, ctx),
         SyntaxKind::MODULE_FIELD_DATA => format_module_field_data(node, ctx),
         SyntaxKind::MODULE_FIELD_ELEM => format_module_field_elem(node, ctx),
         SyntaxKind::MODULE_FIELD_EXPORT => format_module_field_export(node, ctx),
@@ -232,6 +240,15 @@ pub(crate) fn format_node<'a>(node: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a>
         SyntaxKind::MODULE_FIELD_TAG => format_module_field_tag(node, ctx),
         SyntaxKind::TYPE_DEF => format_type_def(node, ctx),
         SyntaxKind::REC_TYPE => format_rec_type(node, ctx),
+        SyntaxKind::ACTION_GET => format_action_get(node, ctx),
+        SyntaxKind::ACTION_INVOKE => format_action_invoke(node, ctx),
+        SyntaxKind::ASSERT_EXHAUSTION => format_assert_exhaustion(node, ctx),
+        SyntaxKind::ASSERT_INVALID => format_assert_invalid(node, ctx),
+        SyntaxKind::ASSERT_MALFORMED => format_assert_malformed(node, ctx),
+        SyntaxKind::ASSERT_RETURN => format_assert_return(node, ctx),
+        SyntaxKind::ASSERT_TRAP => format_assert_trap(node, ctx),
+        SyntaxKind::ASSERT_UNLINKABLE => format_assert_unlinkable(node, ctx),
+        SyntaxKind::REGISTER => format_register(node, ctx),
         SyntaxKind::ROOT => format_root(node, ctx),
         SyntaxKind::WHITESPACE
         | SyntaxKind::LINE_COMMENT
@@ -265,11 +282,11 @@ pub(crate) fn format_root<'a>(root: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a>
     while let Some((index, node_or_token)) = nodes_or_tokens.next() {
         let kind = node_or_token.kind();
         match node_or_token {
-            NodeOrToken::Node(module) => {
-                if should_ignore(module, root, ctx) {
-                    reflow(module.green().to_string(), &mut docs);
+            NodeOrToken::Node(node) => {
+                if should_ignore(node, root, ctx) {
+                    reflow(node.green().to_string(), &mut docs);
                 } else {
-                    docs.push(format_module(module, ctx));
+                    docs.push(format_node(node, ctx));
                 }
             }
             NodeOrToken::Token(token) => match kind {
@@ -387,6 +387,64 @@ pub(crate) fn format_module<'a>(module: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<
     }
 }
 
+pub(crate) fn format_module_binary<'a>(module_binary: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    format_module_with_strings(module_binary, "binary", ctx)
+}
+
+pub(crate) fn format_module_quote<'a>(module_quote: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    format_module_with_strings(module_quote, "quote", ctx)
+}
+
+/// Strings of binary or quoted module are kept on a single line if they fit,
+/// otherwise each string is placed on its own line.
+fn format_module_with_strings<'a>(module: AmberNode<'a>, kind: &'static str, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    let mut docs = BumpVec::with_capacity_in(2, &ctx.bump);
+    let mut trivias = BumpVec::new_in(&ctx.bump);
+    if let Some(l_paren) = module.tokens_by_kind(L_PAREN).next() {
+        docs.push(Doc::char('('));
+        ctx.format_trivias_after_token(l_paren, module, &mut trivias);
+    }
+    let mut keywords = module.tokens_by_kind(KEYWORD);
+    if let Some(keyword) = keywords.next() {
+        docs.append(&mut trivias);
+        docs.push(Doc::text("module"));
+        ctx.format_trivias_after_token(keyword, module, &mut trivias);
+    }
+    if let Some(ident) = module.tokens_by_kind(IDENT).next() {
+        if trivias.is_empty() {
+            docs.push(Doc::space());
+        } else {
+            docs.append(&mut trivias);
+        }
+        docs.push(Doc::text(ident.text()));
+        ctx.format_trivias_after_token(ident, module, &mut trivias);
+    }
+    if let Some(keyword) = keywords.next() {
+        if trivias.is_empty() {
+            docs.push(Doc::space());
+        } else {
+            docs.append(&mut trivias);
+        }
+        docs.push(Doc::text(kind));
+        ctx.format_trivias_after_token(keyword, module, &mut trivias);
+    }
+    module.tokens_by_kind(STRING).for_each(|string| {
+        if trivias.is_empty() {
+            docs.push(Doc::line_or_space());
+        } else {
+            docs.append(&mut trivias);
+        }
+        docs.push(Doc::text(string.text()));
+        ctx.format_trivias_after_token(string, module, &mut trivias);
+    });
+    docs.append(&mut trivias);
+    Doc::slice(ctx.bump.alloc_slice_fill_iter([
+        Doc::slice(docs.into_bump_slice()).nest(ctx.indent_width),
+        ctx.format_right_paren_on_same_line(module),
+    ]))
+    .group()
+}
+
 pub(crate) fn format_module_field_data<'a>(module_field_data: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
     let mut docs = BumpVec::with_capacity_in(2, &ctx.bump);
     let mut trivias = BumpVec::new_in(&ctx.bump);
@@ -0,0 +1,88 @@
+use super::*;
+use bumpalo::collections::Vec as BumpVec;
+use tiny_pretty::Doc;
+use wat_syntax::SyntaxKind::*;
+
+pub(crate) fn format_action_get<'a>(action_get: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    format_command(action_get, "get", ctx)
+}
+
+pub(crate) fn format_action_invoke<'a>(action_invoke: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    format_command(action_invoke, "invoke", ctx)
+}
+
+pub(crate) fn format_assert_exhaustion<'a>(assert_exhaustion: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    format_command(assert_exhaustion, "assert_exhaustion", ctx)
+}
+
+pub(crate) fn format_assert_invalid<'a>(assert_invalid: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    format_command(assert_invalid, "assert_invalid", ctx)
+}
+
+pub(crate) fn format_assert_malformed<'a>(assert_malformed: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    format_command(assert_malformed, "assert_malformed", ctx)
+}
+
+pub(crate) fn format_assert_return<'a>(assert_return: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    format_command(assert_return, "assert_return", ctx)
+}
+
+pub(crate) fn format_assert_trap<'a>(assert_trap: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    format_command(assert_trap, "assert_trap", ctx)
+}
+
+pub(crate) fn format_assert_unlinkable<'a>(assert_unlinkable: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    format_command(assert_unlinkable, "assert_unlinkable", ctx)
+}
+
+pub(crate) fn format_register<'a>(register: AmberNode<'a>, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    format_command(register, "register", ctx)
+}
+
+/// Script commands are kept on a single line,
+/// unless they contain a module which is always placed on its own lines.
+fn format_command<'a>(command: AmberNode<'a>, keyword: &'static str, ctx: &'a Ctx<'a>) -> Doc<'a> {
+    let mut docs = BumpVec::with_capacity_in(2, &ctx.bump);
+    let mut trivias = BumpVec::new_in(&ctx.bump);
+    if let Some(l_paren) = command.tokens_by_kind(L_PAREN).next() {
+        docs.push(Doc::char('('));
+        ctx.format_trivias_after_token(l_paren, command, &mut trivias);
+    }
+    if let Some(keyword_token) = command.tokens_by_kind(KEYWORD).next() {
+        docs.append(&mut trivias);
+        docs.push(Doc::text(keyword));
+        ctx.format_trivias_after_token(keyword_token, command, &mut trivias);
+    }
+    let has_module = command
+        .children_by_kind([MODULE, MODULE_BINARY, MODULE_QUOTE])
+        .next()
+        .is_some();
+    command
+        .children_with_tokens()
+        .filter(|node_or_token| match node_or_token {
+            NodeOrToken::Node(..) => true,
+            NodeOrToken::Token(token) => matches!(token.kind(), IDENT | STRING),
+        })
+        .for_each(|node_or_token| {
+            if trivias.is_empty() {
+                docs.push(if has_module { Doc::hard_line() } else { Doc::space() });
+            } else {
+                docs.append(&mut trivias);
+            }
+            match node_or_token {
+                NodeOrToken::Node(node) => {
+                    docs.push(format_node(node, ctx));
+                    ctx.format_trivias_after_node(node, command, &mut trivias);
+                }
+                NodeOrToken::Token(token) => {
+                    docs.push(Doc::text(token.text()));
+                    ctx.format_trivias_after_token(token, command, &mut trivias);
+                }
+            }
+        });
+    docs.append(&mut trivias);
+    Doc::slice(ctx.bump.alloc_slice_fill_iter([
+        Doc::slice(docs.into_bump_slice()).nest(ctx.indent_width),
+        ctx.format_right_paren_on_same_line(command),
+    ]))
+}
@@ -0,0 +1,187 @@
+use crate::render::Summary;
+use lspt::DiagnosticSeverity;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+use wat_service::{FileDiagnostics, LanguageService, LintLevel, ReportFormat, ServiceConfig};
+
+mod render;
+
+const HELP: &str = "\
+Usage: wat-lint [options] <files or globs>...
+
+Directories are searched recursively for `.wat` and `.wast` files.
+
+Options:
+  -c, --config <file>    Load configuration from a JSON or TOML file instead of discovering
+      --fail-on <level>  Exit with non-zero code if there're diagnostics at or above
+                         this level: `deny` (default), `warn`, `hint` or `allow`
+      --format <format>  Output format: `human` (default), `json`, `sarif`, `github`
+                         or `checkstyle`
+  -h, --help             Print help
+  -v, --version          Print version";
+
+fn main() -> ExitCode {
+    let mut config_file = None;
+    let mut fail_on = LintLevel::Deny;
+    let mut format = None;
+    let mut patterns = vec![];
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match &*arg {
+            "-v" | "-V" | "--version" => {
+                println!("wat-lint v{}", env!("CARGO_PKG_VERSION"));
+                return ExitCode::SUCCESS;
+            }
+            "-h" | "--help" => {
+                println!("{HELP}");
+                return ExitCode::SUCCESS;
+            }
+            "-c" | "--config" => match args.next() {
+                Some(file) => config_file = Some(file),
+                None => return fatal("missing value for `--config`"),
+            },
+            "--fail-on" => match args.next().as_deref().and_then(parse_lint_level) {
+                Some(level) => fail_on = level,
+                None => return fatal("`--fail-on` expects one of `deny`, `warn`, `hint` or `allow`"),
+            },
+            "--format" => match args.next().as_deref() {
+                Some("human") => format = None,
+                Some("json") => format = Some(ReportFormat::Json),
+                Some("sarif") => format = Some(ReportFormat::Sarif),
+                Some("github") => format = Some(ReportFormat::GitHub),
+                Some("checkstyle") => format = Some(ReportFormat::Checkstyle),
+                _ => {
+                    return fatal("`--format` expects one of `human`, `json`, `sarif`, `github` or `checkstyle`");
+                }
+            },
+            _ if arg.starts_with('-') && arg.len() > 1 => return fatal(&format!("unknown option `{arg}`")),
+            _ => patterns.push(arg),
+        }
+    }
+
+    let config_file = match config_file
+        .map(|file| wat_project::load_config_file(Path::new(&file)).map(|config| (PathBuf::from(file), config)))
+        .transpose()
+    {
+        Ok(config_file) => config_file,
+        Err(err) => return fatal(&err.to_string()),
+    };
+    let files = match wat_project::collect_files(&patterns) {
+        Ok(files) if files.is_empty() => return fatal("no input files"),
+        Ok(files) => files,
+        Err(err) => return fatal(&err.to_string()),
+    };
+
+    let mut service = LanguageService::default();
+    let mut config_resolver = wat_project::ConfigResolver::default();
+    let mut sources = Vec::with_capacity(files.len());
+    for file in files {
+        let text = match fs::read_to_string(&file) {
+            Ok(text) => text,
+            Err(err) => return fatal(&format!("failed to read `{}`: {err}", file.display())),
+        };
+        let config = match resolve_config(&file, config_file.as_ref(), &mut config_resolver) {
+            Ok(config) => config,
+            Err(message) => return fatal(&message),
+        };
+        let uri = wat_project::path_to_uri(&file);
+        service.commit(uri.clone(), text.clone());
+        sources.push((file, uri, text, config));
+    }
+    // modules linked via `imports` should be available even if they aren't part of input
+    sources.iter().for_each(|(file, _, _, config)| {
+        config.imports.values().for_each(|import| {
+            let path = import
+                .strip_prefix("file://")
+                .map_or_else(|| file.parent().unwrap_or(file).join(import), PathBuf::from);
+            let path = wat_project::normalize_path(&path);
+            let uri = wat_project::path_to_uri(&path);
+            if sources.iter().all(|(_, source_uri, _, _)| source_uri != &uri)
+                && let Ok(text) = fs::read_to_string(&path)
+            {
+                service.commit(uri, text);
+            }
+        });
+    });
+    sources
+        .iter()
+        .for_each(|(_, uri, _, config)| service.set_config(uri.clone(), Some(config.clone())));
+
+    let mut summary = Summary::default();
+    let reports = sources
+        .iter()
+        .map(|(file, uri, text, _)| {
+            let mut diagnostics = service.publish_diagnostics(uri.clone()).diagnostics;
+            diagnostics.sort_by_key(|diagnostic| (diagnostic.range.start.line, diagnostic.range.start.character));
+            diagnostics.iter().for_each(|diagnostic| summary.add(diagnostic));
+            (file, text, diagnostics)
+        })
+        .collect::<Vec<_>>();
+    if let Some(format) = format {
+        let paths = reports
+            .iter()
+            .map(|(file, _, _)| file.to_string_lossy().replace('\\', "/"))
+            .collect::<Vec<_>>();
+        let files = reports
+            .iter()
+            .zip(&paths)
+            .map(|((_, _, diagnostics), path)| FileDiagnostics { path, diagnostics })
+            .collect::<Vec<_>>();
+        let output = wat_service::serialize_diagnostics(format, &files);
+        if output.is_empty() || output.ends_with('\n') {
+            print!("{output}");
+        } else {
+            println!("{output}");
+        }
+    } else {
+        reports.iter().for_each(|(file, text, diagnostics)| {
+            diagnostics
+                .iter()
+                .for_each(|diagnostic| render::print(diagnostic, file, text));
+        });
+        summary.print();
+    }
+
+    let threshold = match fail_on {
+        LintLevel::Allow => return ExitCode::SUCCESS,
+        LintLevel::Hint => DiagnosticSeverity::Hint,
+        LintLevel::Warn => DiagnosticSeverity::Warning,
+        LintLevel::Deny => DiagnosticSeverity::Error,
+    };
+    if summary.has_severity_at_least(threshold) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn fatal(message: &str) -> ExitCode {
+    eprintln!("error: {message}");
+    ExitCode::from(2)
+}
+
+fn parse_lint_level(level: &str) -> Option<LintLevel> {
+    serde_json::from_value(serde_json::Value::String(level.into())).ok()
+}
+
+/// Resolve configuration of the given file from the configuration file specified via command line,
+/// or from discovered configuration files if not specified.
+fn resolve_config(
+    file: &Path,
+    config_file: Option<&(PathBuf, serde_json::Value)>,
+    config_resolver: &mut wat_project::ConfigResolver,
+) -> Result<ServiceConfig, String> {
+    let config = if let Some((config_file, config)) = config_file {
+        let mut resolved = serde_json::Value::Null;
+        wat_project::apply_config(&mut resolved, config, config_file, file);
+        resolved
+    } else if let Some(config) = config_resolver.resolve(file).map_err(|err| err.to_string())? {
+        config
+    } else {
+        return Ok(ServiceConfig::default());
+    };
+    serde_json::from_value(config).map_err(|err| format!("invalid config for `{}`: {err}", file.display()))
+}
@@ -0,0 +1,145 @@
+use lspt::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, StringOrMarkupContent};
+use std::path::Path;
+
+/// Print diagnostic in the style of rustc, with source snippet.
+///
+/// ```text
+/// warning[unused]: func `$f` is never used
+///  --> module.wat:2:9
+///   |
+/// 2 |   (func $f)
+///   |         ^^
+/// ```
+pub fn print(diagnostic: &Diagnostic, file: &Path, text: &str) {
+    let lines = text.lines().collect::<Vec<_>>();
+    let start = &diagnostic.range.start;
+    let end = &diagnostic.range.end;
+    let line = lines.get(start.line as usize).copied().unwrap_or_default();
+    let start_col = utf16_to_char_col(line, start.character);
+    let end_col = if end.line == start.line {
+        utf16_to_char_col(line, end.character)
+    } else {
+        line.chars().count()
+    };
+    let gutter = " ".repeat((start.line + 1).to_string().len());
+
+    let severity = render_severity(diagnostic.severity);
+    let message = match &diagnostic.message {
+        StringOrMarkupContent::String(message) => message,
+        StringOrMarkupContent::MarkupContent(content) => &content.value,
+    };
+    match &diagnostic.code {
+        Some(NumberOrString::String(code)) => println!("{severity}[{code}]: {message}"),
+        Some(NumberOrString::Integer(code)) => println!("{severity}[{code}]: {message}"),
+        None => println!("{severity}: {message}"),
+    }
+    println!("{gutter}--> {}", render_location(file, line, start));
+    println!("{gutter} |");
+    println!("{} | {line}", start.line + 1);
+    // keep tabs so the marker is aligned with source code
+    let indent = line
+        .chars()
+        .take(start_col)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect::<String>();
+    println!(
+        "{gutter} | {indent}{}",
+        "^".repeat(end_col.saturating_sub(start_col).max(1))
+    );
+    diagnostic.related_information.iter().flatten().for_each(|info| {
+        match lines.get(info.location.range.start.line as usize) {
+            Some(line) => println!(
+                "{gutter} = note: {} at {}",
+                info.message,
+                render_location(file, line, &info.location.range.start),
+            ),
+            None => println!("{gutter} = note: {}", info.message),
+        }
+    });
+    println!();
+}
+
+fn render_severity(severity: Option<DiagnosticSeverity>) -> &'static str {
+    match severity {
+        Some(DiagnosticSeverity::Error) | None => "error",
+        Some(DiagnosticSeverity::Warning) => "warning",
+        Some(DiagnosticSeverity::Information) => "info",
+        Some(DiagnosticSeverity::Hint) => "hint",
+    }
+}
+
+fn render_location(file: &Path, line: &str, position: &Position) -> String {
+    format!(
+        "{}:{}:{}",
+        file.display(),
+        position.line + 1,
+        utf16_to_char_col(line, position.character) + 1,
+    )
+}
+
+/// Positions in diagnostics are measured in UTF-16 code units.
+fn utf16_to_char_col(line: &str, character: u32) -> usize {
+    let mut utf16_col = 0;
+    line.chars()
+        .take_while(|c| {
+            utf16_col += c.len_utf16() as u32;
+            utf16_col <= character
+        })
+        .count()
+}
+
+#[derive(Default)]
+pub struct Summary {
+    errors: usize,
+    warnings: usize,
+    hints: usize,
+    /// The most severe level among reported diagnostics.
+    most_severe: Option<DiagnosticSeverity>,
+}
+
+impl Summary {
+    pub fn add(&mut self, diagnostic: &Diagnostic) {
+        let severity = diagnostic.severity.unwrap_or(DiagnosticSeverity::Error);
+        match severity {
+            DiagnosticSeverity::Error => self.errors += 1,
+            DiagnosticSeverity::Warning => self.warnings += 1,
+            _ => self.hints += 1,
+        }
+        if self
+            .most_severe
+            .is_none_or(|most_severe| (severity as u8) < (most_severe as u8))
+        {
+            self.most_severe = Some(severity);
+        }
+    }
+
+    pub fn has_severity_at_least(&self, threshold: DiagnosticSeverity) -> bool {
+        self.most_severe
+            .is_some_and(|most_severe| (most_severe as u8) <= (threshold as u8))
+    }
+
+    pub fn print(&self) {
+        if self.errors == 0 && self.warnings == 0 && self.hints == 0 {
+            return;
+        }
+        let mut counts = vec![];
+        if self.errors > 0 {
+            counts.push(pluralize(self.errors, "error"));
+        }
+        if self.warnings > 0 {
+            counts.push(pluralize(self.warnings, "warning"));
+        }
+        if self.hints > 0 {
+            counts.push(pluralize(self.hints, "hint"));
+        }
+        println!("found {}", counts.join(", "));
+    }
+}
+
+fn pluralize(count: usize, word: &str) -> String {
+    if count == 1 {
+        format!("{count} {word}")
+    } else {
+        format!("{count} {word}s")
+    }
+}
@@ -0,0 +1,3 @@
+(module
+  (func (export "f") (import "env" "f") (param i32))
+  (func (export "g") (import "env" "g")))
@@ -0,0 +1,5 @@
+(module
+  (func $unused)
+  (func (export "f") (result i32)
+    (local $x i32)
+    i32.const 0))
@@ -0,0 +1,139 @@
+use assert_cmd::Command;
+use predicates::str::contains;
+
+fn wat_lint() -> Command {
+    let mut command = Command::new(assert_cmd::cargo_bin!("wat-lint"));
+    command.current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"));
+    command
+}
+
+#[test]
+fn clean() {
+    wat_lint().arg("clean.wat").assert().success().stdout("");
+}
+
+#[test]
+fn render_snippet() {
+    wat_lint().arg("invalid.wat").assert().code(1).stdout(
+        "\
+error[undef]: cannot find func `$missing` in this scope
+ --> invalid.wat:3:10
+  |
+3 |     call $missing))
+  |          ^^^^^^^^
+
+error[type-check]: expected types [i32], found [] at the end
+ --> invalid.wat:3:18
+  |
+3 |     call $missing))
+  |                  ^
+
+found 2 errors
+",
+    );
+}
+
+#[test]
+fn warnings_pass_by_default() {
+    wat_lint()
+        .arg("unused.wat")
+        .assert()
+        .success()
+        .stdout(contains("warning[unused]: func `$unused` is never used"))
+        .stdout(contains("found 2 warnings"));
+}
+
+#[test]
+fn fail_on_warn() {
+    wat_lint().args(["--fail-on", "warn", "unused.wat"]).assert().code(1);
+}
+
+#[test]
+fn fail_on_allow() {
+    wat_lint()
+        .args(["--fail-on", "allow", "invalid.wat"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn config_file() {
+    wat_lint()
+        .args(["--fail-on", "warn", "--config", "allow-unused.json", "unused.wat"])
+        .assert()
+        .success()
+        .stdout("");
+}
+
+#[test]
+fn glob() {
+    wat_lint()
+        .arg("*.wat")
+        .assert()
+        .code(1)
+        .stdout(contains("--> invalid.wat:3:10"))
+        .stdout(contains("--> unused.wat:2:9"))
+        .stdout(contains("found 2 errors, 2 warnings"));
+}
+
+#[test]
+fn linked_import() {
+    wat_lint()
+        .args(["--config", "linked/config.json", "linked/main.wat"])
+        .assert()
+        .code(1)
+        .stdout(contains("error[undef]: module \"env\" doesn't export \"g\""))
+        .stdout(contains("found 1 error"));
+}
+
+#[test]
+fn format_github() {
+    wat_lint()
+        .args(["--format", "github", "invalid.wat", "clean.wat"])
+        .assert()
+        .code(1)
+        .stdout(
+            "\
+::error file=invalid.wat,line=3,col=10,endLine=3,endColumn=18,title=undef::cannot find func `$missing` in this scope
+::error file=invalid.wat,line=3,col=18,endLine=3,endColumn=19,title=type-check::expected types [i32], found [] at the end
+",
+        );
+}
+
+#[test]
+fn format_json() {
+    wat_lint()
+        .args(["--format", "json", "clean.wat"])
+        .assert()
+        .success()
+        .stdout("[]\n");
+}
+
+#[test]
+fn missing_file() {
+    wat_lint()
+        .arg("missing.wat")
+        .assert()
+        .code(2)
+        .stderr("error: `missing.wat` doesn't exist\n");
+}
+
+#[test]
+fn invalid_fail_on() {
+    wat_lint().args(["--fail-on", "error", "clean.wat"]).assert().code(2);
+}
+
+#[test]
+fn discover_config() {
+    wat_lint()
+        .arg("project")
+        .assert()
+        .code(1)
+        .stdout(contains(
+            "error[unused]: func `$unused` is never used\n --> project/main.wat:2:9",
+        ))
+        .stdout(contains(
+            "hint[unused]: func `$unused` is never used\n --> project/nested/lib.wat:2:9",
+        ))
+        .stdout(contains("found 1 error, 1 hint"));
+}
@@ -52,6 +52,8 @@ pub static KW_THEN: LazyLock<GreenElement> = LazyLock::new(|| GreenToken::new(Sy
 pub static KW_ELSE: LazyLock<GreenElement> = LazyLock::new(|| GreenToken::new(SyntaxKind::KEYWORD, "else").into());
 pub static KW_TRY_TABLE: LazyLock<GreenElement> =
     LazyLock::new(|| GreenToken::new(SyntaxKind::KEYWORD, "try_table").into());
+pub static KW_TRY: LazyLock<GreenElement> = LazyLock::new(|| GreenToken::new(SyntaxKind::KEYWORD, "try").into());
+pub static KW_DO: LazyLock<GreenElement> = LazyLock::new(|| GreenToken::new(SyntaxKind::KEYWORD, "do").into());
 pub static KW_END: LazyLock<GreenElement> = LazyLock::new(|| GreenToken::new(SyntaxKind::KEYWORD, "end").into());
 
 pub static TYPE_KW_I32: LazyLock<GreenElement> =
@@ -146,6 +146,126 @@ impl Parser<'_> {
         Some(self.finish_node(BLOCK_TRY_TABLE, mark))
     }
 
+    fn parse_block_try_folded(&mut self, mark: NodeMark) -> Option<GreenNode> {
+        self.eat(IDENT);
+        if let Some(node) = self.try_parse_with_trivias(Self::parse_type_use) {
+            self.add_child(node);
+        }
+
+        if let Some(node) = self.try_parse_with_trivias(Self::parse_try_do_block) {
+            self.add_child(node);
+        } else {
+            self.report_missing(Message::Name("do block"));
+        }
+        while let Some(node) = self.try_parse_with_trivias(Self::parse_try_handler_folded) {
+            self.add_child(node);
+        }
+
+        self.expect_right_paren();
+        Some(self.finish_node(BLOCK_TRY, mark))
+    }
+
+    fn parse_block_try_sequence(&mut self, mark: NodeMark) -> Option<GreenNode> {
+        self.eat(IDENT);
+        if let Some(node) = self.try_parse_with_trivias(Self::parse_type_use) {
+            self.add_child(node);
+        }
+
+        let do_mark = self.start_node();
+        while !self.should_exit_block_try_body() && self.recover(Self::parse_instr) {}
+        let node = self.finish_node(BLOCK_TRY_DO, do_mark);
+        self.add_child(node);
+
+        while let Some(keyword) = self.try_parse_with_trivias(|parser| {
+            parser
+                .lexer
+                .next(KEYWORD)
+                .filter(|token| matches!(token.text, "catch" | "catch_all"))
+        }) {
+            let handler_mark = self.start_node();
+            let kind = if keyword.text == "catch" {
+                self.add_child(keyword);
+                if let Some(node) = self.try_parse_with_trivias(Self::parse_index) {
+                    self.add_child(node);
+                } else {
+                    self.report_missing(Message::Name("tag index"));
+                }
+                BLOCK_TRY_CATCH
+            } else {
+                self.add_child(keyword);
+                BLOCK_TRY_CATCH_ALL
+            };
+            while !self.should_exit_block_try_body() && self.recover(Self::parse_instr) {}
+            let node = self.finish_node(kind, handler_mark);
+            self.add_child(node);
+        }
+
+        if let Some(keyword) = self.try_parse_with_trivias(|parser| parser.lexer.keyword("delegate")) {
+            let delegate_mark = self.start_node();
+            self.add_child(keyword);
+            if let Some(node) = self.try_parse_with_trivias(Self::parse_index) {
+                self.add_child(node);
+            } else {
+                self.report_missing(Message::Name("label index"));
+            }
+            let node = self.finish_node(BLOCK_TRY_DELEGATE, delegate_mark);
+            self.add_child(node);
+            return Some(self.finish_node(BLOCK_TRY, mark));
+        }
+
+        if !self.recover(Self::parse_end_keyword) {
+            self.report_missing(Message::Str("end"));
+        }
+        self.eat(IDENT);
+        Some(self.finish_node(BLOCK_TRY, mark))
+    }
+
+    fn parse_try_do_block(&mut self) -> Option<GreenNode> {
+        let mark = self.start_node();
+        self.lexer.next(L_PAREN)?;
+        self.add_child(green::L_PAREN.clone());
+        self.parse_trivias();
+        self.lexer.keyword("do")?;
+        self.add_child(green::KW_DO.clone());
+        while self.recover(Self::parse_instr) {}
+        self.expect_right_paren();
+        Some(self.finish_node(BLOCK_TRY_DO, mark))
+    }
+
+    fn parse_try_handler_folded(&mut self) -> Option<GreenNode> {
+        let mark = self.start_node();
+        self.lexer.next(L_PAREN)?;
+        self.add_child(green::L_PAREN.clone());
+        self.parse_trivias();
+        let keyword = self.lexer.next(KEYWORD)?;
+        match keyword.text {
+            "catch" => {
+                self.add_child(keyword);
+                if !self.recover(Self::parse_index) {
+                    self.report_missing(Message::Name("tag index"));
+                }
+                while self.recover(Self::parse_instr) {}
+                self.expect_right_paren();
+                Some(self.finish_node(BLOCK_TRY_CATCH, mark))
+            }
+            "catch_all" => {
+                self.add_child(keyword);
+                while self.recover(Self::parse_instr) {}
+                self.expect_right_paren();
+                Some(self.finish_node(BLOCK_TRY_CATCH_ALL, mark))
+            }
+            "delegate" => {
+                self.add_child(keyword);
+                if !self.recover(Self::parse_index) {
+                    self.report_missing(Message::Name("label index"));
+                }
+                self.expect_right_paren();
+                Some(self.finish_node(BLOCK_TRY_DELEGATE, mark))
+            }
+            _ => None,
+        }
+    }
+
     pub(super) fn parse_catch(&mut self) -> Option<GreenNode> {
         let mark = self.start_node();
         self.lexer.next(L_PAREN)?;
@@ -264,6 +384,10 @@ impl Parser<'_> {
                     self.add_child(green::KW_TRY_TABLE.clone());
                     self.parse_block_try_table_folded(mark)
                 }
+                "try" => {
+                    self.add_child(green::KW_TRY.clone());
+                    self.parse_block_try_folded(mark)
+                }
                 _ => {
                     self.recognize_instr_name(token);
                     self.parse_plain_instr_folded(mark)
@@ -290,6 +414,10 @@ impl Parser<'_> {
                     self.add_child(green::KW_TRY_TABLE.clone());
                     self.parse_block_try_table_sequence(mark)
                 }
+                "try" => {
+                    self.add_child(green::KW_TRY.clone());
+                    self.parse_block_try_sequence(mark)
+                }
                 _ => {
                     self.recognize_instr_name(token);
                     self.parse_plain_instr_sequence(mark, checkpoint)
@@ -398,7 +526,7 @@ impl Parser<'_> {
             if parser
                 .lexer
                 .peek(KEYWORD)
-                .is_some_and(|token| matches!(token.text, "end" | "else"))
+                .is_some_and(|token| matches!(token.text, "end" | "else" | "catch" | "catch_all" | "delegate"))
             {
                 None
             } else {
@@ -495,4 +623,10 @@ impl Parser<'_> {
         self.lexer.reset(checkpoint);
         result
     }
+
+    fn should_exit_block_try_body(&mut self) -> bool {
+        self.lexer
+            .peek(KEYWORD)
+            .is_some_and(|token| matches!(token.text, "end" | "catch" | "catch_all" | "delegate"))
+    }
 }
@@ -19,6 +19,8 @@ pub(super) struct Lexer<'s> {
     source: &'s str,
     input: &'s str,
     pub top_level: bool,
+    /// Whether `nan:canonical` and `nan:arithmetic` are accepted, which only appear in script results.
+    pub nan_pattern: bool,
     annotation_depth: u32,
 }
 
@@ -28,6 +30,7 @@ impl<'s> Lexer<'s> {
             source,
             input: source,
             top_level: false,
+            nan_pattern: false,
             annotation_depth: 0,
         }
     }
@@ -343,14 +346,21 @@ impl<'s> Lexer<'s> {
         {
             self.input = rest;
         } else {
-            let rest = self
-                .input
-                .strip_prefix("nan")
-                .filter(|rest| rest.starts_with(":0x") || !rest.starts_with(is_id_char))?;
+            let rest = self.input.strip_prefix("nan").filter(|rest| {
+                rest.starts_with(":0x")
+                    || !rest.starts_with(is_id_char)
+                    || self.nan_pattern && (rest.starts_with(":canonical") || rest.starts_with(":arithmetic"))
+            })?;
             self.input = rest;
             if let Some(rest) = rest.strip_prefix(":0x") {
                 self.input = rest;
                 valid &= self.unsigned_hex().is_some();
+            } else if self.nan_pattern
+                && let Some(rest) = rest
+                    .strip_prefix(":canonical")
+                    .or_else(|| rest.strip_prefix(":arithmetic"))
+            {
+                self.input = rest;
             }
         }
         if self.input.starts_with(is_id_char) {
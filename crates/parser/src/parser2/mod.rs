@@ -8,6 +8,7 @@ mod helpers;
 mod instr;
 mod lexer;
 mod module;
+mod script;
 mod ty;
 
 /// Parse the code into green node.
@@ -63,10 +64,17 @@ pub fn parse_as(kind: SyntaxKind, source: &str) -> Option<(GreenNode, Vec<Syntax
                 parser.parse_instr()
             }
         }
-        SyntaxKind::BLOCK_BLOCK | SyntaxKind::BLOCK_LOOP | SyntaxKind::BLOCK_IF | SyntaxKind::BLOCK_TRY_TABLE => {
-            parser.parse_instr()
-        }
-        SyntaxKind::BLOCK_IF_THEN | SyntaxKind::BLOCK_IF_ELSE => None,
+        SyntaxKind::BLOCK_BLOCK
+        | SyntaxKind::BLOCK_LOOP
+        | SyntaxKind::BLOCK_IF
+        | SyntaxKind::BLOCK_TRY_TABLE
+        | SyntaxKind::BLOCK_TRY => parser.parse_instr(),
+        SyntaxKind::BLOCK_IF_THEN
+        | SyntaxKind::BLOCK_IF_ELSE
+        | SyntaxKind::BLOCK_TRY_DO
+        | SyntaxKind::BLOCK_TRY_CATCH
+        | SyntaxKind::BLOCK_TRY_CATCH_ALL
+        | SyntaxKind::BLOCK_TRY_DELEGATE => None,
         SyntaxKind::CATCH | SyntaxKind::CATCH_ALL => parser.parse_catch(),
         SyntaxKind::MEM_ARG => parser.parse_mem_arg(),
         SyntaxKind::ON_CLAUSE => parser.parse_on_clause(),
@@ -94,7 +102,7 @@ pub fn parse_as(kind: SyntaxKind, source: &str) -> Option<(GreenNode, Vec<Syntax
         SyntaxKind::ELEM_EXPR => parser.parse_elem_expr(),
         SyntaxKind::TABLE_USE => parser.parse_table_use(),
         SyntaxKind::DATA => parser.parse_data(),
-        SyntaxKind::MODULE => parser.parse_module(),
+        SyntaxKind::MODULE | SyntaxKind::MODULE_BINARY | SyntaxKind::MODULE_QUOTE => parser.parse_module(),
         SyntaxKind::MODULE_FIELD_DATA
         | SyntaxKind::MODULE_FIELD_ELEM
         | SyntaxKind::MODULE_FIELD_EXPORT
@@ -107,6 +115,14 @@ pub fn parse_as(kind: SyntaxKind, source: &str) -> Option<(GreenNode, Vec<Syntax
         | SyntaxKind::MODULE_FIELD_TAG
         | SyntaxKind::TYPE_DEF
         | SyntaxKind::REC_TYPE => parser.parse_module_field(),
+        SyntaxKind::ACTION_GET | SyntaxKind::ACTION_INVOKE => parser.parse_action(),
+        SyntaxKind::ASSERT_EXHAUSTION
+        | SyntaxKind::ASSERT_INVALID
+        | SyntaxKind::ASSERT_MALFORMED
+        | SyntaxKind::ASSERT_RETURN
+        | SyntaxKind::ASSERT_TRAP
+        | SyntaxKind::ASSERT_UNLINKABLE
+        | SyntaxKind::REGISTER => parser.parse_module(),
         SyntaxKind::ROOT => Some(parser.parse_root()),
         SyntaxKind::WHITESPACE
         | SyntaxKind::LINE_COMMENT
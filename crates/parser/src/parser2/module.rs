@@ -247,14 +247,68 @@ impl Parser<'_> {
         self.parse_trivias();
         let keyword = self.lexer.next(KEYWORD)?;
 
+        let top_level = self.lexer.top_level;
         self.lexer.top_level = false;
         let node = match keyword.text {
             "module" => {
                 self.add_child(keyword);
                 self.eat(IDENT);
-                while self.recover(Self::parse_module_field) {}
-                self.expect_right_paren();
-                Some(self.finish_node(MODULE, mark))
+                if let Some(keyword) = self.try_parse_with_trivias(|parser| {
+                    parser
+                        .lexer
+                        .next(KEYWORD)
+                        .filter(|token| matches!(token.text, "binary" | "quote"))
+                }) {
+                    let kind = if keyword.text == "binary" {
+                        MODULE_BINARY
+                    } else {
+                        MODULE_QUOTE
+                    };
+                    self.add_child(keyword);
+                    while self.eat(STRING) {}
+                    self.expect_right_paren();
+                    Some(self.finish_node(kind, mark))
+                } else {
+                    while self.recover(Self::parse_module_field) {}
+                    self.expect_right_paren();
+                    Some(self.finish_node(MODULE, mark))
+                }
+            }
+            "register" => {
+                self.add_child(keyword);
+                Some(self.parse_register(mark))
+            }
+            "invoke" => {
+                self.add_child(keyword);
+                Some(self.parse_action_invoke(mark))
+            }
+            "get" => {
+                self.add_child(keyword);
+                Some(self.parse_action_get(mark))
+            }
+            "assert_return" => {
+                self.add_child(keyword);
+                Some(self.parse_assert_return(mark))
+            }
+            "assert_trap" => {
+                self.add_child(keyword);
+                Some(self.parse_assert_trap(mark))
+            }
+            "assert_exhaustion" => {
+                self.add_child(keyword);
+                Some(self.parse_assert_with_action(ASSERT_EXHAUSTION, mark))
+            }
+            "assert_invalid" => {
+                self.add_child(keyword);
+                Some(self.parse_assert_with_module(ASSERT_INVALID, mark))
+            }
+            "assert_malformed" => {
+                self.add_child(keyword);
+                Some(self.parse_assert_with_module(ASSERT_MALFORMED, mark))
+            }
+            "assert_unlinkable" => {
+                self.add_child(keyword);
+                Some(self.parse_assert_with_module(ASSERT_UNLINKABLE, mark))
             }
             // wabt allows top-level module fields
             "func" => {
@@ -355,7 +409,7 @@ impl Parser<'_> {
             }
             _ => None,
         };
-        self.lexer.top_level = true;
+        self.lexer.top_level = top_level;
         node
     }
 
@@ -0,0 +1,112 @@
+use super::{Parser, builder::NodeMark, green};
+use crate::error::Message;
+use wat_syntax::{GreenNode, SyntaxKind, SyntaxKind::*};
+
+impl Parser<'_> {
+    pub(super) fn parse_action(&mut self) -> Option<GreenNode> {
+        let mark = self.start_node();
+        self.lexer.next(L_PAREN)?;
+        self.add_child(green::L_PAREN.clone());
+        self.parse_trivias();
+        let keyword = self.lexer.next(KEYWORD)?;
+        match keyword.text {
+            "invoke" => {
+                self.add_child(keyword);
+                Some(self.parse_action_invoke(mark))
+            }
+            "get" => {
+                self.add_child(keyword);
+                Some(self.parse_action_get(mark))
+            }
+            _ => None,
+        }
+    }
+
+    pub(super) fn parse_action_get(&mut self, mark: NodeMark) -> GreenNode {
+        self.eat(IDENT);
+        if !self.retry(Self::parse_name) {
+            self.report_missing(Message::Name("export name"));
+        }
+        self.expect_right_paren();
+        self.finish_node(ACTION_GET, mark)
+    }
+
+    pub(super) fn parse_action_invoke(&mut self, mark: NodeMark) -> GreenNode {
+        self.eat(IDENT);
+        if !self.retry(Self::parse_name) {
+            self.report_missing(Message::Name("export name"));
+        }
+        while self.recover(Self::parse_instr) {}
+        self.expect_right_paren();
+        self.finish_node(ACTION_INVOKE, mark)
+    }
+
+    pub(super) fn parse_assert_return(&mut self, mark: NodeMark) -> GreenNode {
+        if !self.recover(Self::parse_action) {
+            self.report_missing(Message::Name("action"));
+        }
+        self.lexer.nan_pattern = true;
+        while self.recover(Self::parse_instr) {}
+        self.lexer.nan_pattern = false;
+        self.expect_right_paren();
+        self.finish_node(ASSERT_RETURN, mark)
+    }
+
+    pub(super) fn parse_assert_trap(&mut self, mark: NodeMark) -> GreenNode {
+        if !self.recover(|parser| {
+            parser
+                .try_parse(Self::parse_action)
+                .or_else(|| parser.parse_script_module())
+        }) {
+            self.report_missing(Message::Name("action or module"));
+        }
+        self.parse_failure_message();
+        self.expect_right_paren();
+        self.finish_node(ASSERT_TRAP, mark)
+    }
+
+    /// Parse assertions that take an action and a failure message, such as `assert_exhaustion`.
+    pub(super) fn parse_assert_with_action(&mut self, kind: SyntaxKind, mark: NodeMark) -> GreenNode {
+        if !self.recover(Self::parse_action) {
+            self.report_missing(Message::Name("action"));
+        }
+        self.parse_failure_message();
+        self.expect_right_paren();
+        self.finish_node(kind, mark)
+    }
+
+    /// Parse assertions that take a module and a failure message, such as `assert_invalid`.
+    pub(super) fn parse_assert_with_module(&mut self, kind: SyntaxKind, mark: NodeMark) -> GreenNode {
+        if !self.recover(Self::parse_script_module) {
+            self.report_missing(Message::Name("module"));
+        }
+        self.parse_failure_message();
+        self.expect_right_paren();
+        self.finish_node(kind, mark)
+    }
+
+    pub(super) fn parse_register(&mut self, mark: NodeMark) -> GreenNode {
+        if !self.retry(Self::parse_name) {
+            self.report_missing(Message::Name("module name"));
+        }
+        self.eat(IDENT);
+        self.expect_right_paren();
+        self.finish_node(REGISTER, mark)
+    }
+
+    fn parse_failure_message(&mut self) {
+        if !self.eat(STRING) {
+            self.report_missing(Message::Name("failure message"));
+        }
+    }
+
+    /// Only accept `(module ...)` here, while `parse_module` also accepts other top-level forms.
+    fn parse_script_module(&mut self) -> Option<GreenNode> {
+        let checkpoint = self.lexer.checkpoint();
+        self.lexer.next(L_PAREN)?;
+        while self.lexer.trivia().is_some() {}
+        let is_module = self.lexer.keyword("module").is_some();
+        self.lexer.reset(checkpoint);
+        if is_module { self.parse_module() } else { None }
+    }
+}
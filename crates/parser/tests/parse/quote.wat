@@ -0,0 +1,5 @@
+(module quote "(func)")
+(module $M quote
+  "(func (export \"f\") (result i32)"
+  "  i32.const 0)")
+(assert_malformed (module quote "(fnuc)") "unexpected token")
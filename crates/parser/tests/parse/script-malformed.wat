@@ -0,0 +1,6 @@
+(register $M)
+(invoke)
+(assert_return (i32.const 1))
+(assert_trap (invoke "f"))
+(assert_invalid (func) "type mismatch")
+(module (func f32.const nan:canonical drop))
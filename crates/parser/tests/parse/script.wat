@@ -0,0 +1,20 @@
+(module $M
+  (func (export "add") (param i32 i32) (result i32)
+    local.get 0
+    local.get 1
+    i32.add)
+  (global (export "g") i32 (i32.const 1)))
+(register "m" $M)
+(invoke "add" (i32.const 1) (i32.const 2))
+(invoke $M "add" (i32.const 1) (i32.const 2))
+(get $M "g")
+(assert_return (invoke "add" (i32.const 1) (i32.const 2)) (i32.const 3))
+(assert_return (get "g") (i32.const 1))
+(assert_return (invoke "f") (f32.const nan:canonical) (f64.const nan:arithmetic))
+(assert_return (invoke "f") (either (i32.const 1) (i32.const 2)))
+(assert_trap (invoke "div" (i32.const 1) (i32.const 0)) "integer divide by zero")
+(assert_trap (module (func $main unreachable) (start $main)) "unreachable")
+(assert_exhaustion (invoke "loop") "call stack exhausted")
+(assert_invalid (module (func (result i32))) "type mismatch")
+(assert_malformed (module (func)) "unexpected token")
+(assert_unlinkable (module (import "m" "h" (func))) "unknown import")
@@ -0,0 +1,13 @@
+(module
+  (func
+    (try (catch $e))
+    (try (do) (catch) (delegate))
+    try
+      nop
+    catch
+    end
+    try
+      nop
+    delegate
+  )
+)
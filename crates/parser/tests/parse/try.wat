@@ -0,0 +1,23 @@
+(module
+  (func
+    (try (do))
+    (try $t (result i32) (do (i32.const 0)) (catch $e) (catch_all (i32.const 1)))
+    (try (do (call $f)) (catch $e0 (drop)) (catch $e1 (rethrow 0)))
+    (try (do (throw $e)) (delegate 0))
+    try $t (result i32)
+      i32.const 0
+    catch $e
+      drop
+      i32.const 1
+    catch_all
+      rethrow $t
+    end $t
+    try
+      call $f
+    delegate $l
+    try
+      nop
+    catch_all
+    end
+  )
+)
@@ -0,0 +1,225 @@
+use crate::Error;
+use rustc_hash::FxHashMap;
+use serde_json::{Map, Value};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Names of configuration files, in order of precedence when they're in the same directory.
+pub const CONFIG_FILE_NAMES: [&str; 2] = ["wat.toml", ".watrc.json"];
+
+/// Find the nearest configuration file, walking up from the directory of the given path.
+pub fn find_config_file(path: &Path) -> Option<PathBuf> {
+    find_config_files(path).pop()
+}
+
+/// Find all configuration files from the outermost directory to the nearest one of the given path.
+///
+/// There's at most one configuration file for each directory.
+pub fn find_config_files(path: &Path) -> Vec<PathBuf> {
+    let path = crate::normalize_path(path);
+    let mut files = path
+        .ancestors()
+        .skip(usize::from(!path.is_dir()))
+        .filter_map(|dir| {
+            CONFIG_FILE_NAMES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|file| file.is_file())
+        })
+        .collect::<Vec<_>>();
+    files.reverse();
+    files
+}
+
+/// Check if the given path is a configuration file by its file name.
+pub fn is_config_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| CONFIG_FILE_NAMES.contains(&name))
+}
+
+/// Load configuration file as JSON value.
+///
+/// Files with `.toml` extension are parsed as TOML, otherwise as JSON.
+pub fn load_config_file(file: &Path) -> Result<Value, Error> {
+    let content = fs::read_to_string(file)
+        .map_err(|err| Error::new(format!("failed to read config `{}`: {err}", file.display())))?;
+    let config = if file.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str::<Value>(&content)
+            .map_err(|err| Error::new(format!("invalid config `{}`: {err}", file.display())))
+    } else {
+        serde_json::from_str::<Value>(&content)
+            .map_err(|err| Error::new(format!("invalid config `{}`: {err}", file.display())))
+    }?;
+    if !config.is_object() {
+        return Err(Error::new(format!(
+            "invalid config `{}`: expected a table",
+            file.display()
+        )));
+    }
+    if let Some(overrides) = config.get("overrides") {
+        let valid = overrides.as_array().is_some_and(|overrides| {
+            overrides.iter().all(|item| {
+                item.get("files")
+                    .and_then(Value::as_array)
+                    .is_some_and(|files| files.iter().all(|pattern| parse_pattern(pattern).is_some()))
+            })
+        });
+        if !valid {
+            return Err(Error::new(format!(
+                "invalid config `{}`: each item of `overrides` must have `files` with valid globs",
+                file.display()
+            )));
+        }
+    }
+    Ok(config)
+}
+
+/// Discover configuration files and resolve configuration for the given path.
+///
+/// It returns `Ok(None)` if there isn't any configuration file.
+pub fn discover_config(path: &Path) -> Result<Option<Value>, Error> {
+    ConfigResolver::default().resolve(path)
+}
+
+/// Apply configuration loaded from `config_file` to `target` for the given path.
+///
+/// Configuration is merged deeply: tables are merged recursively, while other values are replaced.
+/// After that, each item of `overrides` whose `files` globs match the path is applied in order.
+/// Globs are relative to the directory of configuration file,
+/// and globs without slash match file name in any directory.
+pub fn apply_config(target: &mut Value, config: &Value, config_file: &Path, path: &Path) {
+    let Some(config) = config.as_object() else {
+        return;
+    };
+    let target = ensure_object(target);
+    config
+        .iter()
+        .filter(|(key, _)| *key != "overrides" && *key != "root")
+        .for_each(|(key, value)| merge_value(target, key, value));
+
+    let path = crate::normalize_path(path);
+    let relative = config_file
+        .parent()
+        .and_then(|dir| path.strip_prefix(crate::normalize_path(dir)).ok())
+        .unwrap_or(&path);
+    config
+        .get("overrides")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|item| {
+            item.get("files")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(parse_pattern)
+                .any(|pattern| {
+                    if pattern.as_str().contains('/') {
+                        pattern.matches_path_with(relative, MATCH_OPTIONS)
+                    } else {
+                        relative
+                            .file_name()
+                            .is_some_and(|name| pattern.matches_path_with(Path::new(name), MATCH_OPTIONS))
+                    }
+                })
+        })
+        .filter_map(Value::as_object)
+        .for_each(|item| {
+            item.iter()
+                .filter(|(key, _)| *key != "files")
+                .for_each(|(key, value)| merge_value(target, key, value));
+        });
+}
+
+const MATCH_OPTIONS: glob::MatchOptions = glob::MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+fn parse_pattern(pattern: &Value) -> Option<glob::Pattern> {
+    pattern
+        .as_str()
+        .and_then(|pattern| glob::Pattern::new(pattern.trim_start_matches("./")).ok())
+}
+
+/// Merge configuration into `target` deeply.
+///
+/// Tables are merged recursively, while other values including arrays are replaced.
+pub fn merge_config(target: &mut Value, config: &Value) {
+    if let Some(config) = config.as_object() {
+        let target = ensure_object(target);
+        config.iter().for_each(|(key, value)| merge_value(target, key, value));
+    }
+}
+
+fn ensure_object(value: &mut Value) -> &mut Map<String, Value> {
+    if !value.is_object() {
+        *value = Value::Object(Map::new());
+    }
+    match value {
+        Value::Object(object) => object,
+        _ => unreachable!(),
+    }
+}
+
+fn merge_value(target: &mut Map<String, Value>, key: &str, value: &Value) {
+    match (target.get_mut(key), value) {
+        (Some(Value::Object(existing)), Value::Object(object)) => {
+            object.iter().for_each(|(key, value)| merge_value(existing, key, value));
+        }
+        _ => {
+            target.insert(key.to_string(), value.clone());
+        }
+    }
+}
+
+#[derive(Default)]
+/// Resolve configuration for files with loaded configuration files cached.
+///
+/// All configuration files from the outermost directory to the nearest one are applied in order,
+/// so nested configuration files override outer ones.
+/// A configuration file with `root = true` stops searching in its parent directories.
+pub struct ConfigResolver {
+    cache: FxHashMap<PathBuf, Value>,
+}
+
+impl ConfigResolver {
+    /// Resolve configuration for the given path.
+    ///
+    /// It returns `Ok(None)` if there isn't any configuration file.
+    pub fn resolve(&mut self, path: &Path) -> Result<Option<Value>, Error> {
+        let files = find_config_files(path);
+        let mut configs = Vec::with_capacity(files.len());
+        for file in files {
+            let config = match self.cache.get(&file) {
+                Some(config) => config.clone(),
+                None => {
+                    let config = load_config_file(&file)?;
+                    self.cache.insert(file.clone(), config.clone());
+                    config
+                }
+            };
+            if config.get("root").and_then(Value::as_bool) == Some(true) {
+                configs.clear();
+            }
+            configs.push((file, config));
+        }
+        if configs.is_empty() {
+            return Ok(None);
+        }
+        let mut resolved = Value::Object(Map::new());
+        configs
+            .iter()
+            .for_each(|(file, config)| apply_config(&mut resolved, config, file, path));
+        Ok(Some(resolved))
+    }
+
+    /// Forget loaded configuration files, so they will be loaded again when resolving.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
@@ -0,0 +1,149 @@
+use crate::Error;
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+/// Check if it's a WebAssembly text file by its extension.
+pub fn is_source_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "wat" || ext == "wast")
+}
+
+/// Expand globs and directories into file paths, keeping order of input but without duplicates.
+///
+/// Directories are searched recursively for source files, skipping hidden entries and `node_modules`.
+pub fn collect_files(patterns: &[String]) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+    for pattern in patterns {
+        if pattern.contains(['*', '?', '[']) {
+            let paths = glob::glob(pattern).map_err(|err| Error::new(format!("invalid glob `{pattern}`: {err}")))?;
+            let mut paths = paths
+                .filter_map(Result::ok)
+                .filter(|path| path.is_file())
+                .collect::<Vec<_>>();
+            paths.sort_unstable();
+            files.append(&mut paths);
+        } else {
+            let path = PathBuf::from(pattern);
+            if path.is_dir() {
+                collect_dir(&path, &mut files);
+            } else if path.is_file() {
+                files.push(path);
+            } else {
+                return Err(Error::new(format!("`{pattern}` doesn't exist")));
+            }
+        }
+    }
+    let mut seen = Vec::with_capacity(files.len());
+    files.retain(|file| {
+        let path = normalize_path(file);
+        if seen.contains(&path) {
+            false
+        } else {
+            seen.push(path);
+            true
+        }
+    });
+    Ok(files)
+}
+
+/// Search source files in directory recursively, skipping hidden entries and `node_modules`.
+///
+/// Entries are visited in order of file name, and symbolic links aren't followed to avoid cycles.
+pub fn collect_dir(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries = entries.filter_map(Result::ok).collect::<Vec<_>>();
+    entries.sort_unstable_by_key(|entry| entry.file_name());
+    entries.into_iter().for_each(|entry| {
+        let file_name = entry.file_name();
+        let Some(name) = file_name.to_str() else {
+            return;
+        };
+        if name.starts_with('.') || name == "node_modules" {
+            return;
+        }
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => collect_dir(&path, files),
+            Ok(file_type) if file_type.is_file() && is_source_file(&path) => files.push(path),
+            _ => {}
+        }
+    });
+}
+
+/// Make path absolute and remove `.` and `..` lexically,
+/// which is consistent with how language service resolves paths of imports.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    path.components().fold(PathBuf::new(), |mut normalized, component| {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+        normalized
+    })
+}
+
+/// Convert file path to `file://` URI.
+pub fn path_to_uri(path: &Path) -> String {
+    let path = normalize_path(path);
+    let mut uri = String::from("file://");
+    path.components().for_each(|component| match component {
+        Component::Prefix(prefix) => {
+            uri.push('/');
+            uri.push_str(&prefix.as_os_str().to_string_lossy());
+        }
+        Component::RootDir => {}
+        component => {
+            uri.push('/');
+            uri.push_str(&percent_encode(&component.as_os_str().to_string_lossy()));
+        }
+    });
+    uri
+}
+
+/// Convert `file://` URI to file path.
+///
+/// It returns `None` if it isn't a `file://` URI or it can't be decoded.
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    // skip authority, which is usually empty or `localhost`
+    let path = percent_decode(&path[path.find('/')?..])?;
+    // Windows paths look like `/c:/Users`
+    if cfg!(windows) && path.as_bytes().get(2) == Some(&b':') {
+        Some(PathBuf::from(&path[1..]))
+    } else {
+        Some(PathBuf::from(path))
+    }
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let high = (iter.next()? as char).to_digit(16)?;
+            let low = (iter.next()? as char).to_digit(16)?;
+            bytes.push((high * 16 + low) as u8);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn percent_encode(s: &str) -> String {
+    s.bytes().fold(String::with_capacity(s.len()), |mut encoded, byte| {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+        encoded
+    })
+}
@@ -0,0 +1,30 @@
+//! File system related utilities shared by command line tools and language server,
+//! such as collecting source files and discovering configuration files.
+
+pub use crate::{config::*, files::*};
+use std::fmt;
+
+mod config;
+mod files;
+
+#[derive(Debug)]
+/// Error when collecting files or loading configuration.
+pub struct Error {
+    message: String,
+}
+
+impl Error {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Error {}
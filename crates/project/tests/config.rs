@@ -0,0 +1,85 @@
+use serde_json::json;
+use std::{fs, path::PathBuf, process};
+use wat_project::ConfigResolver;
+
+fn create_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("wat_project_{name}_{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("a/b")).unwrap();
+    dir
+}
+
+#[test]
+fn nested() {
+    let dir = create_dir("nested");
+    fs::write(
+        dir.join("wat.toml"),
+        "[lint]\nunused = \"deny\"\nshadow = \"deny\"\n\n[format]\nindentWidth = 4\n",
+    )
+    .unwrap();
+    fs::write(dir.join("a/.watrc.json"), r#"{ "lint": { "unused": "allow" } }"#).unwrap();
+
+    let mut resolver = ConfigResolver::default();
+    let outer = resolver.resolve(&dir.join("module.wat")).unwrap();
+    let inner = resolver.resolve(&dir.join("a/b/module.wat")).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(
+        outer,
+        Some(json!({ "lint": { "unused": "deny", "shadow": "deny" }, "format": { "indentWidth": 4 } })),
+    );
+    assert_eq!(
+        inner,
+        Some(json!({ "lint": { "unused": "allow", "shadow": "deny" }, "format": { "indentWidth": 4 } })),
+    );
+}
+
+#[test]
+fn overrides() {
+    let dir = create_dir("overrides");
+    fs::write(
+        dir.join("wat.toml"),
+        r#"
+[lint]
+unused = "deny"
+
+[[overrides]]
+files = ["a/**/*.wat"]
+lint = { unused = "warn" }
+
+[[overrides]]
+files = ["*.wast"]
+lint = { unused = "allow" }
+"#,
+    )
+    .unwrap();
+
+    let mut resolver = ConfigResolver::default();
+    let root = resolver.resolve(&dir.join("module.wat")).unwrap();
+    let nested = resolver.resolve(&dir.join("a/b/module.wat")).unwrap();
+    let script = resolver.resolve(&dir.join("a/b/test.wast")).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(root, Some(json!({ "lint": { "unused": "deny" } })));
+    assert_eq!(nested, Some(json!({ "lint": { "unused": "warn" } })));
+    assert_eq!(script, Some(json!({ "lint": { "unused": "allow" } })));
+}
+
+#[test]
+fn root() {
+    let dir = create_dir("root");
+    fs::write(dir.join("wat.toml"), "[lint]\nunused = \"deny\"\n").unwrap();
+    fs::write(dir.join("a/wat.toml"), "root = true\n\n[lint]\nshadow = \"deny\"\n").unwrap();
+
+    let config = ConfigResolver::default().resolve(&dir.join("a/module.wat")).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(config, Some(json!({ "lint": { "shadow": "deny" } })));
+}
+
+#[test]
+fn invalid_overrides() {
+    let dir = create_dir("invalid_overrides");
+    fs::write(dir.join("wat.toml"), "[[overrides]]\nlint = { unused = \"deny\" }\n").unwrap();
+
+    let result = ConfigResolver::default().resolve(&dir.join("module.wat"));
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(result.is_err());
+}
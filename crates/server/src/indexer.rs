@@ -0,0 +1,97 @@
+use crate::{message::Message, server::Event, stdio};
+use lspt::{
+    ProgressParams, ProgressToken, WorkDoneProgressBegin, WorkDoneProgressEnd, WorkDoneProgressReport,
+    notification::{Notification as _, ProgressNotification},
+};
+use std::{fs, sync::mpsc::Sender, thread};
+
+/// Scan workspace folders and read WebAssembly text files on another thread.
+///
+/// Each file is sent back as an event, since only the main thread can commit documents to language service.
+pub fn spawn(folders: Vec<String>, progress_token: Option<ProgressToken>, sender: Sender<Event>) {
+    thread::spawn(move || {
+        let mut paths = Vec::new();
+        folders.iter().for_each(|folder| {
+            if let Some(path) = wat_project::uri_to_path(folder) {
+                wat_project::collect_dir(&path, &mut paths);
+            }
+        });
+        let files = paths
+            .into_iter()
+            .map(|path| (wat_project::path_to_uri(&path), path))
+            .collect::<Vec<_>>();
+        if let Some(token) = &progress_token {
+            let _ = report_progress(
+                token,
+                WorkDoneProgressBegin {
+                    kind: "begin".into(),
+                    title: "Indexing".into(),
+                    cancellable: Some(false),
+                    message: Some(format!("0/{}", files.len())),
+                    percentage: Some(0),
+                },
+            );
+        }
+
+        let total = files.len();
+        let mut uris = Vec::with_capacity(total);
+        let mut last_percentage = 0;
+        for (i, (uri, path)) in files.into_iter().enumerate() {
+            if let Ok(text) = fs::read_to_string(path) {
+                uris.push(uri.clone());
+                if sender.send(Event::Indexed { uri, text }).is_err() {
+                    return;
+                }
+            }
+            let percentage = ((i + 1) * 100 / total) as u32;
+            if let Some(token) = &progress_token
+                && percentage != last_percentage
+            {
+                last_percentage = percentage;
+                let _ = report_progress(
+                    token,
+                    WorkDoneProgressReport {
+                        kind: "report".into(),
+                        cancellable: Some(false),
+                        message: Some(format!("{}/{total}", i + 1)),
+                        percentage: Some(percentage),
+                    },
+                );
+            }
+        }
+
+        if let Some(token) = &progress_token {
+            let _ = report_progress(
+                token,
+                WorkDoneProgressEnd {
+                    kind: "end".into(),
+                    message: None,
+                },
+            );
+        }
+        let _ = sender.send(Event::IndexingDone(uris));
+    });
+}
+
+/// Read file of the given URI if it should be indexed.
+pub fn read(uri: &str) -> Option<String> {
+    if is_indexable(uri) {
+        wat_project::uri_to_path(uri).and_then(|path| fs::read_to_string(path).ok())
+    } else {
+        None
+    }
+}
+
+pub fn is_indexable(uri: &str) -> bool {
+    uri.starts_with("file://") && (uri.ends_with(".wat") || uri.ends_with(".wast"))
+}
+
+fn report_progress(token: &ProgressToken, value: impl serde::Serialize) -> anyhow::Result<()> {
+    stdio::write(Message::Notification {
+        method: ProgressNotification::METHOD.into(),
+        params: serde_json::to_value(ProgressParams {
+            token: token.clone(),
+            value: serde_json::to_value(value)?,
+        })?,
+    })
+}
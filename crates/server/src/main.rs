@@ -1,6 +1,7 @@
 use crate::server::Server;
 use std::{env, path, time::SystemTime};
 
+mod indexer;
 mod message;
 mod sent;
 mod server;
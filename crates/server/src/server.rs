@@ -1,15 +1,18 @@
 use crate::{
+    indexer,
     message::{Message, ResponseError, try_cast_notification, try_cast_request},
     sent::SentRequests,
     stdio,
 };
 use lspt::{
     ConfigurationItem, ConfigurationParams, DidChangeConfigurationParams, DidChangeTextDocumentParams,
-    DidOpenTextDocumentParams, InitializeParams, NumberOrString, Registration, RegistrationParams,
+    DidChangeWatchedFilesParams, DidChangeWatchedFilesRegistrationOptions, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, FileChangeType, FileSystemWatcher, InitializeParams, NumberOrString, Registration,
+    RegistrationParams, TextDocumentIdentifier, WorkDoneProgressCreateParams,
     notification::{
         CancelNotification, DidChangeConfigurationNotification, DidChangeTextDocumentNotification,
-        DidCloseTextDocumentNotification, DidOpenTextDocumentNotification, ExitNotification, InitializedNotification,
-        Notification as _, PublishDiagnosticsNotification,
+        DidChangeWatchedFilesNotification, DidCloseTextDocumentNotification, DidOpenTextDocumentNotification,
+        ExitNotification, InitializedNotification, Notification as _, PublishDiagnosticsNotification,
     },
     request::{
         CallHierarchyIncomingCallsRequest, CallHierarchyOutgoingCallsRequest, CallHierarchyPrepareRequest,
@@ -21,13 +24,32 @@ use lspt::{
         RegistrationRequest, RenameRequest, Request as _, SelectionRangeRequest, SemanticTokensRangeRequest,
         SemanticTokensRequest, ShutdownRequest, SignatureHelpRequest, TypeDefinitionRequest,
         TypeHierarchyPrepareRequest, TypeHierarchySubtypesRequest, TypeHierarchySupertypesRequest,
+        WorkDoneProgressCreateRequest, WorkspaceDiagnosticRequest, WorkspaceSymbolRequest,
     },
 };
 use parking_lot::RwLock;
-use rustc_hash::FxHashMap;
-use std::{io::StdinLock, sync::Arc};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{
+    io::StdinLock,
+    sync::{
+        Arc,
+        mpsc::{self, Receiver, Sender},
+    },
+};
 use wat_service::{CancellationToken, LanguageService};
 
+pub enum Event {
+    Message(Message),
+    /// A file on disk has been read by indexer.
+    Indexed {
+        uri: String,
+        text: String,
+    },
+    /// Indexer has finished, with URIs of all indexed files.
+    IndexingDone(Vec<String>),
+    Exit,
+}
+
 pub struct Server {
     service: LanguageService,
     support_pull_diagnostics: bool,
@@ -35,12 +57,28 @@ pub struct Server {
     support_refresh_inlay_hint: bool,
     support_pull_config: bool,
     support_register_change_config: bool,
+    support_register_watched_files: bool,
+    support_work_done_progress: bool,
+    workspace_folders: Vec<String>,
+    /// Configuration from client via `initializationOptions` or `workspace/didChangeConfiguration`.
+    global_config: serde_json::Value,
+    /// Configuration of each document pulled from client.
+    client_configs: FxHashMap<String, serde_json::Value>,
+    /// Configuration files discovered from disk, which override configuration from client.
+    project_configs: wat_project::ConfigResolver,
+    /// Documents opened in editor, whose content may be different from files on disk.
+    opened: FxHashSet<String>,
+    /// Documents read from disk.
+    indexed: FxHashSet<String>,
     sent_requests: SentRequests,
     cancellation_tokens: Arc<RwLock<FxHashMap<NumberOrString, CancellationToken>>>,
+    sender: Sender<Event>,
+    receiver: Receiver<Event>,
 }
 
 impl Server {
     pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
         Self {
             service: LanguageService::default(),
             support_pull_diagnostics: false,
@@ -48,22 +86,66 @@ impl Server {
             support_refresh_inlay_hint: false,
             support_pull_config: false,
             support_register_change_config: false,
+            support_register_watched_files: false,
+            support_work_done_progress: false,
+            workspace_folders: Vec::new(),
+            global_config: serde_json::Value::Null,
+            client_configs: FxHashMap::default(),
+            project_configs: wat_project::ConfigResolver::default(),
+            opened: FxHashSet::default(),
+            indexed: FxHashSet::default(),
             sent_requests: SentRequests::default(),
             cancellation_tokens: Default::default(),
+            sender,
+            receiver,
         }
     }
 
     pub fn run(&mut self) -> anyhow::Result<()> {
-        let mut stdin = std::io::stdin().lock();
-        self.initialize(&mut stdin)?;
+        self.initialize(&mut std::io::stdin().lock())?;
+
+        // read messages on another thread, so events from indexer can be handled in the meanwhile
+        let sender = self.sender.clone();
+        std::thread::spawn(move || {
+            let mut stdin = std::io::stdin().lock();
+            loop {
+                match stdio::read(&mut stdin) {
+                    Ok(Some(message)) => {
+                        if sender.send(Event::Message(message)).is_err() {
+                            return;
+                        }
+                    }
+                    Ok(None) => {
+                        let _ = sender.send(Event::Exit);
+                        return;
+                    }
+                    _ => continue,
+                }
+            }
+        });
 
         loop {
-            let message = match stdio::read(&mut stdin) {
-                Ok(Some(message)) => message,
-                Ok(None) => return Ok(()),
-                _ => continue,
+            let message = match self.receiver.recv() {
+                Ok(Event::Message(message)) => message,
+                Ok(Event::Indexed { uri, text }) => {
+                    if !self.opened.contains(&uri) {
+                        self.service.commit(uri.clone(), text);
+                        self.apply_config(&uri);
+                    }
+                    self.indexed.insert(uri);
+                    continue;
+                }
+                Ok(Event::IndexingDone(uris)) => {
+                    self.handle_indexing_done(uris)?;
+                    continue;
+                }
+                Ok(Event::Exit) | Err(..) => return Ok(()),
             };
             match message {
+                // disassembling commits a new document, so it can't run on cloned service
+                Message::Request { id, method, params } if method == "wasmLanguageTools/disassemble" => {
+                    self.handle_disassemble(id, params)?;
+                }
                 Message::Request { id, method, params } => {
                     let service = self.service.clone();
                     self.cancellation_tokens
@@ -100,7 +182,7 @@ impl Server {
                     }
                     match try_cast_notification::<DidCloseTextDocumentNotification>(&method, params) {
                         Ok(Ok(params)) => {
-                            self.service.did_close(params);
+                            self.handle_did_close_text_document(params);
                             continue;
                         }
                         Ok(Err(..)) => continue,
@@ -114,21 +196,17 @@ impl Server {
                         Ok(Err(..)) => continue,
                         Err(p) => params = p,
                     }
+                    match try_cast_notification::<DidChangeWatchedFilesNotification>(&method, params) {
+                        Ok(Ok(params)) => {
+                            self.handle_did_change_watched_files(params)?;
+                            continue;
+                        }
+                        Ok(Err(..)) => continue,
+                        Err(p) => params = p,
+                    }
                     match try_cast_notification::<InitializedNotification>(&method, params) {
                         Ok(..) => {
-                            if self.support_register_change_config {
-                                stdio::write(Message::Request {
-                                    id: NumberOrString::Integer(self.sent_requests.next_id()),
-                                    method: RegistrationRequest::METHOD.into(),
-                                    params: serde_json::to_value(RegistrationParams {
-                                        registrations: vec![Registration {
-                                            id: DidChangeConfigurationNotification::METHOD.into(),
-                                            method: DidChangeConfigurationNotification::METHOD.into(),
-                                            register_options: None,
-                                        }],
-                                    })?,
-                                })?;
-                            }
+                            self.handle_initialized()?;
                             continue;
                         }
                         Err(p) => params = p,
@@ -196,6 +274,32 @@ impl Server {
                 .and_then(|it| it.dynamic_registration),
             Some(true)
         );
+        self.support_register_watched_files = matches!(
+            params
+                .capabilities
+                .workspace
+                .as_ref()
+                .and_then(|it| it.did_change_watched_files.as_ref())
+                .and_then(|it| it.dynamic_registration),
+            Some(true)
+        );
+        self.support_work_done_progress = matches!(
+            params.capabilities.window.as_ref().and_then(|it| it.work_done_progress),
+            Some(true)
+        );
+        self.workspace_folders = params
+            .workspace_folders
+            .iter()
+            .flatten()
+            .map(|folder| folder.uri.clone())
+            .collect();
+        if let Some(config) = params
+            .initialization_options
+            .as_ref()
+            .filter(|config| config.is_object())
+        {
+            self.global_config = config.clone();
+        }
         stdio::write(Message::OkResponse {
             id,
             result: serde_json::to_value(self.service.initialize(params))?,
@@ -203,6 +307,58 @@ impl Server {
         Ok(())
     }
 
+    fn handle_initialized(&mut self) -> anyhow::Result<()> {
+        let mut registrations = Vec::with_capacity(2);
+        if self.support_register_change_config {
+            registrations.push(Registration {
+                id: DidChangeConfigurationNotification::METHOD.into(),
+                method: DidChangeConfigurationNotification::METHOD.into(),
+                register_options: None,
+            });
+        }
+        if self.support_register_watched_files && !self.workspace_folders.is_empty() {
+            registrations.push(Registration {
+                id: DidChangeWatchedFilesNotification::METHOD.into(),
+                method: DidChangeWatchedFilesNotification::METHOD.into(),
+                register_options: Some(serde_json::to_value(DidChangeWatchedFilesRegistrationOptions {
+                    watchers: vec![
+                        FileSystemWatcher {
+                            glob_pattern: "**/*.{wat,wast}".to_string().into(),
+                            kind: None,
+                        },
+                        FileSystemWatcher {
+                            glob_pattern: format!("**/{{{}}}", wat_project::CONFIG_FILE_NAMES.join(",")).into(),
+                            kind: None,
+                        },
+                    ],
+                })?),
+            });
+        }
+        if !registrations.is_empty() {
+            stdio::write(Message::Request {
+                id: NumberOrString::Integer(self.sent_requests.next_id()),
+                method: RegistrationRequest::METHOD.into(),
+                params: serde_json::to_value(RegistrationParams { registrations })?,
+            })?;
+        }
+
+        if !self.workspace_folders.is_empty() {
+            let progress_token = if self.support_work_done_progress {
+                let token = NumberOrString::String("wasmLanguageTools/indexing".into());
+                stdio::write(Message::Request {
+                    id: NumberOrString::Integer(self.sent_requests.next_id()),
+                    method: WorkDoneProgressCreateRequest::METHOD.into(),
+                    params: serde_json::to_value(WorkDoneProgressCreateParams { token: token.clone() })?,
+                })?;
+                Some(token)
+            } else {
+                None
+            };
+            indexer::spawn(self.workspace_folders.clone(), progress_token, self.sender.clone());
+        }
+        Ok(())
+    }
+
     fn handle_request(
         service: LanguageService,
         id: NumberOrString,
@@ -264,6 +420,13 @@ impl Server {
                         .map(|result| Message::OkResponse { id: id.clone(), result })
                 })
             })
+            .or_else(|params| {
+                try_cast_request::<WorkspaceDiagnosticRequest>(&method, params).map(|params| {
+                    params
+                        .and_then(|params| serde_json::to_value(service.pull_workspace_diagnostics(params)))
+                        .map(|result| Message::OkResponse { id: id.clone(), result })
+                })
+            })
             .or_else(|params| {
                 try_cast_request::<DocumentHighlightRequest>(&method, params).map(|params| {
                     params
@@ -397,6 +560,13 @@ impl Server {
                         .map(|result| Message::OkResponse { id: id.clone(), result })
                 })
             })
+            .or_else(|params| {
+                try_cast_request::<WorkspaceSymbolRequest>(&method, params).map(|params| {
+                    params
+                        .and_then(|params| serde_json::to_value(service.workspace_symbol(params)))
+                        .map(|result| Message::OkResponse { id: id.clone(), result })
+                })
+            })
             .or_else(|params| {
                 try_cast_request::<TypeHierarchyPrepareRequest>(&method, params).map(|params| {
                     params
@@ -446,6 +616,44 @@ impl Server {
             })
     }
 
+    fn handle_disassemble(&mut self, id: NumberOrString, params: serde_json::Value) -> anyhow::Result<()> {
+        #[derive(serde::Deserialize)]
+        struct DisassembleParams {
+            uri: String,
+            binary: Vec<u8>,
+        }
+
+        let result = serde_json::from_value::<DisassembleParams>(params)
+            .map_err(|error| error.to_string())
+            .and_then(|params| {
+                self.service
+                    .disassemble(params.uri.clone(), &params.binary)
+                    .map(|text| (params.uri, text))
+                    .map_err(|error| error.to_string())
+            });
+        match result {
+            Ok((uri, text)) => {
+                self.opened.insert(uri.clone());
+                stdio::write(Message::OkResponse {
+                    id,
+                    result: serde_json::json!({ "text": text }),
+                })?;
+                if !self.support_pull_diagnostics {
+                    self.publish_diagnostics(uri)?;
+                }
+            }
+            Err(message) => stdio::write(Message::ErrResponse {
+                id,
+                error: ResponseError {
+                    code: -32603,
+                    message,
+                    data: None,
+                },
+            })?,
+        }
+        Ok(())
+    }
+
     fn handle_response(&mut self, id: i32, result: serde_json::Value) -> anyhow::Result<()> {
         if let Some(callback) = self.sent_requests.remove(id) {
             callback(self, result)
@@ -456,7 +664,9 @@ impl Server {
 
     fn handle_did_open_text_document(&mut self, params: DidOpenTextDocumentParams) -> anyhow::Result<()> {
         let uri = params.text_document.uri.clone();
+        self.opened.insert(uri.clone());
         self.service.did_open(params);
+        self.apply_config(&uri);
         if !self.support_pull_diagnostics {
             self.publish_diagnostics(uri.clone())?;
         }
@@ -484,6 +694,81 @@ impl Server {
         Ok(())
     }
 
+    fn handle_did_close_text_document(&mut self, params: DidCloseTextDocumentParams) {
+        let uri = &params.text_document.uri;
+        self.opened.remove(uri);
+        // keep indexed document, but revert to the content on disk
+        if self.indexed.contains(uri)
+            && let Some(text) = indexer::read(uri)
+        {
+            self.service.commit(uri.clone(), text);
+        } else {
+            self.indexed.remove(uri);
+            self.client_configs.remove(uri);
+            self.service.did_close(params);
+        }
+    }
+
+    fn handle_did_change_watched_files(&mut self, params: DidChangeWatchedFilesParams) -> anyhow::Result<()> {
+        let (config_changes, changes) = params.changes.into_iter().partition::<Vec<_>, _>(|change| {
+            wat_project::uri_to_path(&change.uri).is_some_and(|path| wat_project::is_config_file(&path))
+        });
+        for change in changes {
+            if !indexer::is_indexable(&change.uri) || self.opened.contains(&change.uri) {
+                continue;
+            }
+            match change.ty {
+                FileChangeType::Created | FileChangeType::Changed => {
+                    if let Some(text) = indexer::read(&change.uri) {
+                        self.service.commit(change.uri.clone(), text);
+                        self.apply_config(&change.uri);
+                        self.indexed.insert(change.uri);
+                    }
+                }
+                FileChangeType::Deleted => {
+                    if self.indexed.remove(&change.uri) {
+                        self.client_configs.remove(&change.uri);
+                        self.service.did_close(DidCloseTextDocumentParams {
+                            text_document: TextDocumentIdentifier { uri: change.uri },
+                        });
+                    }
+                }
+            }
+        }
+        if !config_changes.is_empty() {
+            self.project_configs.clear();
+            self.apply_all_configs();
+            self.refresh_inlay_hints()?;
+        }
+        self.refresh_diagnostics(self.opened.iter().cloned().collect())
+    }
+
+    fn handle_indexing_done(&mut self, uris: Vec<String>) -> anyhow::Result<()> {
+        if self.support_pull_config {
+            let uris = uris
+                .into_iter()
+                .filter(|uri| !self.opened.contains(uri))
+                .collect::<Vec<_>>();
+            stdio::write(
+                self.sent_requests.add(
+                    ConfigurationRequest::METHOD.into(),
+                    serde_json::to_value(ConfigurationParams {
+                        items: uris
+                            .iter()
+                            .map(|uri| ConfigurationItem {
+                                scope_uri: Some(uri.clone()),
+                                section: Some("wasmLanguageTools".to_string()),
+                            })
+                            .collect(),
+                    })?,
+                    move |server, result| server.update_configs(uris, result),
+                ),
+            )
+        } else {
+            self.refresh_diagnostics(self.opened.iter().cloned().collect())
+        }
+    }
+
     fn handle_did_change_configuration(&mut self, params: DidChangeConfigurationParams) -> anyhow::Result<()> {
         if self.support_pull_config {
             let uris = self.service.get_opened_uris();
@@ -505,8 +790,11 @@ impl Server {
         }
         match &params.settings {
             serde_json::Value::Object(object) if !object.is_empty() => {
-                if let Ok(config) = serde_json::from_value(params.settings) {
+                if let Ok(config) = serde_json::from_value(params.settings.clone()) {
                     self.service.set_global_config(config);
+                    self.global_config = params.settings;
+                    // project configuration is layered over global configuration
+                    self.apply_all_configs();
                 }
             }
             _ => {}
@@ -521,21 +809,31 @@ impl Server {
         })
     }
 
-    fn update_configs(&mut self, uris: Vec<String>, result: serde_json::Value) -> anyhow::Result<()> {
-        uris.iter()
-            .zip(serde_json::from_value::<Vec<_>>(result)?)
-            .for_each(|(uri, config)| self.service.set_config(uri.clone(), config));
+    /// Ask client to pull diagnostics again, or publish diagnostics of the given documents directly.
+    fn refresh_diagnostics(&mut self, uris: Vec<String>) -> anyhow::Result<()> {
         if self.support_refresh_diagnostics {
             stdio::write(Message::Request {
                 id: NumberOrString::Integer(self.sent_requests.next_id()),
                 method: DiagnosticRefreshRequest::METHOD.into(),
                 params: serde_json::Value::Null,
-            })?;
+            })
         } else {
-            for uri in uris {
-                self.publish_diagnostics(uri)?;
-            }
+            uris.into_iter().try_for_each(|uri| self.publish_diagnostics(uri))
         }
+    }
+
+    fn update_configs(&mut self, uris: Vec<String>, result: serde_json::Value) -> anyhow::Result<()> {
+        uris.iter()
+            .zip(serde_json::from_value::<Vec<serde_json::Value>>(result)?)
+            .for_each(|(uri, config)| {
+                self.client_configs.insert(uri.clone(), config);
+                self.apply_config(uri);
+            });
+        self.refresh_diagnostics(uris.into_iter().filter(|uri| self.opened.contains(uri)).collect())?;
+        self.refresh_inlay_hints()
+    }
+
+    fn refresh_inlay_hints(&mut self) -> anyhow::Result<()> {
         if self.support_refresh_inlay_hint {
             stdio::write(Message::Request {
                 id: NumberOrString::Integer(self.sent_requests.next_id()),
@@ -545,4 +843,35 @@ impl Server {
         }
         Ok(())
     }
+
+    /// Set configuration of the given document to language service.
+    ///
+    /// If there're configuration files on disk for the document,
+    /// they're layered over configuration from client.
+    fn apply_config(&mut self, uri: &str) {
+        let project_config = wat_project::uri_to_path(uri).and_then(|path| {
+            self.project_configs
+                .resolve(&path)
+                .inspect_err(|err| log::error!("{err}"))
+                .ok()
+                .flatten()
+        });
+        let client_config = self.client_configs.get(uri).filter(|config| !config.is_null());
+        let config = match project_config {
+            Some(project_config) => {
+                let mut config = client_config.unwrap_or(&self.global_config).clone();
+                wat_project::merge_config(&mut config, &project_config);
+                serde_json::from_value(config)
+                    .inspect_err(|err| log::error!("invalid configuration for `{uri}`: {err}"))
+                    .ok()
+            }
+            None => client_config.and_then(|config| serde_json::from_value(config.clone()).ok()),
+        };
+        self.service.set_config(uri.to_string(), config);
+    }
+
+    fn apply_all_configs(&mut self) {
+        let uris = self.opened.union(&self.indexed).cloned().collect::<Vec<_>>();
+        uris.iter().for_each(|uri| self.apply_config(uri));
+    }
 }
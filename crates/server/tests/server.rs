@@ -1,5 +1,10 @@
 use assert_cmd::Command;
-use std::time::Duration;
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    process::{self, Stdio},
+    time::Duration,
+};
 
 fn construct_stdin(messages: &[&str]) -> String {
     let mut stdin = String::new();
@@ -10,6 +15,23 @@ fn construct_stdin(messages: &[&str]) -> String {
     stdin
 }
 
+fn read_message(stdout: &mut impl BufRead) -> String {
+    let mut length = 0;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        stdout.read_line(&mut line).unwrap();
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            length = value.trim().parse().unwrap();
+        } else if line.trim().is_empty() {
+            break;
+        }
+    }
+    let mut content = vec![0; length];
+    stdout.read_exact(&mut content).unwrap();
+    String::from_utf8(content).unwrap()
+}
+
 #[test]
 fn should_exit_after_stdin_close() {
     let stdin = construct_stdin(&[
@@ -123,3 +145,124 @@ fn should_accept_string_id() {
         .success()
         .stdout(predicates::str::contains("\"68480f111586fdf6f1b3\""));
 }
+
+#[test]
+fn should_disassemble_binary() {
+    let stdin = construct_stdin(&[
+        r#"{"jsonrpc":"2.0","id":0,"method":"initialize","params":{"processId":null,"rootUri":null,"capabilities":{}}}"#,
+        r#"{"jsonrpc":"2.0","method":"initialized","params":{}}"#,
+        r#"{"jsonrpc":"2.0","id":1,"method":"wasmLanguageTools/disassemble","params":{"uri":"file:///home/a.wasm","binary":[0,97,115,109,1,0,0,0,5,3,1,0,1]}}"#,
+        r#"{"jsonrpc":"2.0","id":2,"method":"wasmLanguageTools/disassemble","params":{"uri":"file:///home/b.wasm","binary":[0,97,115,109]}}"#,
+        r#"{"jsonrpc":"2.0","id":10,"method":"shutdown"}"#,
+    ]);
+    Command::new(assert_cmd::cargo_bin!("wat_server"))
+        .write_stdin(stdin)
+        .timeout(Duration::from_secs(5))
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            r#""result":{"text":"(module\n  (memory (;0;) 1))\n"}"#,
+        ))
+        .stdout(predicates::str::contains(r#""error":{"code":-32603"#));
+}
+
+#[test]
+fn should_index_workspace_folders() {
+    let dir = std::env::temp_dir().join(format!("wat_server_indexing_{}", process::id()));
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    fs::create_dir_all(dir.join(".hidden")).unwrap();
+    fs::write(dir.join("sub/lib.wat"), "(module (func $indexed_func))").unwrap();
+    fs::write(dir.join(".hidden/ignored.wat"), "(module (func $hidden_func))").unwrap();
+
+    let mut child = process::Command::new(assert_cmd::cargo_bin!("wat_server"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let initialize = format!(
+        r#"{{"jsonrpc":"2.0","id":0,"method":"initialize","params":{{"processId":null,"capabilities":{{"window":{{"workDoneProgress":true}}}},"workspaceFolders":[{{"uri":"file://{}","name":"test"}}]}}}}"#,
+        dir.display(),
+    );
+    stdin
+        .write_all(
+            construct_stdin(&[&initialize, r#"{"jsonrpc":"2.0","method":"initialized","params":{}}"#]).as_bytes(),
+        )
+        .unwrap();
+    while !read_message(&mut stdout).contains(r#""kind":"end""#) {}
+
+    stdin
+        .write_all(
+            construct_stdin(&[r#"{"jsonrpc":"2.0","id":1,"method":"workspace/symbol","params":{"query":"func"}}"#])
+                .as_bytes(),
+        )
+        .unwrap();
+    let response = loop {
+        let message = read_message(&mut stdout);
+        if message.contains(r#""id":1,"#) {
+            break message;
+        }
+    };
+    drop(stdin);
+    child.wait().unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(response.contains("$indexed_func"));
+    assert!(response.contains("/sub/lib.wat"));
+    assert!(!response.contains("$hidden_func"));
+}
+
+#[test]
+fn should_apply_project_config() {
+    let dir = std::env::temp_dir().join(format!("wat_server_project_config_{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("wat.toml"), "[lint]\nunused = \"deny\"\n").unwrap();
+
+    let mut child = process::Command::new(assert_cmd::cargo_bin!("wat_server"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let did_open = format!(
+        r#"{{"jsonrpc":"2.0","method":"textDocument/didOpen","params":{{"textDocument":{{"uri":"file://{}/module.wat","languageId":"wat","version":0,"text":"(module (func $f))"}}}}}}"#,
+        dir.display(),
+    );
+    stdin
+        .write_all(
+            construct_stdin(&[
+                r#"{"jsonrpc":"2.0","id":0,"method":"initialize","params":{"processId":null,"rootUri":null,"capabilities":{}}}"#,
+                r#"{"jsonrpc":"2.0","method":"initialized","params":{}}"#,
+                &did_open,
+            ])
+            .as_bytes(),
+        )
+        .unwrap();
+    let diagnostics = loop {
+        let message = read_message(&mut stdout);
+        if message.contains("textDocument/publishDiagnostics") {
+            break message;
+        }
+    };
+    assert!(diagnostics.contains(r#""severity":1"#));
+
+    fs::write(dir.join("wat.toml"), "[lint]\nunused = \"allow\"\n").unwrap();
+    let did_change_watched_files = format!(
+        r#"{{"jsonrpc":"2.0","method":"workspace/didChangeWatchedFiles","params":{{"changes":[{{"uri":"file://{}/wat.toml","type":2}}]}}}}"#,
+        dir.display(),
+    );
+    stdin
+        .write_all(construct_stdin(&[&did_change_watched_files]).as_bytes())
+        .unwrap();
+    let diagnostics = loop {
+        let message = read_message(&mut stdout);
+        if message.contains("textDocument/publishDiagnostics") {
+            break message;
+        }
+    };
+    drop(stdin);
+    child.wait().unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(diagnostics.contains(r#""diagnostics":[]"#));
+}
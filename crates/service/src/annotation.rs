@@ -0,0 +1,58 @@
+use wat_syntax::{AmberToken, SyntaxKind, TextRange, TextSize};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Malformed payload of annotation.
+pub(crate) struct Malformed {
+    pub range: TextRange,
+    pub message: String,
+}
+
+/// Element of annotation payload, which is either a single token or a parenthesized list.
+pub(crate) enum Item<'a> {
+    Atom(AmberToken<'a>),
+    List(Vec<Item<'a>>, TextRange),
+}
+impl Item<'_> {
+    pub fn range(&self) -> TextRange {
+        match self {
+            Item::Atom(token) => token.text_range(),
+            Item::List(_, range) => *range,
+        }
+    }
+}
+
+/// Collect payload items from tokens that follow annotation start, until the end of annotation.
+///
+/// It returns items and the end offset of annotation, which is `end` if there're no more tokens.
+/// Lists that aren't closed are dropped.
+pub(crate) fn collect_items<'a>(
+    tokens: impl Iterator<Item = AmberToken<'a>>,
+    mut end: TextSize,
+) -> (Vec<Item<'a>>, TextSize) {
+    let mut items = vec![];
+    // lists that are still open, with their start offsets
+    let mut lists: Vec<(Vec<Item>, _)> = vec![];
+    for token in tokens {
+        end = token.text_range().end();
+        match token.kind() {
+            SyntaxKind::ANNOT_END => break,
+            SyntaxKind::ANNOT_ELEM if token.text() == "(" => {
+                lists.push((vec![], token.text_range().start()));
+            }
+            SyntaxKind::ANNOT_ELEM if token.text() == ")" => {
+                if let Some((list, start)) = lists.pop() {
+                    let item = Item::List(list, TextRange::new(start, token.text_range().end()));
+                    lists.last_mut().map_or(&mut items, |(list, _)| list).push(item);
+                }
+            }
+            SyntaxKind::ANNOT_ELEM => {
+                lists
+                    .last_mut()
+                    .map_or(&mut items, |(list, _)| list)
+                    .push(Item::Atom(token));
+            }
+            _ => {}
+        }
+    }
+    (items, end)
+}
@@ -26,6 +26,7 @@ pub(crate) struct SymbolTable<'db> {
     resolved: Resolved,
     modules: Box<[ModuleDefSymbols]>,
     type_nodes: FxHashMap<SymbolKey, (GreenNode, TextRange)>,
+    name_annots: FxHashMap<SymbolKey, NameAnnot>,
 }
 fn create_symbol_table<'db>(db: &'db dyn salsa::Database, document: Document) -> SymbolTable<'db> {
     fn create_module_level_symbol<'db>(
@@ -144,6 +145,7 @@ fn create_symbol_table<'db>(db: &'db dyn salsa::Database, document: Document) ->
                         | SyntaxKind::BLOCK_LOOP
                         | SyntaxKind::BLOCK_IF
                         | SyntaxKind::BLOCK_TRY_TABLE
+                        | SyntaxKind::BLOCK_TRY
                         | SyntaxKind::MODULE_FIELD_FUNC
                 )
             })
@@ -170,6 +172,7 @@ fn create_symbol_table<'db>(db: &'db dyn salsa::Database, document: Document) ->
                         | SyntaxKind::BLOCK_LOOP
                         | SyntaxKind::BLOCK_IF
                         | SyntaxKind::BLOCK_TRY_TABLE
+                        | SyntaxKind::BLOCK_TRY
                 ) && symbols
                     .get(key)
                     .and_then(|other| other.idx.name)
@@ -194,9 +197,11 @@ fn create_symbol_table<'db>(db: &'db dyn salsa::Database, document: Document) ->
     let mut resolved = Vec::new();
     let mut modules = Vec::with_capacity(1);
     let mut type_nodes = FxHashMap::default();
+    let mut name_annots = FxHashMap::default();
     let bump = Bump::new();
     let mut pre_resolved = BumpHashMap::new_in(&bump);
-    root.children().enumerate().for_each(|(module_id, module)| {
+    let module_nodes = root.children_by_kind(SyntaxKind::MODULE);
+    module_nodes.enumerate().for_each(|(module_id, module)| {
         let module_key = module.into();
         let module_index = symbols.values.len() as u32;
         symbols.insert(Symbol {
@@ -288,6 +293,7 @@ fn create_symbol_table<'db>(db: &'db dyn salsa::Database, document: Document) ->
                                     },
                                 });
                             } else {
+                                record_single_local_annot(node, &mut name_annots);
                                 node.children_by_kind(ValType::can_cast).for_each(|val_type| {
                                     let key = val_type.into();
                                     locals.push((key, None, symbols.values.len() as u32));
@@ -322,6 +328,7 @@ fn create_symbol_table<'db>(db: &'db dyn salsa::Database, document: Document) ->
                                     },
                                 });
                             } else {
+                                record_single_local_annot(node, &mut name_annots);
                                 node.children_by_kind(ValType::can_cast).for_each(|val_type| {
                                     let key = val_type.into();
                                     locals.push((key, None, symbols.values.len() as u32));
@@ -446,7 +453,7 @@ fn create_symbol_table<'db>(db: &'db dyn salsa::Database, document: Document) ->
                                 }
                                 Some(
                                     "br" | "br_if" | "br_table" | "br_on_null" | "br_on_non_null" | "br_on_cast"
-                                    | "br_on_cast_fail",
+                                    | "br_on_cast_fail" | "rethrow",
                                 ) => {
                                     if let Some(region) = find_up_blocks(&node_stack).next().map(|node| node.into()) {
                                         node.children()
@@ -689,7 +696,8 @@ fn create_symbol_table<'db>(db: &'db dyn salsa::Database, document: Document) ->
                         SyntaxKind::BLOCK_BLOCK
                         | SyntaxKind::BLOCK_IF
                         | SyntaxKind::BLOCK_LOOP
-                        | SyntaxKind::BLOCK_TRY_TABLE => {
+                        | SyntaxKind::BLOCK_TRY_TABLE
+                        | SyntaxKind::BLOCK_TRY => {
                             if let Some(symbol) = find_up_blocks(&node_stack).next().map(|region| Symbol {
                                 key: node.into(),
                                 green: node.green().clone(),
@@ -916,6 +924,29 @@ fn create_symbol_table<'db>(db: &'db dyn salsa::Database, document: Document) ->
                                 symbols.insert(symbol);
                             }
                         }
+                        SyntaxKind::BLOCK_TRY_CATCH => {
+                            if let Some(symbol) = node
+                                .children_by_kind(SyntaxKind::INDEX)
+                                .next()
+                                .and_then(|node| create_ref_symbol(db, node, module_key, SymbolKind::TagRef))
+                            {
+                                symbols.insert(symbol);
+                            }
+                        }
+                        SyntaxKind::BLOCK_TRY_DELEGATE => {
+                            // label of `delegate` is resolved outside the `try` block
+                            if let Some(region) = find_up_blocks(&node_stack).nth(1).map(|node| node.into())
+                                && let Some(symbol) = node
+                                    .children_by_kind(SyntaxKind::INDEX)
+                                    .next()
+                                    .and_then(|node| create_ref_symbol(db, node, region, SymbolKind::BlockRef))
+                            {
+                                if let Some(def_key) = resolve_block_def(&symbol, &symbols, &node_stack, true) {
+                                    pre_resolved.insert(symbols.values.len() as u32, def_key);
+                                }
+                                symbols.insert(symbol);
+                            }
+                        }
                         SyntaxKind::ON_CLAUSE => {
                             let mut indexes = node.children_by_kind(SyntaxKind::INDEX);
                             if let Some(symbol) = indexes
@@ -1048,12 +1079,80 @@ fn create_symbol_table<'db>(db: &'db dyn salsa::Database, document: Document) ->
         });
     });
 
+    symbols
+        .values
+        .iter()
+        .filter(|symbol| {
+            matches!(
+                symbol.kind,
+                SymbolKind::Module
+                    | SymbolKind::Func
+                    | SymbolKind::Param
+                    | SymbolKind::Local
+                    | SymbolKind::Type
+                    | SymbolKind::GlobalDef
+                    | SymbolKind::MemoryDef
+                    | SymbolKind::TableDef
+                    | SymbolKind::BlockDef
+                    | SymbolKind::FieldDef
+                    | SymbolKind::TagDef
+                    | SymbolKind::DataDef
+                    | SymbolKind::ElemDef
+            )
+        })
+        .for_each(|symbol| {
+            // for imports, the annotation can also be placed in extern type
+            if let Some(annot) = find_name_annot(symbol.amber()).or_else(|| {
+                type_nodes
+                    .get(&symbol.key)
+                    .and_then(|(green, range)| find_name_annot(AmberNode::new(green, range.start())))
+            }) {
+                name_annots.entry(symbol.key).or_insert(annot);
+            }
+        });
+
     symbols.values.shrink_to_fit();
     SymbolTable {
         symbols,
         resolved: resolved.into_boxed_slice(),
         modules: modules.into_boxed_slice(),
         type_nodes,
+        name_annots,
+    }
+}
+
+/// Find `(@name "...")` annotation which is introduced by the name annotations proposal.
+fn find_name_annot(node: AmberNode) -> Option<NameAnnot> {
+    let mut tokens = node.children_with_tokens().filter_map(NodeOrToken::into_token);
+    tokens.find(|token| {
+        token.kind() == SyntaxKind::ANNOT_START
+            && token
+                .text()
+                .strip_prefix("(@")
+                .map(|s| s.strip_prefix('"').and_then(|s| s.strip_suffix('"')).unwrap_or(s))
+                .is_some_and(|name| name == "name")
+    })?;
+    tokens
+        .take_while(|token| token.kind() != SyntaxKind::ANNOT_END)
+        .find(|token| token.kind() == SyntaxKind::ANNOT_ELEM && token.text().starts_with('"'))
+        .map(|token| {
+            let mut bytes = Vec::with_capacity(token.text().len());
+            helpers::decode_string(token.text(), token.text_range().start(), &mut bytes, &mut vec![]);
+            NameAnnot {
+                name: String::from_utf8_lossy(&bytes).into_owned(),
+                literal: token.text().into(),
+                range: token.text_range(),
+            }
+        })
+}
+/// Symbols of params or locals without identifier are keyed by their types,
+/// so annotation on the whole param or local is only applicable when there's only one type.
+fn record_single_local_annot(node: AmberNode, name_annots: &mut FxHashMap<SymbolKey, NameAnnot>) {
+    let mut val_types = node.children_by_kind(ValType::can_cast);
+    if let (Some(val_type), None) = (val_types.next(), val_types.next())
+        && let Some(annot) = find_name_annot(node)
+    {
+        name_annots.insert(val_type.into(), annot);
     }
 }
 
@@ -1185,6 +1284,19 @@ impl<'db> SymbolTable<'db> {
             .unwrap_or(symbol.amber())
     }
 
+    /// Get `(@name "...")` annotation of definition symbol.
+    pub fn get_name_annot(&self, symbol: &Symbol) -> Option<&NameAnnot> {
+        self.name_annots.get(&symbol.key)
+    }
+    /// Get name of symbol for rendering headers like `(func $name ...)`,
+    /// falling back to the `(@name "...")` annotation if there's no identifier.
+    pub fn get_header_name(&self, db: &'db dyn salsa::Database, symbol: &Symbol<'db>) -> Option<InternIdent<'db>> {
+        symbol.idx.name.or_else(|| {
+            self.get_name_annot(symbol)
+                .map(|annot| InternIdent::new(db, format!("(@name {})", annot.literal)))
+        })
+    }
+
     pub fn iter_may_resolved(&self) -> slice::Iter<'_, Option<NonZeroU32>> {
         self.resolved.iter()
     }
@@ -1231,6 +1343,17 @@ impl Deref for SymbolKey {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Name from `(@name "...")` annotation.
+pub(crate) struct NameAnnot {
+    /// Decoded name.
+    pub name: String,
+    /// String literal as written.
+    pub literal: String,
+    /// Range of string literal.
+    pub range: TextRange,
+}
+
 #[derive(Clone, Debug, salsa::SalsaValue)]
 pub struct Symbol<'db> {
     pub key: SymbolKey,
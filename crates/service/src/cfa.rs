@@ -68,7 +68,7 @@ impl<'db, 'bump> Builder<'db, 'bump> {
                     });
 
                     let unreachable = match instr_name.text() {
-                        "unreachable" | "throw" | "throw_ref" => {
+                        "unreachable" | "throw" | "throw_ref" | "rethrow" => {
                             self.add_basic_block();
                             true
                         }
@@ -124,6 +124,7 @@ impl<'db, 'bump> Builder<'db, 'bump> {
                 SyntaxKind::BLOCK_BLOCK
                 | SyntaxKind::BLOCK_LOOP
                 | SyntaxKind::BLOCK_IF
+                | SyntaxKind::BLOCK_TRY
                 | SyntaxKind::BLOCK_TRY_TABLE => {
                     self.add_basic_block();
                     let block_entry = self
@@ -166,6 +167,23 @@ impl<'db, 'bump> Builder<'db, 'bump> {
                                 self.graph.add_edge(condition, block_exit);
                             }
                         }
+                        SyntaxKind::BLOCK_TRY => {
+                            self.block_stack.push((block_exit, ident));
+                            let saved_unreachable = self.unreachable;
+                            if let Some(do_block) = instr.children_by_kind(SyntaxKind::BLOCK_TRY_DO).next() {
+                                self.visit_block_like(do_block, block_exit);
+                            }
+                            // exception may be thrown from anywhere in `do` block
+                            instr
+                                .children_by_kind(|kind| {
+                                    matches!(kind, SyntaxKind::BLOCK_TRY_CATCH | SyntaxKind::BLOCK_TRY_CATCH_ALL)
+                                })
+                                .for_each(|handler| {
+                                    self.current = Some(block_entry);
+                                    self.unreachable = saved_unreachable;
+                                    self.visit_block_like(handler, block_exit);
+                                });
+                        }
                         SyntaxKind::BLOCK_TRY_TABLE => {
                             for index in instr
                                 .children_by_kind(Cat::can_cast)
@@ -256,6 +274,12 @@ impl<'db, 'bump> Builder<'db, 'bump> {
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct FlowNodeId(u32);
+impl FlowNodeId {
+    #[inline]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
 
 #[derive(Clone, PartialEq, Eq)]
 pub struct FlowNode {
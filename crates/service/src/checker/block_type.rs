@@ -2,7 +2,7 @@ use super::{Diagnostic, DiagnosticCtx};
 use crate::types_analyzer::CompositeType;
 use wat_syntax::{AmberNode, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "block-type";
+pub(super) const DIAGNOSTIC_CODE: &str = "block-type";
 
 pub fn check(ctx: &DiagnosticCtx, node: AmberNode) -> Option<Diagnostic> {
     let index = node
@@ -3,7 +3,7 @@ use crate::types_analyzer::{join_types, resolve_br_types};
 use bumpalo::collections::Vec as BumpVec;
 use wat_syntax::{AmberNode, AmberToken, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "br-table-branches";
+pub(super) const DIAGNOSTIC_CODE: &str = "br-table-branches";
 
 pub fn check(
     diagnostics: &mut Vec<Diagnostic>,
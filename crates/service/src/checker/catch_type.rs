@@ -7,7 +7,7 @@ use bumpalo::collections::Vec as BumpVec;
 use itertools::Itertools;
 use wat_syntax::{AmberNode, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "catch-type";
+pub(super) const DIAGNOSTIC_CODE: &str = "catch-type";
 
 pub fn check(ctx: &DiagnosticCtx, node: AmberNode) -> Option<Diagnostic> {
     let (label_index, results) = match node.kind() {
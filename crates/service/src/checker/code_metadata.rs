@@ -0,0 +1,139 @@
+use super::Diagnostic;
+use crate::{
+    LintLevel,
+    binder::{SymbolKind, SymbolTable},
+    code_metadata::{self, MetadataKind, Payload},
+    document::Document,
+    helpers,
+    idx::{Idx, InternIdent},
+};
+use lspt::DiagnosticSeverity;
+use wat_syntax::{
+    SyntaxKind, SyntaxNode,
+    ast::{AstNode, Instr, support},
+};
+
+pub(super) const DIAGNOSTIC_CODE: &str = "code-metadata";
+
+/// Report code metadata annotations that are misplaced or have malformed payloads.
+pub fn check(
+    db: &dyn salsa::Database,
+    diagnostics: &mut Vec<Diagnostic>,
+    lint_level: LintLevel,
+    document: Document,
+    root: &SyntaxNode,
+    symbol_table: &SymbolTable,
+) {
+    let severity = match lint_level {
+        LintLevel::Allow => return,
+        LintLevel::Hint => DiagnosticSeverity::Hint,
+        LintLevel::Warn => DiagnosticSeverity::Warning,
+        LintLevel::Deny => DiagnosticSeverity::Error,
+    };
+    code_metadata::get_code_metadata(db, document)
+        .iter()
+        .for_each(|metadata| {
+            let target = metadata.target.and_then(|key| key.to_node(root));
+            let placed = match metadata.kind {
+                MetadataKind::BranchHint => target.as_ref().is_some_and(|target| {
+                    target.kind() == SyntaxKind::BLOCK_IF || instr_name(target).is_some_and(|name| name == "br_if")
+                }),
+                MetadataKind::InstrFreq => target.as_ref().is_some_and(|target| {
+                    Instr::can_cast(target.kind())
+                        && target
+                            .ancestors()
+                            .any(|node| node.kind() == SyntaxKind::MODULE_FIELD_FUNC)
+                }),
+                MetadataKind::CallTargets => target.as_ref().and_then(instr_name).is_some_and(|name| {
+                    matches!(
+                        name.as_str(),
+                        "call_indirect" | "return_call_indirect" | "call_ref" | "return_call_ref"
+                    )
+                }),
+                // function level hint must come before the function body
+                MetadataKind::CompilationPriority => metadata
+                    .parent
+                    .to_node(root)
+                    .filter(|parent| parent.kind() == SyntaxKind::MODULE_FIELD_FUNC)
+                    .is_some_and(|func| {
+                        func.children()
+                            .find(|child| Instr::can_cast(child.kind()))
+                            .is_none_or(|instr| instr.text_range().start() >= metadata.range.end())
+                    }),
+            };
+            if !placed {
+                diagnostics.push(Diagnostic {
+                    range: metadata.range,
+                    severity,
+                    code: DIAGNOSTIC_CODE.into(),
+                    message: match metadata.kind {
+                        MetadataKind::BranchHint => "branch hint must be placed directly before `if` or `br_if`",
+                        MetadataKind::InstrFreq => {
+                            "instruction frequency hint must be placed directly before an instruction"
+                        }
+                        MetadataKind::CallTargets => {
+                            "call targets hint must be placed directly before `call_indirect` or `call_ref`"
+                        }
+                        MetadataKind::CompilationPriority => {
+                            "compilation priority hint must be placed in function header"
+                        }
+                    }
+                    .into(),
+                    ..Default::default()
+                });
+            }
+
+            match &metadata.payload {
+                Ok(Payload::CallTargets(targets)) => {
+                    let Some(module) = root
+                        .children_by_kind(SyntaxKind::MODULE)
+                        .find(|module| module.text_range().contains_range(metadata.range))
+                    else {
+                        return;
+                    };
+                    targets.iter().for_each(|target| {
+                        let idx = if target.idx.starts_with('$') {
+                            Idx {
+                                num: None,
+                                name: Some(InternIdent::new(db, &*target.idx)),
+                            }
+                        } else {
+                            Idx {
+                                num: helpers::parse_u32(&target.idx).ok(),
+                                name: None,
+                            }
+                        };
+                        if !idx.is_ref()
+                            || !symbol_table
+                                .get_declared(&module, SymbolKind::Func)
+                                .any(|func| idx.is_defined_by(&func.idx))
+                        {
+                            diagnostics.push(Diagnostic {
+                                range: target.range,
+                                severity,
+                                code: DIAGNOSTIC_CODE.into(),
+                                message: format!("call target `{}` isn't a function", target.idx),
+                                ..Default::default()
+                            });
+                        }
+                    });
+                }
+                Ok(..) => {}
+                Err(malformed) => diagnostics.push(Diagnostic {
+                    range: malformed.range,
+                    severity,
+                    code: DIAGNOSTIC_CODE.into(),
+                    message: malformed.message.clone(),
+                    ..Default::default()
+                }),
+            }
+        });
+}
+
+fn instr_name(node: &SyntaxNode) -> Option<String> {
+    if node.kind() == SyntaxKind::PLAIN_INSTR {
+        support::token(node, SyntaxKind::INSTR_NAME).map(|token| token.text().to_string())
+    } else {
+        None
+    }
+}
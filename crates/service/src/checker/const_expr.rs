@@ -5,7 +5,7 @@ use wat_syntax::{
     ast::{AstNode, Instr},
 };
 
-const DIAGNOSTIC_CODE: &str = "const-expr";
+pub(super) const DIAGNOSTIC_CODE: &str = "const-expr";
 
 pub fn check(node: AmberNode) -> Option<Diagnostic> {
     let mut first = None;
@@ -0,0 +1,48 @@
+use super::{Diagnostic, DiagnosticCtx};
+use crate::{
+    LintLevel,
+    const_fold::{self, FoldKind},
+    types_analyzer::InstrSigResolverCtx,
+};
+use lspt::DiagnosticSeverity;
+use wat_syntax::AmberNode;
+
+pub(super) const DIAGNOSTIC_CODE: &str = "const-fold";
+
+/// Report foldable constant expressions and operations that have no effect in the given block.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, ctx: &mut DiagnosticCtx, node: AmberNode) {
+    let severity = match ctx.config.lint.const_fold {
+        LintLevel::Allow => return,
+        LintLevel::Hint => DiagnosticSeverity::Hint,
+        LintLevel::Warn => DiagnosticSeverity::Warning,
+        LintLevel::Deny => DiagnosticSeverity::Error,
+    };
+    let Some(outer_block) = node.to_ptr().to_node(ctx.module) else {
+        return;
+    };
+    let foldables = const_fold::find_foldables(
+        &outer_block,
+        &InstrSigResolverCtx {
+            db: ctx.db,
+            document: ctx.document,
+            symbol_table: ctx.symbol_table,
+            def_types: ctx.def_types,
+            module: ctx.module,
+            module_id: ctx.module_id,
+            bump: ctx.bump,
+        },
+    );
+    diagnostics.extend(foldables.into_iter().map(|foldable| Diagnostic {
+        range: foldable.range,
+        severity,
+        code: DIAGNOSTIC_CODE.into(),
+        message: match foldable.kind {
+            FoldKind::Const(value) => format!("constant expression can be folded to `{value}`"),
+            FoldKind::Identity { instr_name, operand } => {
+                format!("`{instr_name}` with `{operand}` has no effect")
+            }
+        },
+        ..Default::default()
+    }));
+    ctx.bump.reset();
+}
@@ -2,7 +2,7 @@ use super::{Diagnostic, DiagnosticCtx, RelatedInformation};
 use crate::{binder::SymbolKey, types_analyzer::CompositeType};
 use wat_syntax::{AmberNode, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "cont-type";
+pub(super) const DIAGNOSTIC_CODE: &str = "cont-type";
 
 pub fn check(ctx: &DiagnosticCtx, node: AmberNode) -> Option<Diagnostic> {
     let index = node.children_by_kind(SyntaxKind::INDEX).next()?;
@@ -0,0 +1,63 @@
+use super::{Diagnostic, RelatedInformation};
+use crate::{custom_section, document::Document};
+use rustc_hash::FxHashMap;
+use wat_syntax::{SyntaxKind, SyntaxNode};
+
+pub(super) const DIAGNOSTIC_CODE: &str = "custom-section";
+
+/// Well-known custom sections that can appear at most once in a module.
+const SINGLETONS: [&str; 3] = ["name", "producers", "target_features"];
+
+/// Report custom section annotations that are misplaced, malformed or duplicated.
+pub fn check(db: &dyn salsa::Database, diagnostics: &mut Vec<Diagnostic>, document: Document, root: &SyntaxNode) {
+    let mut singletons = FxHashMap::<_, Vec<_>>::default();
+    custom_section::get_custom_sections(db, document)
+        .iter()
+        .for_each(|section| {
+            diagnostics.extend(section.errors.iter().map(|malformed| Diagnostic {
+                range: malformed.range,
+                code: DIAGNOSTIC_CODE.into(),
+                message: malformed.message.clone(),
+                ..Default::default()
+            }));
+            if section
+                .parent
+                .to_node(root)
+                .is_none_or(|parent| parent.kind() != SyntaxKind::MODULE)
+            {
+                diagnostics.push(Diagnostic {
+                    range: section.range,
+                    code: DIAGNOSTIC_CODE.into(),
+                    message: "custom section annotation must be placed directly in module".into(),
+                    ..Default::default()
+                });
+            } else if let Some((name, range)) = &section.name
+                && SINGLETONS.contains(&&**name)
+            {
+                singletons.entry((section.parent, name)).or_default().push(*range);
+            }
+        });
+    singletons
+        .into_iter()
+        .filter(|(_, ranges)| ranges.len() > 1)
+        .for_each(|((_, name), ranges)| {
+            diagnostics.extend(ranges.iter().map(|range| {
+                Diagnostic {
+                    range: *range,
+                    code: DIAGNOSTIC_CODE.into(),
+                    message: format!("duplicated custom section `{name}`"),
+                    related_information: Some(
+                        ranges
+                            .iter()
+                            .filter(|other| *other != range)
+                            .map(|range| RelatedInformation {
+                                range: *range,
+                                message: "already defined here".into(),
+                            })
+                            .collect(),
+                    ),
+                    ..Default::default()
+                }
+            }));
+        });
+}
@@ -6,8 +6,9 @@ use crate::{
     document::Document,
 };
 use lspt::{DiagnosticSeverity, DiagnosticTag};
+use wat_syntax::{AmberToken, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "deprecated";
+pub(super) const DIAGNOSTIC_CODE: &str = "deprecated";
 
 pub fn check(
     diagnostics: &mut Vec<Diagnostic>,
@@ -58,3 +59,29 @@ pub fn check(
         });
     diagnostics.extend(deprecated_usage);
 }
+
+/// Report legacy exception handling instructions, which are superseded by `try_table` and `throw_ref`.
+pub fn check_legacy_eh(lint_level: LintLevel, token: AmberToken) -> Option<Diagnostic> {
+    let severity = match lint_level {
+        LintLevel::Allow => return None,
+        LintLevel::Hint => DiagnosticSeverity::Hint,
+        LintLevel::Warn => DiagnosticSeverity::Warning,
+        LintLevel::Deny => DiagnosticSeverity::Error,
+    };
+    let replacement = match (token.kind(), token.text()) {
+        (SyntaxKind::KEYWORD, "try") => "try_table",
+        (SyntaxKind::INSTR_NAME, "rethrow") => "throw_ref",
+        _ => return None,
+    };
+    Some(Diagnostic {
+        range: token.text_range(),
+        severity,
+        code: DIAGNOSTIC_CODE.into(),
+        message: format!(
+            "`{}` from legacy exception handling is deprecated, use `{replacement}` instead",
+            token.text()
+        ),
+        tags: Some(vec![DiagnosticTag::Deprecated]),
+        ..Default::default()
+    })
+}
@@ -7,7 +7,7 @@ use crate::{
 };
 use bumpalo::{Bump, collections::Vec as BumpVec};
 
-const DIAGNOSTIC_CODE: &str = "duplicated-names";
+pub(super) const DIAGNOSTIC_CODE: &str = "duplicated-names";
 
 pub fn check(
     db: &dyn salsa::Database,
@@ -2,7 +2,7 @@ use super::{Diagnostic, DiagnosticCtx};
 use crate::types_analyzer::{self, RefType};
 use wat_syntax::{AmberNode, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "elem-type";
+pub(super) const DIAGNOSTIC_CODE: &str = "elem-type";
 
 pub fn check(ctx: &DiagnosticCtx, node: AmberNode) -> Option<Diagnostic> {
     let table = ctx.symbol_table.find_def(
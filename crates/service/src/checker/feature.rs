@@ -0,0 +1,108 @@
+use super::Diagnostic;
+use crate::{
+    config::{Features, Proposal},
+    data_set,
+};
+use lspt::DiagnosticSeverity;
+use wat_syntax::{AmberNode, SyntaxKind, TextRange};
+
+pub(super) const DIAGNOSTIC_CODE: &str = "feature";
+
+/// Report instructions, types and module fields that come from proposals not enabled by features config.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, features: &Features, root: AmberNode) {
+    // all proposals are enabled by default, so skip traversing the whole tree
+    if Proposal::ALL.iter().all(|proposal| features.is_enabled(*proposal)) {
+        return;
+    }
+
+    let mut report = |range: TextRange, what: &str, proposals: &[Proposal]| {
+        if let Some(proposal) = proposals.iter().find(|proposal| !features.is_enabled(**proposal)) {
+            diagnostics.push(Diagnostic {
+                range,
+                severity: DiagnosticSeverity::Error,
+                code: DIAGNOSTIC_CODE.into(),
+                message: format!("{what} requires the {} proposal, which isn't enabled", proposal.name()),
+                ..Default::default()
+            });
+        }
+    };
+    let mut memories = 0;
+    root.descendant_tokens().for_each(|(token, parent, _)| {
+        let text = token.text();
+        let range = token.text_range();
+        match (token.kind(), parent.kind()) {
+            (SyntaxKind::INSTR_NAME, _) => {
+                report(range, &format!("`{text}`"), data_set::get_instr_proposals(text));
+            }
+            (SyntaxKind::KEYWORD, SyntaxKind::BLOCK_TRY_TABLE) if text == "try_table" => {
+                report(range, "`try_table`", &[Proposal::ExceptionHandling]);
+            }
+            (SyntaxKind::KEYWORD, SyntaxKind::BLOCK_TRY) if text == "try" => {
+                report(range, "`try`", &[Proposal::ExceptionHandling]);
+            }
+            (SyntaxKind::KEYWORD, SyntaxKind::MODULE) if text == "module" => memories = 0,
+            (SyntaxKind::KEYWORD, SyntaxKind::MODULE_FIELD_MEMORY | SyntaxKind::EXTERN_TYPE_MEMORY)
+                if text == "memory" =>
+            {
+                memories += 1;
+                if memories > 1 {
+                    report(range, "multiple memories", &[Proposal::MultiMemory]);
+                }
+            }
+            (SyntaxKind::KEYWORD, SyntaxKind::MEM_TYPE) if text == "shared" => {
+                report(range, "shared memory", &[Proposal::Threads]);
+            }
+            (SyntaxKind::KEYWORD, SyntaxKind::MODULE_FIELD_TAG | SyntaxKind::EXTERN_TYPE_TAG) if text == "tag" => {
+                report(range, "tag", &[Proposal::ExceptionHandling]);
+            }
+            (SyntaxKind::KEYWORD, SyntaxKind::REC_TYPE) if text == "rec" => {
+                report(range, "recursive type group", &[Proposal::Gc]);
+            }
+            (SyntaxKind::KEYWORD, SyntaxKind::SUB_TYPE) if text == "sub" => {
+                report(range, "subtype declaration", &[Proposal::Gc]);
+            }
+            (SyntaxKind::KEYWORD, SyntaxKind::STRUCT_TYPE) if text == "struct" => {
+                report(range, "struct type", &[Proposal::Gc]);
+            }
+            (SyntaxKind::KEYWORD, SyntaxKind::ARRAY_TYPE) if text == "array" => {
+                report(range, "array type", &[Proposal::Gc]);
+            }
+            (SyntaxKind::KEYWORD, SyntaxKind::CONT_TYPE) if text == "cont" => {
+                report(range, "continuation type", &[Proposal::StackSwitching]);
+            }
+            // `(ref null func)` and `(ref null extern)` are the same as `funcref` and `externref`
+            (SyntaxKind::KEYWORD, SyntaxKind::REF_TYPE)
+                if text == "ref"
+                    && (parent.tokens_by_kind(SyntaxKind::MODIFIER_KEYWORD).next().is_none()
+                        || parent
+                            .children_by_kind(SyntaxKind::HEAP_TYPE)
+                            .any(|heap_type| heap_type.tokens_by_kind(SyntaxKind::TYPE_KEYWORD).next().is_none())) =>
+            {
+                report(range, "typed reference", &[Proposal::FunctionReferences]);
+            }
+            (SyntaxKind::TYPE_KEYWORD, SyntaxKind::ADDR_TYPE) if text == "i64" => {
+                report(range, "64-bit address type", &[Proposal::Memory64]);
+            }
+            (SyntaxKind::TYPE_KEYWORD, SyntaxKind::VEC_TYPE) => {
+                report(range, &format!("type `{text}`"), &[Proposal::Simd]);
+            }
+            (SyntaxKind::TYPE_KEYWORD, SyntaxKind::REF_TYPE | SyntaxKind::HEAP_TYPE) => {
+                if let Some(proposal) = get_ref_type_proposal(text) {
+                    report(range, &format!("type `{text}`"), &[proposal]);
+                }
+            }
+            _ => {}
+        }
+    });
+}
+
+fn get_ref_type_proposal(name: &str) -> Option<Proposal> {
+    match name {
+        "externref" => Some(Proposal::ReferenceTypes),
+        "anyref" | "eqref" | "i31ref" | "structref" | "arrayref" | "nullref" | "nullfuncref" | "nullexternref"
+        | "any" | "eq" | "i31" | "struct" | "array" | "none" | "nofunc" | "noextern" => Some(Proposal::Gc),
+        "exnref" | "nullexnref" | "exn" | "noexn" => Some(Proposal::ExceptionHandling),
+        "contref" | "nullcontref" | "cont" | "nocont" => Some(Proposal::StackSwitching),
+        _ => None,
+    }
+}
@@ -5,7 +5,7 @@ use wat_syntax::{
     ast::{AstNode, ModuleField},
 };
 
-const DIAGNOSTIC_CODE: &str = "import-occurrence";
+pub(super) const DIAGNOSTIC_CODE: &str = "import-occurrence";
 
 pub fn check(diagnostics: &mut Vec<Diagnostic>, imports: &[SymbolKey], node: AmberNode) {
     diagnostics.extend(
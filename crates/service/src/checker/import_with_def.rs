@@ -1,7 +1,7 @@
 use super::{Diagnostic, DiagnosticCtx, RelatedInformation};
 use wat_syntax::{AmberNode, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "import-with-def";
+pub(super) const DIAGNOSTIC_CODE: &str = "import-with-def";
 
 pub fn check(ctx: &mut DiagnosticCtx, node: AmberNode) -> Option<Diagnostic> {
     if !ctx.imports.contains(&node.into()) {
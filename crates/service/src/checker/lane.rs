@@ -3,7 +3,7 @@ use crate::helpers;
 use std::num::IntErrorKind;
 use wat_syntax::{AmberNode, AmberToken, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "lane";
+pub(super) const DIAGNOSTIC_CODE: &str = "lane";
 
 pub fn check(diagnostics: &mut Vec<Diagnostic>, node: AmberNode, instr_name: AmberToken) -> Option<()> {
     match instr_name.text().split_once('.')? {
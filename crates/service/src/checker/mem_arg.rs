@@ -1,12 +1,13 @@
 use super::{Diagnostic, DiagnosticCtx, RelatedInformation};
 use crate::{
+    binder::Symbol,
     helpers,
     types_analyzer::{self, ValType},
 };
 use std::num::IntErrorKind;
 use wat_syntax::{AmberNode, AmberToken, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "mem-arg";
+pub(super) const DIAGNOSTIC_CODE: &str = "mem-arg";
 
 pub fn check(
     diagnostics: &mut Vec<Diagnostic>,
@@ -14,30 +15,16 @@ pub fn check(
     node: AmberNode,
     instr_name: AmberToken,
 ) -> Option<()> {
-    let (nt, action) = instr_name.text().split_once('.')?;
+    let (_, action) = instr_name.text().split_once('.')?;
     let rest = action.strip_prefix("store").or_else(|| action.strip_prefix("load"))?;
     // check if instr name is applicable
     if !(rest.is_empty() || rest.starts_with(|c: char| c.is_ascii_digit())) {
         return None;
     }
-    let mut immediates = node.children_by_kind(SyntaxKind::IMMEDIATE).peekable();
-    let mem_def = if let Some(immediate) = immediates.next_if(|immediate| {
-        immediate
-            .tokens_by_kind([SyntaxKind::IDENT, SyntaxKind::INT, SyntaxKind::UNSIGNED_INT])
-            .next()
-            .is_some()
-    }) {
-        ctx.symbol_table.find_def(immediate.into())
-    } else {
-        ctx.symbol_table
-            .find_module(ctx.module_id)?
-            .memories
-            .first()
-            .and_then(|index| ctx.symbol_table.symbols.get_index(*index))
-    };
+    let mem_def = resolve_memory(ctx, node);
     let mut has_align = false;
     let mut has_offset = false;
-    immediates
+    node.children_by_kind(SyntaxKind::IMMEDIATE)
         .filter_map(|immediate| immediate.children_by_kind(SyntaxKind::MEM_ARG).next())
         .for_each(|mem_arg| {
             match mem_arg
@@ -55,18 +42,8 @@ pub fn check(
                         });
                     }
                     has_align = true;
-                    let ty_size = match rest.split_once('_').map(|(left, _)| left).unwrap_or(rest) {
-                        "8" => 1,
-                        "16" => 2,
-                        "32" => 4,
-                        "64" | "8x8" | "16x4" | "32x2" => 8,
-                        "" => match nt {
-                            "i32" | "f32" => 4,
-                            "i64" | "f64" => 8,
-                            "v128" => 16,
-                            _ => return,
-                        },
-                        _ => return,
+                    let Some(ty_size) = access_size(instr_name.text()) else {
+                        return;
                     };
                     let Some(align) = mem_arg
                         .tokens_by_kind(SyntaxKind::UNSIGNED_INT)
@@ -128,3 +105,41 @@ pub fn check(
         });
     None
 }
+
+/// Get number of bytes accessed by memory instruction, which is the same as its natural alignment.
+pub(super) fn access_size(instr_name: &str) -> Option<u32> {
+    helpers::natural_align(instr_name).map(|align| 1 << align)
+}
+
+/// Find memory accessed by memory instruction, which is the first memory if omitted.
+pub(super) fn resolve_memory<'db>(ctx: &DiagnosticCtx<'db, '_>, node: AmberNode) -> Option<&'db Symbol<'db>> {
+    if let Some(immediate) = node.children_by_kind(SyntaxKind::IMMEDIATE).next().filter(|immediate| {
+        immediate
+            .tokens_by_kind([SyntaxKind::IDENT, SyntaxKind::INT, SyntaxKind::UNSIGNED_INT])
+            .next()
+            .is_some()
+    }) {
+        ctx.symbol_table.find_def(immediate.into())
+    } else {
+        ctx.symbol_table
+            .find_module(ctx.module_id)?
+            .memories
+            .first()
+            .and_then(|index| ctx.symbol_table.symbols.get_index(*index))
+    }
+}
+
+/// Get static offset of memory instruction, which is 0 if omitted.
+pub(super) fn get_offset(node: AmberNode) -> Option<u64> {
+    node.children_by_kind(SyntaxKind::IMMEDIATE)
+        .filter_map(|immediate| immediate.children_by_kind(SyntaxKind::MEM_ARG).next())
+        .find(|mem_arg| {
+            mem_arg
+                .tokens_by_kind(SyntaxKind::MEM_ARG_KEYWORD)
+                .next()
+                .is_some_and(|keyword| keyword.text() == "offset")
+        })
+        .map_or(Some(0), |mem_arg| {
+            helpers::parse_u64(mem_arg.tokens_by_kind(SyntaxKind::UNSIGNED_INT).next()?.text()).ok()
+        })
+}
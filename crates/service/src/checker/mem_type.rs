@@ -6,7 +6,7 @@ use crate::{
 use std::num::IntErrorKind;
 use wat_syntax::{AmberNode, AmberToken, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "mem-type";
+pub(super) const DIAGNOSTIC_CODE: &str = "mem-type";
 
 pub fn check(diagnostics: &mut Vec<Diagnostic>, node: AmberNode) -> Option<()> {
     let limits = node.children_by_kind(SyntaxKind::LIMITS).next()?;
@@ -16,7 +16,7 @@ pub fn check(diagnostics: &mut Vec<Diagnostic>, node: AmberNode) -> Option<()> {
         .and_then(|page_size| page_size.tokens_by_kind(SyntaxKind::UNSIGNED_INT).next())
         && let Ok(page_size) = helpers::parse_u32(token.text())
     {
-        if page_size == 1 || page_size == 65536 {
+        if helpers::is_valid_mem_page_size(page_size) {
             page_size
         } else {
             diagnostics.push(Diagnostic {
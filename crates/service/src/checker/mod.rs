@@ -3,45 +3,56 @@ use crate::{
     config::ServiceConfig,
     document::Document,
     helpers::LineIndexExt,
-    imex,
-    types_analyzer::{DefTypes, get_def_types},
+    imex::{self, LinkedModules},
+    script,
+    types_analyzer::{DefTypes, OperandType, Sig, get_def_types},
 };
 use bumpalo::Bump;
 use lspt::{
     DiagnosticRelatedInformation, DiagnosticSeverity, DiagnosticTag, Location, NumberOrString, StringOrMarkupContent,
 };
-use std::cmp::Ordering;
+use std::{cmp::Ordering, iter};
 use wat_syntax::{NodeOrToken, SyntaxKind, SyntaxNode, TextRange};
 
 mod block_type;
 mod br_table_branches;
 mod catch_type;
+mod code_metadata;
 mod const_expr;
+mod const_fold;
 mod cont_type;
+mod custom_section;
 mod deprecated;
 mod dup_names;
 mod elem_type;
+mod feature;
 mod implicit_module;
 mod import_occur;
 mod import_with_def;
 mod lane;
 mod mem_arg;
 mod mem_type;
+mod module_binary;
+mod module_quote;
 mod multi_modules;
 mod multi_starts;
 mod mutated_immutable;
+mod name_annot;
 mod needless_mut;
 mod needless_try_table;
 mod new_non_defaultable;
 mod omitted_idx_in_instr;
 mod packing;
 mod plain_instr;
+mod segment_bounds;
 mod shadow;
 mod start;
 mod subtyping;
+mod suppression;
 mod syntax;
 mod table_type;
 mod tag_type;
+mod trap;
 mod type_misuse;
 mod typeck;
 mod undef;
@@ -51,7 +62,65 @@ mod unread;
 mod unused;
 mod useless_catch;
 
-pub fn check(db: &dyn salsa::Database, uri: &str, document: Document, config: &ServiceConfig) -> Vec<lspt::Diagnostic> {
+/// Codes of all diagnostics, and whether each of them has a page in `docs/diagnostics`.
+///
+/// Codes are stable, so they can be used as rule IDs by external tools.
+pub(crate) const RULES: &[(&str, bool)] = &[
+    (block_type::DIAGNOSTIC_CODE, false),
+    (br_table_branches::DIAGNOSTIC_CODE, false),
+    (catch_type::DIAGNOSTIC_CODE, false),
+    (code_metadata::DIAGNOSTIC_CODE, false),
+    (const_expr::DIAGNOSTIC_CODE, true),
+    (const_fold::DIAGNOSTIC_CODE, false),
+    (cont_type::DIAGNOSTIC_CODE, false),
+    (custom_section::DIAGNOSTIC_CODE, false),
+    (deprecated::DIAGNOSTIC_CODE, false),
+    (dup_names::DIAGNOSTIC_CODE, true),
+    (elem_type::DIAGNOSTIC_CODE, false),
+    (feature::DIAGNOSTIC_CODE, false),
+    (implicit_module::DIAGNOSTIC_CODE, false),
+    (import_occur::DIAGNOSTIC_CODE, false),
+    (import_with_def::DIAGNOSTIC_CODE, false),
+    (lane::DIAGNOSTIC_CODE, false),
+    (mem_arg::DIAGNOSTIC_CODE, false),
+    (mem_type::DIAGNOSTIC_CODE, false),
+    (module_binary::DIAGNOSTIC_CODE, false),
+    (multi_modules::DIAGNOSTIC_CODE, false),
+    (multi_starts::DIAGNOSTIC_CODE, false),
+    (mutated_immutable::DIAGNOSTIC_CODE, true),
+    (name_annot::DIAGNOSTIC_CODE, false),
+    (needless_mut::DIAGNOSTIC_CODE, true),
+    (needless_try_table::DIAGNOSTIC_CODE, false),
+    (new_non_defaultable::DIAGNOSTIC_CODE, true),
+    (omitted_idx_in_instr::DIAGNOSTIC_CODE, false),
+    (packing::DIAGNOSTIC_CODE, true),
+    (plain_instr::DIAGNOSTIC_CODE, false),
+    (segment_bounds::DIAGNOSTIC_CODE, false),
+    (shadow::DIAGNOSTIC_CODE, true),
+    (start::DIAGNOSTIC_CODE, false),
+    (subtyping::DIAGNOSTIC_CODE, true),
+    (suppression::DIAGNOSTIC_CODE, false),
+    (syntax::DIAGNOSTIC_CODE, false),
+    (table_type::DIAGNOSTIC_CODE, false),
+    (tag_type::DIAGNOSTIC_CODE, false),
+    (trap::DIAGNOSTIC_CODE, false),
+    (type_misuse::DIAGNOSTIC_CODE, true),
+    (typeck::DIAGNOSTIC_CODE, true),
+    (undef::DIAGNOSTIC_CODE, true),
+    (uninit::DIAGNOSTIC_CODE, true),
+    (unreachable::DIAGNOSTIC_CODE, true),
+    (unread::DIAGNOSTIC_CODE, true),
+    (unused::DIAGNOSTIC_CODE, true),
+    (useless_catch::DIAGNOSTIC_CODE, false),
+];
+
+pub fn check(
+    db: &dyn salsa::Database,
+    uri: &str,
+    document: Document,
+    config: &ServiceConfig,
+    linked_modules: &LinkedModules,
+) -> Vec<lspt::Diagnostic> {
     let mut bump = Bump::with_capacity(32 * 1024);
 
     let line_index = document.line_index(db);
@@ -63,7 +132,8 @@ pub fn check(db: &dyn salsa::Database, uri: &str, document: Document, config: &S
     let mut diagnostics = Vec::with_capacity(4);
     syntax::check(db, &mut diagnostics, document);
     multi_modules::check(&mut diagnostics, config.lint.multi_modules, root.amber());
-    root.children().enumerate().for_each(|(module_id, module)| {
+    let modules = root.children_by_kind(SyntaxKind::MODULE);
+    modules.enumerate().for_each(|(module_id, module)| {
         if let Some(diagnostic) = implicit_module::check(config.lint.implicit_module, module.amber()) {
             diagnostics.push(diagnostic);
         }
@@ -87,6 +157,8 @@ pub fn check(db: &dyn salsa::Database, uri: &str, document: Document, config: &S
                         SyntaxKind::MODULE_FIELD_FUNC => {
                             typeck::check_func(&mut diagnostics, &mut ctx, node);
                             unreachable::check(&mut diagnostics, &mut ctx, node);
+                            const_fold::check(&mut diagnostics, &mut ctx, node);
+                            trap::check(&mut diagnostics, &mut ctx, node);
                             if let Some(diagnostic) = import_with_def::check(&mut ctx, node) {
                                 diagnostics.push(diagnostic);
                             }
@@ -111,6 +183,11 @@ pub fn check(db: &dyn salsa::Database, uri: &str, document: Document, config: &S
                                 if let Some(diagnostic) = new_non_defaultable::check(&ctx, node, instr_name) {
                                     diagnostics.push(diagnostic);
                                 }
+                                if let Some(diagnostic) =
+                                    deprecated::check_legacy_eh(ctx.config.lint.deprecated, instr_name)
+                                {
+                                    diagnostics.push(diagnostic);
+                                }
                                 mem_arg::check(&mut diagnostics, &ctx, node, instr_name);
                                 lane::check(&mut diagnostics, node, instr_name);
                                 if let Some(diagnostic) =
@@ -121,10 +198,25 @@ pub fn check(db: &dyn salsa::Database, uri: &str, document: Document, config: &S
                             }
                             ctx.bump.reset();
                         }
-                        SyntaxKind::BLOCK_BLOCK | SyntaxKind::BLOCK_LOOP | SyntaxKind::BLOCK_IF => {
+                        SyntaxKind::BLOCK_BLOCK | SyntaxKind::BLOCK_LOOP => {
                             if let Some(diagnostic) = block_type::check(&ctx, node) {
                                 diagnostics.push(diagnostic);
                             }
+                            const_fold::check(&mut diagnostics, &mut ctx, node);
+                            trap::check(&mut diagnostics, &mut ctx, node);
+                        }
+                        SyntaxKind::BLOCK_IF => {
+                            if let Some(diagnostic) = block_type::check(&ctx, node) {
+                                diagnostics.push(diagnostic);
+                            }
+                        }
+                        SyntaxKind::BLOCK_IF_THEN
+                        | SyntaxKind::BLOCK_IF_ELSE
+                        | SyntaxKind::BLOCK_TRY_DO
+                        | SyntaxKind::BLOCK_TRY_CATCH
+                        | SyntaxKind::BLOCK_TRY_CATCH_ALL => {
+                            const_fold::check(&mut diagnostics, &mut ctx, node);
+                            trap::check(&mut diagnostics, &mut ctx, node);
                         }
                         SyntaxKind::MODULE_FIELD_START => {
                             if let Some(diagnostic) = start::check(&ctx, node) {
@@ -144,6 +236,14 @@ pub fn check(db: &dyn salsa::Database, uri: &str, document: Document, config: &S
                             if let Some(diagnostic) = elem_type::check(&ctx, node) {
                                 diagnostics.push(diagnostic);
                             }
+                            if let Some(diagnostic) = segment_bounds::check_elem(&ctx, node) {
+                                diagnostics.push(diagnostic);
+                            }
+                        }
+                        SyntaxKind::MODULE_FIELD_DATA => {
+                            if let Some(diagnostic) = segment_bounds::check_data(&ctx, node) {
+                                diagnostics.push(diagnostic);
+                            }
                         }
                         SyntaxKind::MODULE_FIELD_MEMORY => {
                             if let Some(diagnostic) = import_with_def::check(&mut ctx, node) {
@@ -157,7 +257,7 @@ pub fn check(db: &dyn salsa::Database, uri: &str, document: Document, config: &S
                             table_type::check(&mut diagnostics, node);
                         }
                         SyntaxKind::OFFSET => {
-                            typeck::check_offset(&mut diagnostics, &mut ctx, node);
+                            typeck::check_offset(&mut diagnostics, &mut ctx, node, *parent);
                             if let Some(diagnostic) = const_expr::check(node) {
                                 diagnostics.push(diagnostic);
                             }
@@ -179,6 +279,18 @@ pub fn check(db: &dyn salsa::Database, uri: &str, document: Document, config: &S
                         SyntaxKind::EXTERN_TYPE_TAG => {
                             tag_type::check(&mut diagnostics, &ctx, node);
                         }
+                        SyntaxKind::BLOCK_TRY => {
+                            if let Some(diagnostic) = block_type::check(&ctx, node) {
+                                diagnostics.push(diagnostic);
+                            }
+                            if let Some(diagnostic) = node
+                                .tokens_by_kind(SyntaxKind::KEYWORD)
+                                .next()
+                                .and_then(|keyword| deprecated::check_legacy_eh(ctx.config.lint.deprecated, keyword))
+                            {
+                                diagnostics.push(diagnostic);
+                            }
+                        }
                         SyntaxKind::BLOCK_TRY_TABLE => {
                             if let Some(diagnostic) =
                                 needless_try_table::check(ctx.config.lint.needless_try_table, node)
@@ -189,6 +301,8 @@ pub fn check(db: &dyn salsa::Database, uri: &str, document: Document, config: &S
                             if let Some(diagnostic) = block_type::check(&ctx, node) {
                                 diagnostics.push(diagnostic);
                             }
+                            const_fold::check(&mut diagnostics, &mut ctx, node);
+                            trap::check(&mut diagnostics, &mut ctx, node);
                         }
                         SyntaxKind::CATCH | SyntaxKind::CATCH_ALL => {
                             if let Some(diagnostic) = catch_type::check(&ctx, node) {
@@ -231,6 +345,53 @@ pub fn check(db: &dyn salsa::Database, uri: &str, document: Document, config: &S
         multi_starts::check(&mut diagnostics, module.amber());
         import_occur::check(&mut diagnostics, imports, module.amber());
     });
+    root.amber()
+        .children()
+        .filter(|command| command.kind() != SyntaxKind::ASSERT_MALFORMED)
+        .flat_map(|command| iter::once(command).chain(command.children()))
+        .for_each(|node| match node.kind() {
+            SyntaxKind::MODULE_BINARY => module_binary::check(&mut diagnostics, node),
+            SyntaxKind::MODULE_QUOTE => module_quote::check(&mut diagnostics, node),
+            _ => {}
+        });
+    script::get_actions(db, document)
+        .iter()
+        .filter(|action| action.key.kind() == SyntaxKind::ACTION_INVOKE)
+        .for_each(|action| {
+            let Some(module_id) = action.module_id else {
+                return;
+            };
+            let Some(func) = action
+                .def_key
+                .and_then(|def_key| symbol_table.symbols.get(def_key))
+                .filter(|symbol| symbol.kind == SymbolKind::Func)
+            else {
+                return;
+            };
+            let (Some(module), Some(node)) = (
+                root.children_by_kind(SyntaxKind::MODULE).nth(module_id as usize),
+                action.key.to_node(&root),
+            ) else {
+                return;
+            };
+            let params = Sig::from_func(db, document, symbol_table.get_type_node_of(func))
+                .params
+                .into_iter()
+                .map(OperandType::Val)
+                .collect::<Vec<_>>();
+            let mut ctx = DiagnosticCtx {
+                db,
+                document,
+                config,
+                symbol_table,
+                def_types,
+                imports,
+                module: &module,
+                module_id,
+                bump: &mut bump,
+            };
+            typeck::check_invoke(&mut diagnostics, &mut ctx, node.amber(), &params);
+        });
     symbol_table
         .symbols
         .iter()
@@ -243,25 +404,42 @@ pub fn check(db: &dyn salsa::Database, uri: &str, document: Document, config: &S
         })
         .for_each(|(local, func)| {
             uninit::check(&mut diagnostics, db, symbol_table, func, local, &bump);
-            unread::check(
-                &mut diagnostics,
-                db,
-                config.lint.unread,
-                symbol_table,
-                func,
-                local,
-                &bump,
-            );
             bump.reset();
         });
+    symbol_table
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.kind == SymbolKind::Func && symbol.key.kind() == SyntaxKind::MODULE_FIELD_FUNC)
+        .for_each(|func| {
+            unread::check(&mut diagnostics, db, config.lint.unread, document, symbol_table, func);
+        });
     undef::check(db, &mut diagnostics, symbol_table);
+    undef::check_linked_imports(db, &mut diagnostics, document, imports, linked_modules);
     dup_names::check(db, &mut diagnostics, document, symbol_table, &mut bump);
     unused::check(db, &mut diagnostics, config.lint.unused, symbol_table, imports, &bump);
     shadow::check(db, &mut diagnostics, config.lint.shadow, symbol_table, &mut bump);
     mutated_immutable::check(db, &mut diagnostics, document, symbol_table);
     needless_mut::check(db, &mut diagnostics, config.lint.needless_mut, document, symbol_table);
     subtyping::check(&mut diagnostics, db, document, symbol_table, def_types);
+    typeck::check_linked_imports(&mut diagnostics, db, document, symbol_table, imports, linked_modules);
     deprecated::check(&mut diagnostics, db, document, config.lint.deprecated, symbol_table);
+    code_metadata::check(
+        db,
+        &mut diagnostics,
+        config.lint.code_metadata,
+        document,
+        &root,
+        symbol_table,
+    );
+    name_annot::check(db, &mut diagnostics, config.lint.name_annotation, symbol_table);
+    custom_section::check(db, &mut diagnostics, document, &root);
+    feature::check(&mut diagnostics, &config.features, root.amber());
+    suppression::check(
+        &mut diagnostics,
+        config.lint.unused_suppression,
+        root.amber(),
+        document.text(db),
+    );
 
     diagnostics.sort_unstable_by(|a, b| match a.code.cmp(&b.code) {
         Ordering::Equal => a.range.ordering(b.range),
@@ -0,0 +1,87 @@
+use super::Diagnostic;
+use crate::helpers;
+use wat_syntax::{AmberNode, SyntaxKind, TextRange};
+
+pub(super) const DIAGNOSTIC_CODE: &str = "malformed-binary";
+
+pub fn check(diagnostics: &mut Vec<Diagnostic>, node: AmberNode) {
+    let mut bytes = Vec::new();
+    let mut offsets = Vec::new();
+    node.tokens_by_kind(SyntaxKind::STRING).for_each(|string| {
+        // drop the offset of previous closing quote, since strings are concatenated
+        offsets.pop();
+        helpers::decode_string(string.text(), string.text_range().start(), &mut bytes, &mut offsets);
+    });
+    let fallback = node
+        .tokens_by_kind(SyntaxKind::KEYWORD)
+        .nth(1)
+        .map_or_else(|| node.text_range(), |keyword| keyword.text_range());
+    let range = |start: usize, end: usize| {
+        offsets
+            .get(start)
+            .zip(offsets.get(end))
+            .map_or(fallback, |(start, end)| TextRange::new(*start, *end))
+    };
+
+    if bytes.get(0..4) != Some(b"\0asm") {
+        diagnostics.push(Diagnostic {
+            range: range(0, bytes.len().min(4)),
+            code: DIAGNOSTIC_CODE.into(),
+            message: "bad magic number, expected `\\00asm`".into(),
+            ..Default::default()
+        });
+        return;
+    }
+    if bytes.get(4..8) != Some(&[1, 0, 0, 0]) {
+        diagnostics.push(Diagnostic {
+            range: range(4, bytes.len().min(8)),
+            code: DIAGNOSTIC_CODE.into(),
+            message: "bad version, expected `\\01\\00\\00\\00`".into(),
+            ..Default::default()
+        });
+        return;
+    }
+
+    let mut pos = 8;
+    while let Some(id) = bytes.get(pos) {
+        let start = pos;
+        pos += 1;
+        let Some((size, len)) = read_u32(&bytes[pos..]) else {
+            diagnostics.push(Diagnostic {
+                range: range(start, bytes.len()),
+                code: DIAGNOSTIC_CODE.into(),
+                message: format!("section {id} is truncated: missing or malformed section size"),
+                ..Default::default()
+            });
+            return;
+        };
+        pos += len;
+        let remaining = bytes.len() - pos;
+        if size as usize > remaining {
+            diagnostics.push(Diagnostic {
+                range: range(start, bytes.len()),
+                code: DIAGNOSTIC_CODE.into(),
+                message: format!("section {id} is truncated: expected {size} bytes, found {remaining} bytes"),
+                ..Default::default()
+            });
+            return;
+        }
+        pos += size as usize;
+    }
+}
+
+/// Read unsigned LEB128 integer, returning the value and the number of bytes consumed.
+fn read_u32(bytes: &[u8]) -> Option<(u32, usize)> {
+    let mut value = 0u32;
+    for (i, byte) in bytes.iter().take(5).enumerate() {
+        // the last byte can only contain the remaining 4 bits, which means no continuation or unused bits
+        if i == 4 && *byte > 0x0f {
+            return None;
+        }
+        value |= ((byte & 0x7f) as u32) << (i * 7);
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
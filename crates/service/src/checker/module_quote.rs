@@ -0,0 +1,43 @@
+use super::Diagnostic;
+use crate::helpers;
+use wat_syntax::{AmberNode, SyntaxKind, TextRange, TextSize};
+
+pub(super) const DIAGNOSTIC_CODE: &str = "syntax";
+
+/// Parse quoted module as nested WAT,
+/// and map syntax errors back to the original strings.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, node: AmberNode) {
+    let mut bytes = Vec::new();
+    let mut offsets = Vec::<TextSize>::new();
+    node.tokens_by_kind(SyntaxKind::STRING).for_each(|string| {
+        if !offsets.is_empty() {
+            // strings are joined with whitespace, which is mapped to previous closing quote
+            bytes.push(b' ');
+        }
+        helpers::decode_string(string.text(), string.text_range().start(), &mut bytes, &mut offsets);
+    });
+    let source = match String::from_utf8(bytes) {
+        Ok(source) => source,
+        Err(error) => {
+            let start = error.utf8_error().valid_up_to();
+            diagnostics.push(Diagnostic {
+                range: TextRange::new(offsets[start], offsets[start + 1]),
+                code: DIAGNOSTIC_CODE.into(),
+                message: "syntax error: malformed UTF-8 encoding".into(),
+                ..Default::default()
+            });
+            return;
+        }
+    };
+    let (_, errors) = wat_parser::parse(&source);
+    diagnostics.extend(errors.into_iter().filter_map(|error| {
+        let start = offsets.get(usize::from(error.range.start()))?;
+        let end = offsets.get(usize::from(error.range.end()))?;
+        Some(Diagnostic {
+            range: TextRange::new(*start, *end),
+            code: DIAGNOSTIC_CODE.into(),
+            message: format!("syntax error: {}", error.message),
+            ..Default::default()
+        })
+    }));
+}
@@ -3,7 +3,7 @@ use crate::config::LintLevel;
 use lspt::DiagnosticSeverity;
 use wat_syntax::{AmberNode, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "multiple-modules";
+pub(super) const DIAGNOSTIC_CODE: &str = "multiple-modules";
 
 pub fn check(diagnostics: &mut Vec<Diagnostic>, lint_level: LintLevel, root: AmberNode) {
     let severity = match lint_level {
@@ -12,6 +12,10 @@ pub fn check(diagnostics: &mut Vec<Diagnostic>, lint_level: LintLevel, root: Amb
         LintLevel::Warn => DiagnosticSeverity::Warning,
         LintLevel::Deny => DiagnosticSeverity::Error,
     };
+    if root.children().any(|child| child.kind() != SyntaxKind::MODULE) {
+        // it's a script which is allowed to have multiple modules
+        return;
+    }
     diagnostics.extend(
         root.children_by_kind(SyntaxKind::MODULE)
             .skip(1)
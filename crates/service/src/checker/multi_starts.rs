@@ -1,7 +1,7 @@
 use super::Diagnostic;
 use wat_syntax::{AmberNode, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "multiple-starts";
+pub(super) const DIAGNOSTIC_CODE: &str = "multiple-starts";
 
 pub fn check(diagnostics: &mut Vec<Diagnostic>, module: AmberNode) {
     diagnostics.extend(
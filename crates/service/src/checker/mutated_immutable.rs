@@ -5,7 +5,7 @@ use crate::{
     mutability,
 };
 
-const DIAGNOSTIC_CODE: &str = "mutated-immutable";
+pub(super) const DIAGNOSTIC_CODE: &str = "mutated-immutable";
 
 pub fn check(
     db: &dyn salsa::Database,
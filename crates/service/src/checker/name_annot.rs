@@ -0,0 +1,44 @@
+use super::{Diagnostic, RelatedInformation};
+use crate::{LintLevel, binder::SymbolTable, helpers};
+use lspt::DiagnosticSeverity;
+use wat_syntax::SyntaxKind;
+
+pub(super) const DIAGNOSTIC_CODE: &str = "name-annotation";
+
+/// Report `(@name "...")` annotation that doesn't match the identifier of the same definition.
+pub fn check(
+    db: &dyn salsa::Database,
+    diagnostics: &mut Vec<Diagnostic>,
+    lint_level: LintLevel,
+    symbol_table: &SymbolTable,
+) {
+    let severity = match lint_level {
+        LintLevel::Allow => return,
+        LintLevel::Hint => DiagnosticSeverity::Hint,
+        LintLevel::Warn => DiagnosticSeverity::Warning,
+        LintLevel::Deny => DiagnosticSeverity::Error,
+    };
+    diagnostics.extend(symbol_table.symbols.iter().filter_map(|symbol| {
+        let ident = symbol.idx.name?.ident(db);
+        let annot = symbol_table.get_name_annot(symbol)?;
+        if annot.name == helpers::ident_to_name(ident) {
+            return None;
+        }
+        Some(Diagnostic {
+            range: annot.range,
+            severity,
+            code: DIAGNOSTIC_CODE.into(),
+            message: format!("name annotation `{}` conflicts with identifier `{ident}`", annot.name),
+            related_information: Some(vec![RelatedInformation {
+                range: match symbol.key.kind() {
+                    SyntaxKind::MODULE_FIELD_IMPORT | SyntaxKind::IMPORT_ITEM => {
+                        helpers::syntax::infer_def_poi(symbol_table.get_type_node_of(symbol))
+                    }
+                    _ => helpers::syntax::infer_def_poi(symbol.amber()),
+                },
+                message: "identifier is defined here".into(),
+            }]),
+            ..Default::default()
+        })
+    }));
+}
@@ -7,7 +7,7 @@ use crate::{
 };
 use lspt::{DiagnosticSeverity, DiagnosticTag};
 
-const DIAGNOSTIC_CODE: &str = "needless-mut";
+pub(super) const DIAGNOSTIC_CODE: &str = "needless-mut";
 
 pub fn check(
     db: &dyn salsa::Database,
@@ -6,7 +6,7 @@ use wat_syntax::{
     ast::{AstNode, Cat},
 };
 
-const DIAGNOSTIC_CODE: &str = "needless-try-table";
+pub(super) const DIAGNOSTIC_CODE: &str = "needless-try-table";
 
 pub fn check(lint_level: LintLevel, node: AmberNode) -> Option<Diagnostic> {
     let severity = match lint_level {
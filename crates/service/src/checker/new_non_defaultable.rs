@@ -5,7 +5,7 @@ use crate::{
 };
 use wat_syntax::{AmberNode, AmberToken, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "new-non-defaultable";
+pub(super) const DIAGNOSTIC_CODE: &str = "new-non-defaultable";
 
 pub fn check(ctx: &DiagnosticCtx, node: AmberNode, instr_name: AmberToken) -> Option<Diagnostic> {
     if !instr_name.text().ends_with(".new_default") {
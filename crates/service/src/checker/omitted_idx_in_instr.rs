@@ -3,7 +3,7 @@ use crate::config::LintLevel;
 use lspt::DiagnosticSeverity;
 use wat_syntax::{AmberNode, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "omitted-idx-in-instr";
+pub(super) const DIAGNOSTIC_CODE: &str = "omitted-idx-in-instr";
 
 pub fn check(lint_level: LintLevel, node: AmberNode) -> Option<Diagnostic> {
     let severity = match lint_level {
@@ -5,7 +5,7 @@ use crate::{
 };
 use wat_syntax::{AmberNode, AmberToken, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "packing";
+pub(super) const DIAGNOSTIC_CODE: &str = "packing";
 
 pub fn check(ctx: &DiagnosticCtx, node: AmberNode, instr_name: AmberToken) -> Option<Diagnostic> {
     match instr_name.text() {
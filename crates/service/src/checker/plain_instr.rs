@@ -3,7 +3,7 @@ use crate::data_set::INSTR_OP_CODES;
 use std::iter::Peekable;
 use wat_syntax::{AmberNode, AmberToken, NodeOrToken, SyntaxKind, SyntaxKindMatch};
 
-const DIAGNOSTIC_CODE: &str = "plain-instr";
+pub(super) const DIAGNOSTIC_CODE: &str = "plain-instr";
 
 const INDEX: [SyntaxKind; 2] = [SyntaxKind::IDENT, SyntaxKind::INT];
 
@@ -16,9 +16,10 @@ pub fn check(diagnostics: &mut Vec<Diagnostic>, node: AmberNode, instr_name: Amb
     let name = instr_name.text();
     match name {
         "call" | "local.get" | "local.set" | "local.tee" | "global.get" | "global.set" | "ref.func" | "data.drop"
-        | "elem.drop" | "br" | "br_if" | "struct.new" | "struct.new_default" | "array.new" | "array.new_default"
-        | "array.get" | "array.get_u" | "array.get_s" | "array.set" | "array.fill" | "br_on_null"
-        | "br_on_non_null" | "call_ref" | "return_call" | "return_call_ref" | "throw" | "cont.new" | "suspend" => {
+        | "elem.drop" | "br" | "br_if" | "rethrow" | "struct.new" | "struct.new_default" | "array.new"
+        | "array.new_default" | "array.get" | "array.get_u" | "array.get_s" | "array.set" | "array.fill"
+        | "br_on_null" | "br_on_non_null" | "call_ref" | "return_call" | "return_call_ref" | "throw" | "cont.new"
+        | "suspend" => {
             check_immediate(
                 diagnostics,
                 &mut immediates,
@@ -0,0 +1,144 @@
+use super::{Diagnostic, DiagnosticCtx, RelatedInformation};
+use crate::{
+    LintLevel,
+    binder::{Symbol, SymbolKind},
+    const_eval::{self, ConstValue},
+    helpers,
+};
+use lspt::DiagnosticSeverity;
+use wat_syntax::{AmberNode, SyntaxKind};
+
+pub(super) const DIAGNOSTIC_CODE: &str = "segment-bounds";
+
+/// Report active data segment that doesn't fit in the initial size of memory,
+/// which will trap when instantiating module.
+pub fn check_data(ctx: &DiagnosticCtx, node: AmberNode) -> Option<Diagnostic> {
+    let severity = match ctx.config.lint.segment_bounds {
+        LintLevel::Allow => return None,
+        LintLevel::Hint => DiagnosticSeverity::Hint,
+        LintLevel::Warn => DiagnosticSeverity::Warning,
+        LintLevel::Deny => DiagnosticSeverity::Error,
+    };
+    let offset_node = node.children_by_kind(SyntaxKind::OFFSET).next()?;
+    let memory = resolve_target(ctx, node, SyntaxKind::MEM_USE, SymbolKind::MemoryDef)?;
+    let mem_type = memory.amber().children_by_kind(SyntaxKind::MEM_TYPE).next()?;
+    let limits = mem_type.children_by_kind(SyntaxKind::LIMITS).next()?;
+    let (min, _) = helpers::parse_limits(limits)?;
+    let size = min as u128 * helpers::mem_page_size(mem_type)? as u128;
+
+    let offset = eval_offset(ctx, offset_node)?;
+    let mut bytes = Vec::new();
+    let mut offsets = Vec::new();
+    node.tokens_by_kind(SyntaxKind::STRING).for_each(|token| {
+        helpers::decode_string(token.text(), token.text_range().start(), &mut bytes, &mut offsets);
+    });
+    let end = offset as u128 + bytes.len() as u128;
+    if end <= size {
+        return None;
+    }
+    Some(Diagnostic {
+        range: offset_node.text_range(),
+        severity,
+        code: DIAGNOSTIC_CODE.into(),
+        message: format!(
+            "data segment ends at byte {end}, which exceeds initial size {size} of memory `{}`",
+            memory.idx.render(ctx.db),
+        ),
+        related_information: Some(vec![RelatedInformation {
+            range: limits.text_range(),
+            message: "initial size of memory is defined here".into(),
+        }]),
+        ..Default::default()
+    })
+}
+
+/// Report active element segment that doesn't fit in the initial size of table,
+/// which will trap when instantiating module.
+pub fn check_elem(ctx: &DiagnosticCtx, node: AmberNode) -> Option<Diagnostic> {
+    let severity = match ctx.config.lint.segment_bounds {
+        LintLevel::Allow => return None,
+        LintLevel::Hint => DiagnosticSeverity::Hint,
+        LintLevel::Warn => DiagnosticSeverity::Warning,
+        LintLevel::Deny => DiagnosticSeverity::Error,
+    };
+    let offset_node = node.children_by_kind(SyntaxKind::OFFSET).next()?;
+    if node
+        .tokens_by_kind(SyntaxKind::MODIFIER_KEYWORD)
+        .any(|token| token.text() == "declare")
+    {
+        return None;
+    }
+    let table = resolve_target(ctx, node, SyntaxKind::TABLE_USE, SymbolKind::TableDef)?;
+    let limits = table
+        .amber()
+        .children_by_kind(SyntaxKind::TABLE_TYPE)
+        .next()?
+        .children_by_kind(SyntaxKind::LIMITS)
+        .next()?;
+    let (size, _) = helpers::parse_limits(limits)?;
+
+    let offset = eval_offset(ctx, offset_node)?;
+    let len = node
+        .children_by_kind(SyntaxKind::ELEM_LIST)
+        .next()
+        .map_or(0, |elem_list| {
+            elem_list
+                .children_by_kind([SyntaxKind::INDEX, SyntaxKind::ELEM_EXPR])
+                .count()
+        });
+    let end = offset as u128 + len as u128;
+    if end <= size as u128 {
+        return None;
+    }
+    Some(Diagnostic {
+        range: offset_node.text_range(),
+        severity,
+        code: DIAGNOSTIC_CODE.into(),
+        message: format!(
+            "element segment ends at index {end}, which exceeds initial size {size} of table `{}`",
+            table.idx.render(ctx.db),
+        ),
+        related_information: Some(vec![RelatedInformation {
+            range: limits.text_range(),
+            message: "initial size of table is defined here".into(),
+        }]),
+        ..Default::default()
+    })
+}
+
+/// Find memory or table that segment is applied to.
+/// Imported ones are skipped since their actual sizes are unknown.
+fn resolve_target<'db>(
+    ctx: &DiagnosticCtx<'db, '_>,
+    node: AmberNode,
+    use_kind: SyntaxKind,
+    kind: SymbolKind,
+) -> Option<&'db Symbol<'db>> {
+    let symbol = if let Some(index) = node
+        .children_by_kind(use_kind)
+        .next()
+        .and_then(|use_node| use_node.children_by_kind(SyntaxKind::INDEX).next())
+    {
+        ctx.symbol_table.find_def(index.into())?
+    } else {
+        ctx.symbol_table.get_declared(ctx.module, kind).next()?
+    };
+    let def = symbol.amber();
+    if matches!(
+        def.kind(),
+        SyntaxKind::MODULE_FIELD_MEMORY | SyntaxKind::MODULE_FIELD_TABLE
+    ) && def.children_by_kind(SyntaxKind::IMPORT).next().is_none()
+    {
+        Some(symbol)
+    } else {
+        None
+    }
+}
+
+fn eval_offset(ctx: &DiagnosticCtx, offset: AmberNode) -> Option<u64> {
+    match const_eval::eval(ctx.db, ctx.document, offset)? {
+        ConstValue::I32(value) => Some(value as u32 as u64),
+        ConstValue::I64(value) => Some(value as u64),
+        _ => None,
+    }
+}
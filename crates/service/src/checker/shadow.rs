@@ -8,7 +8,7 @@ use crate::{
 use bumpalo::{Bump, collections::Vec as BumpVec};
 use lspt::DiagnosticSeverity;
 
-const DIAGNOSTIC_CODE: &str = "shadow";
+pub(super) const DIAGNOSTIC_CODE: &str = "shadow";
 
 pub fn check(
     db: &dyn salsa::Database,
@@ -2,7 +2,7 @@ use super::{Diagnostic, DiagnosticCtx};
 use crate::types_analyzer::Sig;
 use wat_syntax::{AmberNode, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "start";
+pub(super) const DIAGNOSTIC_CODE: &str = "start";
 
 pub fn check(ctx: &DiagnosticCtx, node: AmberNode) -> Option<Diagnostic> {
     let index = node.children_by_kind(SyntaxKind::INDEX).next()?;
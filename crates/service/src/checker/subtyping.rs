@@ -2,7 +2,7 @@ use super::Diagnostic;
 use crate::{binder::SymbolTable, document::Document, types_analyzer::DefTypes};
 use wat_syntax::SyntaxKind;
 
-const DIAGNOSTIC_CODE: &str = "subtyping";
+pub(super) const DIAGNOSTIC_CODE: &str = "subtyping";
 
 pub fn check(
     diagnostics: &mut Vec<Diagnostic>,
@@ -0,0 +1,226 @@
+use super::Diagnostic;
+use crate::config::LintLevel;
+use lspt::{DiagnosticSeverity, DiagnosticTag};
+use wat_syntax::{AmberNode, SyntaxKind, TextRange, TextSize};
+
+pub(super) const DIAGNOSTIC_CODE: &str = "unused-suppression";
+
+const DISABLE_NEXT_LINE: &str = "wat-lint-disable-next-line";
+const DISABLE: &str = "wat-lint-disable";
+const ENABLE: &str = "wat-lint-enable";
+
+struct Directive {
+    /// Range of the comment or annotation itself.
+    range: TextRange,
+    /// Range where diagnostics are suppressed.
+    scope: TextRange,
+    /// Suppressed diagnostic codes with their ranges. Empty means all diagnostics.
+    codes: Vec<(TextRange, String, bool)>,
+    used: bool,
+}
+
+/// Remove diagnostics suppressed by comment directives or `(@allow)` annotations,
+/// then report directives that don't suppress anything.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, lint_level: LintLevel, root: AmberNode, text: &str) {
+    let mut directives = collect_directives(root, text);
+    if directives.is_empty() {
+        return;
+    }
+    diagnostics.retain(|diagnostic| {
+        let mut suppressed = false;
+        directives
+            .iter_mut()
+            .filter(|directive| directive.scope.contains_inclusive(diagnostic.range.start()))
+            .for_each(|directive| {
+                if directive.codes.is_empty() {
+                    directive.used = true;
+                    suppressed = true;
+                } else if let Some((_, _, used)) =
+                    directive.codes.iter_mut().find(|(_, code, _)| *code == diagnostic.code)
+                {
+                    *used = true;
+                    suppressed = true;
+                }
+            });
+        !suppressed
+    });
+
+    let severity = match lint_level {
+        LintLevel::Allow => return,
+        LintLevel::Hint => DiagnosticSeverity::Hint,
+        LintLevel::Warn => DiagnosticSeverity::Warning,
+        LintLevel::Deny => DiagnosticSeverity::Error,
+    };
+    directives.into_iter().for_each(|directive| {
+        if directive.codes.is_empty() {
+            if !directive.used {
+                diagnostics.push(Diagnostic {
+                    range: directive.range,
+                    severity,
+                    code: DIAGNOSTIC_CODE.into(),
+                    message: "this directive doesn't suppress any diagnostics".into(),
+                    tags: Some(vec![DiagnosticTag::Unnecessary]),
+                    ..Default::default()
+                });
+            }
+        } else {
+            diagnostics.extend(
+                directive
+                    .codes
+                    .into_iter()
+                    .filter(|(_, _, used)| !used)
+                    .map(|(range, code, _)| Diagnostic {
+                        range,
+                        severity,
+                        code: DIAGNOSTIC_CODE.into(),
+                        message: format!("there're no `{code}` diagnostics to suppress"),
+                        tags: Some(vec![DiagnosticTag::Unnecessary]),
+                        ..Default::default()
+                    }),
+            );
+        }
+    });
+}
+
+fn collect_directives(root: AmberNode, text: &str) -> Vec<Directive> {
+    let mut directives = Vec::new();
+    // indices of directives from `wat-lint-disable` that haven't been closed by `wat-lint-enable`
+    let mut opened = Vec::new();
+    let mut tokens = root.descendant_tokens();
+    while let Some((token, parent, _)) = tokens.next() {
+        match token.kind() {
+            SyntaxKind::LINE_COMMENT | SyntaxKind::BLOCK_COMMENT => {
+                let content = if token.kind() == SyntaxKind::LINE_COMMENT {
+                    token.text().strip_prefix(";;")
+                } else {
+                    token.text().strip_prefix("(;").and_then(|s| s.strip_suffix(";)"))
+                };
+                let Some(content) = content else {
+                    continue;
+                };
+                let trimmed = content.trim_start();
+                let range = token.text_range();
+                // comment starts with `;;` or `(;` which are two bytes
+                let offset = range.start() + TextSize::new((2 + content.len() - trimmed.len()) as u32);
+                if let Some(rest) = strip_directive(trimmed, DISABLE_NEXT_LINE) {
+                    let start = next_line_start(text, range.end());
+                    directives.push(Directive {
+                        range,
+                        scope: TextRange::new(start, line_end(text, start)),
+                        codes: parse_codes(rest, offset + TextSize::of(DISABLE_NEXT_LINE)),
+                        used: false,
+                    });
+                } else if let Some(rest) = strip_directive(trimmed, DISABLE) {
+                    opened.push(directives.len());
+                    directives.push(Directive {
+                        range,
+                        scope: TextRange::new(range.end(), root.text_range().end()),
+                        codes: parse_codes(rest, offset + TextSize::of(DISABLE)),
+                        used: false,
+                    });
+                } else if let Some(rest) = strip_directive(trimmed, ENABLE) {
+                    let codes = parse_codes(rest, offset + TextSize::of(ENABLE));
+                    opened.retain(|index| {
+                        let Some(directive) = directives.get_mut(*index) else {
+                            return false;
+                        };
+                        if codes.is_empty()
+                            || directive
+                                .codes
+                                .iter()
+                                .any(|(_, code, _)| codes.iter().any(|(_, enabled, _)| enabled == code))
+                        {
+                            directive.scope = TextRange::new(directive.scope.start(), range.start());
+                            false
+                        } else {
+                            true
+                        }
+                    });
+                }
+            }
+            SyntaxKind::ANNOT_START if is_allow_annotation(token.text()) => {
+                let start = token.text_range().start();
+                let mut end = token.text_range().end();
+                let mut codes = Vec::new();
+                let mut depth = 0;
+                for (token, ..) in tokens.by_ref() {
+                    end = token.text_range().end();
+                    match token.kind() {
+                        SyntaxKind::ANNOT_END => break,
+                        SyntaxKind::ANNOT_ELEM if token.text() == "(" => depth += 1,
+                        SyntaxKind::ANNOT_ELEM if token.text() == ")" => depth -= 1,
+                        SyntaxKind::ANNOT_ELEM if depth == 0 => {
+                            let code = token.text();
+                            let code = code
+                                .strip_prefix('"')
+                                .and_then(|code| code.strip_suffix('"'))
+                                .unwrap_or(code);
+                            codes.push((token.text_range(), code.to_string(), false));
+                        }
+                        _ => {}
+                    }
+                }
+                let range = TextRange::new(start, end);
+                // annotation between module fields applies to the next field, otherwise it applies to its parent
+                let scope = if matches!(parent.kind(), SyntaxKind::MODULE | SyntaxKind::ROOT) {
+                    parent
+                        .children()
+                        .find(|node| node.text_range().start() >= range.end())
+                        .map(|node| node.text_range())
+                } else {
+                    Some(parent.text_range())
+                };
+                if let Some(scope) = scope {
+                    directives.push(Directive {
+                        range,
+                        scope,
+                        codes,
+                        used: false,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    directives
+}
+
+fn is_allow_annotation(text: &str) -> bool {
+    text.strip_prefix("(@")
+        .map(|s| s.strip_prefix('"').and_then(|s| s.strip_suffix('"')).unwrap_or(s))
+        .is_some_and(|name| name == "allow")
+}
+
+/// Strip directive name, and the rest must be separated by whitespace.
+fn strip_directive<'s>(text: &'s str, directive: &str) -> Option<&'s str> {
+    text.strip_prefix(directive)
+        .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+/// Parse codes separated by commas or whitespace, ignoring description after `--`.
+fn parse_codes(text: &str, start: TextSize) -> Vec<(TextRange, String, bool)> {
+    let text = text.split_once("--").map_or(text, |(codes, _)| codes);
+    let mut codes = Vec::new();
+    let mut offset = 0;
+    text.split(|c: char| c == ',' || c.is_ascii_whitespace())
+        .for_each(|code| {
+            if !code.is_empty() {
+                let code_start = start + TextSize::new(offset as u32);
+                codes.push((TextRange::at(code_start, TextSize::of(code)), code.to_string(), false));
+            }
+            offset += code.len() + 1;
+        });
+    codes
+}
+
+fn next_line_start(text: &str, offset: TextSize) -> TextSize {
+    text.get(usize::from(offset)..)
+        .and_then(|rest| rest.find('\n'))
+        .map_or(TextSize::of(text), |index| offset + TextSize::new(index as u32 + 1))
+}
+
+fn line_end(text: &str, offset: TextSize) -> TextSize {
+    text.get(usize::from(offset)..)
+        .and_then(|rest| rest.find('\n'))
+        .map_or(TextSize::of(text), |index| offset + TextSize::new(index as u32))
+}
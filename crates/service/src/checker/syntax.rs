@@ -1,7 +1,7 @@
 use super::Diagnostic;
 use crate::document::Document;
 
-const DIAGNOSTIC_CODE: &str = "syntax";
+pub(super) const DIAGNOSTIC_CODE: &str = "syntax";
 
 pub fn check(db: &dyn salsa::Database, diagnostics: &mut Vec<Diagnostic>, document: Document) {
     diagnostics.extend(document.syntax_errors(db).iter().map(|error| Diagnostic {
@@ -6,7 +6,7 @@ use crate::{
 use std::num::IntErrorKind;
 use wat_syntax::{AmberNode, AmberToken, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "table-type";
+pub(super) const DIAGNOSTIC_CODE: &str = "table-type";
 
 pub fn check(diagnostics: &mut Vec<Diagnostic>, node: AmberNode) -> Option<()> {
     let addr_type = types_analyzer::extract_addr_type(node.green());
@@ -2,7 +2,7 @@ use super::{Diagnostic, DiagnosticCtx};
 use crate::types_analyzer::CompositeType;
 use wat_syntax::{AmberNode, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "tag-type";
+pub(super) const DIAGNOSTIC_CODE: &str = "tag-type";
 
 pub fn check(diagnostics: &mut Vec<Diagnostic>, ctx: &DiagnosticCtx, node: AmberNode) {
     let Some(type_use) = node.children_by_kind(SyntaxKind::TYPE_USE).next() else {
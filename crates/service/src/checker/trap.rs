@@ -0,0 +1,164 @@
+use super::{Diagnostic, DiagnosticCtx, RelatedInformation, mem_arg};
+use crate::{
+    LintLevel,
+    const_eval::ConstValue,
+    const_fold, helpers,
+    types_analyzer::{InstrSigResolverCtx, ValType, extract_addr_type},
+};
+use lspt::DiagnosticSeverity;
+use std::slice;
+use wat_syntax::{AmberNode, SyntaxKind};
+
+pub(super) const DIAGNOSTIC_CODE: &str = "trap";
+
+/// Report instructions in the given block that will always trap with constant operands.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, ctx: &mut DiagnosticCtx, node: AmberNode) {
+    let severity = match ctx.config.lint.trap {
+        LintLevel::Allow => return,
+        LintLevel::Hint => DiagnosticSeverity::Hint,
+        LintLevel::Warn => DiagnosticSeverity::Warning,
+        LintLevel::Deny => DiagnosticSeverity::Error,
+    };
+    let Some(outer_block) = node.to_ptr().to_node(ctx.module) else {
+        return;
+    };
+    const_fold::propagate(
+        &outer_block,
+        &InstrSigResolverCtx {
+            db: ctx.db,
+            document: ctx.document,
+            symbol_table: ctx.symbol_table,
+            def_types: ctx.def_types,
+            module: ctx.module,
+            module_id: ctx.module_id,
+            bump: ctx.bump,
+        },
+        |instr, instr_name, operands| {
+            let Some(instr_name_token) = instr.tokens_by_kind(SyntaxKind::INSTR_NAME).next() else {
+                return;
+            };
+            let (reason, related_information) = if let Some(reason) = check_numeric(instr_name, operands) {
+                (reason, None)
+            } else if let Some(reason) = check_null(instr_name, operands) {
+                (reason, None)
+            } else if let Some((reason, related)) = check_memory(ctx, instr, instr_name, operands) {
+                (reason, Some(vec![related]))
+            } else {
+                return;
+            };
+            diagnostics.push(Diagnostic {
+                range: instr_name_token.text_range(),
+                severity,
+                code: DIAGNOSTIC_CODE.into(),
+                message: format!("`{instr_name}` will always trap: {reason}"),
+                related_information,
+                ..Default::default()
+            });
+        },
+    );
+    ctx.bump.reset();
+}
+
+fn check_numeric(instr_name: &str, operands: &[Option<ConstValue>]) -> Option<String> {
+    let (ty, op) = instr_name.split_once('.')?;
+    match (op, operands) {
+        ("div_s" | "div_u" | "rem_s" | "rem_u", [lhs, Some(rhs)]) => match (ty, lhs, rhs) {
+            ("i32", _, ConstValue::I32(0)) | ("i64", _, ConstValue::I64(0)) => Some("integer divide by zero".into()),
+            ("i32", Some(ConstValue::I32(i32::MIN)), ConstValue::I32(-1)) if op == "div_s" => Some(format!(
+                "integer overflow, because `{} / -1` can't be represented",
+                i32::MIN
+            )),
+            ("i64", Some(ConstValue::I64(i64::MIN)), ConstValue::I64(-1)) if op == "div_s" => Some(format!(
+                "integer overflow, because `{} / -1` can't be represented",
+                i64::MIN
+            )),
+            _ => None,
+        },
+        (_, [Some(operand)]) => {
+            let (value, operand_ty) = match operand {
+                ConstValue::F32(bits) => (f32::from_bits(*bits) as f64, "f32"),
+                ConstValue::F64(bits) => (f64::from_bits(*bits), "f64"),
+                _ => return None,
+            };
+            if !op.strip_prefix("trunc_")?.starts_with(operand_ty) {
+                return None;
+            }
+            if value.is_nan() {
+                Some("invalid conversion to integer, because operand is NaN".into())
+            } else if const_fold::eval_op(instr_name, slice::from_ref(operand)).is_none() {
+                Some(format!("integer overflow, because `{value}` is out of range of `{ty}`"))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+fn check_null(instr_name: &str, operands: &[Option<ConstValue>]) -> Option<String> {
+    let operand = match instr_name {
+        "ref.as_non_null" | "struct.get" | "struct.get_s" | "struct.get_u" | "struct.set" | "array.get"
+        | "array.get_s" | "array.get_u" | "array.set" | "array.len" | "array.fill" | "i31.get_s" | "i31.get_u" => {
+            operands.first()
+        }
+        "call_ref" | "return_call_ref" => operands.last(),
+        _ => return None,
+    };
+    if let Some(Some(ConstValue::RefNull(..))) = operand {
+        Some("null reference, because operand is `ref.null`".into())
+    } else {
+        None
+    }
+}
+
+fn check_memory(
+    ctx: &DiagnosticCtx,
+    instr: AmberNode,
+    instr_name: &str,
+    operands: &[Option<ConstValue>],
+) -> Option<(String, RelatedInformation)> {
+    let size = mem_arg::access_size(instr_name)?;
+    let offset = mem_arg::get_offset(instr)?;
+    let memory = mem_arg::resolve_memory(ctx, instr)?;
+    let type_node = ctx.symbol_table.get_type_node_of(memory);
+    let mem_type = if type_node.kind() == SyntaxKind::MEM_TYPE {
+        type_node
+    } else {
+        type_node.children_by_kind(SyntaxKind::MEM_TYPE).next()?
+    };
+    let addr_type = extract_addr_type(mem_type.green());
+    // mismatched address type and invalid offset are reported by other checkers
+    let addr = match (&addr_type, operands.first()?) {
+        (ValType::I32, Some(ConstValue::I32(addr))) if offset <= u32::MAX as u64 => *addr as u32 as u128,
+        (ValType::I64, Some(ConstValue::I64(addr))) => *addr as u64 as u128,
+        _ => return None,
+    };
+    let limits = mem_type.children_by_kind(SyntaxKind::LIMITS).next()?;
+    let start = addr + offset as u128;
+    let page_size = helpers::mem_page_size(mem_type)?;
+    let (max, message) = if let (_, Some(max)) = helpers::parse_limits(limits)? {
+        (
+            max as u128 * page_size as u128,
+            "maximum size of memory is defined here",
+        )
+    } else if addr_type == ValType::I32 {
+        // 32-bit memory can't grow beyond 4 GiB
+        (1 << 32, "memory is defined here with `i32` address type")
+    } else {
+        return None;
+    };
+    if start + size as u128 <= max {
+        return None;
+    }
+    Some((
+        format!(
+            "out of bounds memory access, because accessing {size} byte{} at address {start} exceeds maximum size {max} of memory `{}`",
+            if size == 1 { "" } else { "s" },
+            memory.idx.render(ctx.db),
+        ),
+        RelatedInformation {
+            range: limits.text_range(),
+            message: message.into(),
+        },
+    ))
+}
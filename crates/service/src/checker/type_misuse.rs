@@ -10,7 +10,7 @@ use crate::{
 };
 use wat_syntax::{AmberNode, AmberToken, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "type-misuse";
+pub(super) const DIAGNOSTIC_CODE: &str = "type-misuse";
 
 pub fn check(
     diagnostics: &mut Vec<Diagnostic>,
@@ -1,20 +1,23 @@
 use super::{Diagnostic, DiagnosticCtx, RelatedInformation};
 use crate::{
+    binder::{SymbolKey, SymbolKind, SymbolTable},
+    document::Document,
     helpers,
+    imex::{self, LinkedModules},
     types_analyzer::{
-        InstrSigResolverCtx, OperandType, RefType, Sig, ValType, extract_global_type, extract_table_ref_type,
-        join_types, resolve_instr_sig,
+        HeapType, InstrSigResolverCtx, NamedSig, OperandType, RefType, Sig, ValType, extract_addr_type,
+        extract_global_type, extract_table_ref_type, join_types, resolve_instr_sig,
     },
 };
 use bumpalo::collections::Vec as BumpVec;
 use itertools::{EitherOrBoth, Itertools};
-use std::iter;
+use std::{fmt, iter};
 use wat_syntax::{
     AmberNode, SyntaxKind, TextRange,
     ast::{AstNode, BlockInstr, Instr},
 };
 
-const DIAGNOSTIC_CODE: &str = "type-check";
+pub(super) const DIAGNOSTIC_CODE: &str = "type-check";
 
 pub fn check_func(diagnostics: &mut Vec<Diagnostic>, ctx: &mut DiagnosticCtx, node: AmberNode) {
     {
@@ -100,14 +103,31 @@ pub fn check_table(diagnostics: &mut Vec<Diagnostic>, ctx: &mut DiagnosticCtx, n
     ctx.bump.reset();
 }
 
-pub fn check_offset(diagnostics: &mut Vec<Diagnostic>, ctx: &mut DiagnosticCtx, node: AmberNode) {
+pub fn check_offset(diagnostics: &mut Vec<Diagnostic>, ctx: &mut DiagnosticCtx, node: AmberNode, field: AmberNode) {
+    // offset type depends on address type of memory or table that segment is applied to
+    let (use_kind, kind) = if field.kind() == SyntaxKind::MODULE_FIELD_DATA {
+        (SyntaxKind::MEM_USE, SymbolKind::MemoryDef)
+    } else {
+        (SyntaxKind::TABLE_USE, SymbolKind::TableDef)
+    };
+    let addr_type = field
+        .children_by_kind(use_kind)
+        .next()
+        .and_then(|use_node| use_node.children_by_kind(SyntaxKind::INDEX).next())
+        .map_or_else(
+            || ctx.symbol_table.get_declared(ctx.module, kind).next(),
+            |index| ctx.symbol_table.find_def(index.into()),
+        )
+        .map_or(ValType::I32, |symbol| {
+            extract_addr_type(ctx.symbol_table.get_type_node_of(symbol).green())
+        });
     check_block_like(
         diagnostics,
         ctx,
         node,
         BumpVec::with_capacity_in(1, ctx.bump),
         BumpVec::with_capacity_in(1, ctx.bump),
-        &[OperandType::Val(ValType::I32)],
+        &[OperandType::Val(addr_type)],
     );
     ctx.bump.reset();
 }
@@ -134,6 +154,23 @@ pub fn check_elem_list(diagnostics: &mut Vec<Diagnostic>, ctx: &mut DiagnosticCt
     ctx.bump.reset();
 }
 
+pub fn check_invoke(
+    diagnostics: &mut Vec<Diagnostic>,
+    ctx: &mut DiagnosticCtx,
+    node: AmberNode,
+    params: &[OperandType],
+) {
+    check_block_like(
+        diagnostics,
+        ctx,
+        node,
+        BumpVec::with_capacity_in(params.len(), ctx.bump),
+        BumpVec::with_capacity_in(params.len(), ctx.bump),
+        params,
+    );
+    ctx.bump.reset();
+}
+
 fn check_block_like(
     diagnostics: &mut Vec<Diagnostic>,
     ctx: &DiagnosticCtx,
@@ -222,6 +259,51 @@ fn check_instr<'db, 'bump>(
                 }
                 check_block_like(diagnostics, ctx, node, init_stack, init_producers, &results);
             }
+            SyntaxKind::BLOCK_TRY => {
+                if let Some(diagnostic) = type_stack.check(
+                    &BumpVec::from_iter_in(signature.params.into_iter().map(OperandType::Val), ctx.bump),
+                    ReportRange::Instr(node),
+                ) {
+                    diagnostics.push(diagnostic);
+                }
+                node.children().for_each(|child| match child.kind() {
+                    SyntaxKind::BLOCK_TRY_DO => check_block_like(
+                        diagnostics,
+                        ctx,
+                        child,
+                        init_stack.clone(),
+                        BumpVec::from_iter_in(init_producers.iter().cloned(), ctx.bump),
+                        &results,
+                    ),
+                    SyntaxKind::BLOCK_TRY_CATCH => {
+                        let params = child
+                            .children_by_kind(SyntaxKind::INDEX)
+                            .next()
+                            .and_then(|index| ctx.symbol_table.find_def(index.into()))
+                            .map(|tag| {
+                                Sig::from_func(ctx.db, ctx.document, ctx.symbol_table.get_type_node_of(tag)).params
+                            })
+                            .unwrap_or_default();
+                        check_block_like(
+                            diagnostics,
+                            ctx,
+                            child,
+                            BumpVec::from_iter_in(params.iter().map(|ty| OperandType::Val(ty.clone())), ctx.bump),
+                            BumpVec::from_iter_in(iter::repeat_n(Some(child), params.len()), ctx.bump),
+                            &results,
+                        );
+                    }
+                    SyntaxKind::BLOCK_TRY_CATCH_ALL => check_block_like(
+                        diagnostics,
+                        ctx,
+                        child,
+                        BumpVec::new_in(ctx.bump),
+                        BumpVec::new_in(ctx.bump),
+                        &results,
+                    ),
+                    _ => {}
+                });
+            }
             SyntaxKind::BLOCK_IF => {
                 if let Some(mut diagnostic) =
                     type_stack.check(&[OperandType::Val(ValType::I32)], ReportRange::Keyword(node))
@@ -452,3 +534,210 @@ impl ReportRange<'_> {
         }
     }
 }
+
+/// Check types of imports against the exported definitions in linked modules.
+///
+/// Types that refer to type definitions can't be compared across documents, so they're skipped.
+pub fn check_linked_imports(
+    diagnostics: &mut Vec<Diagnostic>,
+    db: &dyn salsa::Database,
+    document: Document,
+    symbol_table: &SymbolTable,
+    imports: &[SymbolKey],
+    linked_modules: &LinkedModules,
+) {
+    if linked_modules.is_empty() {
+        return;
+    }
+    let root = AmberNode::new_root(document.root(db));
+    diagnostics.extend(imports.iter().filter_map(|key| {
+        let (module_name, name) = imex::get_import_names(root, *key)?;
+        let (linked, def_key) = imex::resolve_linked_import(db, module_name, name, linked_modules)?;
+        let import = symbol_table.symbols.get(key)?;
+        let exporter_symbol_table = SymbolTable::of(db, linked.document);
+        let export = exporter_symbol_table.symbols.get(def_key?)?;
+        let message = if import.kind != export.kind {
+            format!(
+                "import {} {} expects {}, but found {}",
+                module_name.green(),
+                name.green(),
+                import.kind,
+                export.kind,
+            )
+        } else {
+            let module_id = symbol_table.symbols.get(import.region)?.idx.num?;
+            let expected = symbol_table.get_type_node_of(import);
+            let actual = exporter_symbol_table.get_type_node_of(export);
+            let (expected, actual) = match import.kind {
+                SymbolKind::Func | SymbolKind::TagDef => {
+                    let expected = NamedSig::from_func(db, document, expected);
+                    let actual = NamedSig::from_func(db, linked.document, actual);
+                    let comparable = expected
+                        .params
+                        .iter()
+                        .map(|(ty, _)| ty)
+                        .chain(&expected.results)
+                        .chain(actual.params.iter().map(|(ty, _)| ty))
+                        .chain(&actual.results)
+                        .all(is_comparable);
+                    if !comparable
+                        || import.kind == SymbolKind::Func && actual.matches(&expected, db, document, module_id)
+                        || import.kind == SymbolKind::TagDef && actual.type_equals(&expected, db, document, module_id)
+                    {
+                        return None;
+                    }
+                    (
+                        expected.render_compact(db).to_string(),
+                        actual.render_compact(db).to_string(),
+                    )
+                }
+                SymbolKind::GlobalDef => {
+                    let expected_ty = extract_global_type(db, expected.green()).filter(is_comparable)?;
+                    let actual_ty = extract_global_type(db, actual.green()).filter(is_comparable)?;
+                    let expected_mut = is_mutable_global(expected);
+                    let actual_mut = is_mutable_global(actual);
+                    if expected_mut == actual_mut
+                        && if expected_mut {
+                            actual_ty.type_equals(&expected_ty, db, document, module_id)
+                        } else {
+                            actual_ty.matches(&expected_ty, db, document, module_id)
+                        }
+                    {
+                        return None;
+                    }
+                    (
+                        render_global_type(db, &expected_ty, expected_mut),
+                        render_global_type(db, &actual_ty, actual_mut),
+                    )
+                }
+                SymbolKind::MemoryDef => {
+                    let expected = Limits::extract(expected)?;
+                    let actual = Limits::extract(actual)?;
+                    if actual.matches(&expected) {
+                        return None;
+                    }
+                    (expected.to_string(), actual.to_string())
+                }
+                SymbolKind::TableDef => {
+                    let expected_ref_type =
+                        extract_table_ref_type(db, expected.green()).filter(|ty| is_comparable_heap(&ty.heap_ty))?;
+                    let actual_ref_type =
+                        extract_table_ref_type(db, actual.green()).filter(|ty| is_comparable_heap(&ty.heap_ty))?;
+                    let expected = Limits::extract(expected)?;
+                    let actual = Limits::extract(actual)?;
+                    if actual.matches(&expected)
+                        && actual_ref_type.type_equals(&expected_ref_type, db, document, module_id)
+                    {
+                        return None;
+                    }
+                    (
+                        format!("{expected} {}", expected_ref_type.render(db)),
+                        format!("{actual} {}", actual_ref_type.render(db)),
+                    )
+                }
+                _ => return None,
+            };
+            format!(
+                "type of import {} {} doesn't match its export: expected `{expected}`, found `{actual}`",
+                module_name.green(),
+                name.green(),
+            )
+        };
+        Some(Diagnostic {
+            range: name.text_range(),
+            code: DIAGNOSTIC_CODE.into(),
+            message,
+            ..Default::default()
+        })
+    }));
+}
+
+fn is_comparable(ty: &ValType) -> bool {
+    match ty {
+        ValType::Ref(RefType { heap_ty, .. }) => is_comparable_heap(heap_ty),
+        _ => true,
+    }
+}
+fn is_comparable_heap(heap_ty: &HeapType) -> bool {
+    !matches!(heap_ty, HeapType::Type(..) | HeapType::DefFunc(..) | HeapType::Rec(..))
+}
+
+fn is_mutable_global(node: AmberNode) -> bool {
+    node.children_by_kind(SyntaxKind::GLOBAL_TYPE)
+        .next()
+        .is_some_and(|global_type| {
+            global_type
+                .tokens_by_kind(SyntaxKind::KEYWORD)
+                .any(|token| token.text() == "mut")
+        })
+}
+
+fn render_global_type(db: &dyn salsa::Database, ty: &ValType, mutable: bool) -> String {
+    if mutable {
+        format!("(mut {})", ty.render(db))
+    } else {
+        ty.render(db).to_string()
+    }
+}
+
+/// Limits of memory or table type.
+struct Limits {
+    is_64: bool,
+    min: u64,
+    max: Option<u64>,
+    shared: bool,
+    page_size: Option<u64>,
+}
+impl Limits {
+    fn extract(node: AmberNode) -> Option<Self> {
+        let ty = node
+            .children_by_kind([SyntaxKind::MEM_TYPE, SyntaxKind::TABLE_TYPE])
+            .next()?;
+        let mut numbers = ty
+            .children_by_kind(SyntaxKind::LIMITS)
+            .next()?
+            .tokens_by_kind(SyntaxKind::UNSIGNED_INT)
+            .map(|token| helpers::parse_u64(token.text()).ok());
+        Some(Self {
+            is_64: extract_addr_type(ty.green()) == ValType::I64,
+            min: numbers.next()??,
+            max: numbers.next().flatten(),
+            shared: ty
+                .tokens_by_kind(SyntaxKind::KEYWORD)
+                .any(|token| token.text() == "shared"),
+            page_size: ty
+                .children_by_kind(SyntaxKind::MEM_PAGE_SIZE)
+                .next()
+                .and_then(|page_size| page_size.tokens_by_kind(SyntaxKind::UNSIGNED_INT).next())
+                .and_then(|token| helpers::parse_u64(token.text()).ok()),
+        })
+    }
+
+    fn matches(&self, expected: &Self) -> bool {
+        self.is_64 == expected.is_64
+            && self.shared == expected.shared
+            && self.page_size.unwrap_or(65536) == expected.page_size.unwrap_or(65536)
+            && self.min >= expected.min
+            && expected
+                .max
+                .is_none_or(|max| self.max.is_some_and(|actual| actual <= max))
+    }
+}
+impl fmt::Display for Limits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_64 {
+            write!(f, "i64 ")?;
+        }
+        write!(f, "{}", self.min)?;
+        if let Some(max) = self.max {
+            write!(f, " {max}")?;
+        }
+        if self.shared {
+            write!(f, " shared")?;
+        }
+        if let Some(page_size) = self.page_size {
+            write!(f, " (pagesize {page_size})")?;
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,12 @@
 use super::Diagnostic;
-use crate::binder::{SymbolKind, SymbolTable};
+use crate::{
+    binder::{SymbolKey, SymbolKind, SymbolTable},
+    document::Document,
+    imex::{self, LinkedModules},
+};
+use wat_syntax::AmberNode;
 
-const DIAGNOSTIC_CODE: &str = "undef";
+pub(super) const DIAGNOSTIC_CODE: &str = "undef";
 
 pub fn check(db: &dyn salsa::Database, diagnostics: &mut Vec<Diagnostic>, symbol_table: &SymbolTable) {
     diagnostics.extend(
@@ -43,3 +48,29 @@ pub fn check(db: &dyn salsa::Database, diagnostics: &mut Vec<Diagnostic>, symbol
             }),
     );
 }
+
+/// Report imports whose modules are linked to workspace files but don't export the items.
+pub fn check_linked_imports(
+    db: &dyn salsa::Database,
+    diagnostics: &mut Vec<Diagnostic>,
+    document: Document,
+    imports: &[SymbolKey],
+    linked_modules: &LinkedModules,
+) {
+    if linked_modules.is_empty() {
+        return;
+    }
+    let root = AmberNode::new_root(document.root(db));
+    diagnostics.extend(imports.iter().filter_map(|key| {
+        let (module_name, name) = imex::get_import_names(root, *key)?;
+        match imex::resolve_linked_import(db, module_name, name, linked_modules)? {
+            (_, Some(_)) => None,
+            (_, None) => Some(Diagnostic {
+                range: name.text_range(),
+                code: DIAGNOSTIC_CODE.into(),
+                message: format!("module {} doesn't export {}", module_name.green(), name.green()),
+                ..Default::default()
+            }),
+        }
+    }));
+}
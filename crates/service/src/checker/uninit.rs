@@ -8,7 +8,7 @@ use crate::{
 use bumpalo::{Bump, collections::Vec as BumpVec};
 use wat_syntax::{AmberNode, SyntaxKind};
 
-const DIAGNOSTIC_CODE: &str = "uninit";
+pub(super) const DIAGNOSTIC_CODE: &str = "uninit";
 
 pub fn check(
     diagnostics: &mut Vec<Diagnostic>,
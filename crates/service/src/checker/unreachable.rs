@@ -10,7 +10,7 @@ use wat_syntax::{
     ast::{AstNode, Instr, support},
 };
 
-const DIAGNOSTIC_CODE: &str = "unreachable";
+pub(super) const DIAGNOSTIC_CODE: &str = "unreachable";
 
 pub fn check(diagnostics: &mut Vec<Diagnostic>, ctx: &mut DiagnosticCtx, node: AmberNode) {
     let severity = match ctx.config.lint.unreachable {
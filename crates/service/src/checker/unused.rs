@@ -8,7 +8,7 @@ use bumpalo::Bump;
 use lspt::{DiagnosticSeverity, DiagnosticTag};
 use wat_syntax::{SyntaxKind, TextRange};
 
-const DIAGNOSTIC_CODE: &str = "unused";
+pub(super) const DIAGNOSTIC_CODE: &str = "unused";
 
 pub fn check(
     db: &dyn salsa::Database,
@@ -7,7 +7,7 @@ use wat_syntax::{
     ast::{AstNode, Cat},
 };
 
-const DIAGNOSTIC_CODE: &str = "useless-catch";
+pub(super) const DIAGNOSTIC_CODE: &str = "useless-catch";
 
 pub fn check(diagnostics: &mut Vec<Diagnostic>, ctx: &DiagnosticCtx, node: AmberNode) {
     let severity = match ctx.config.lint.useless_catch {
@@ -0,0 +1,197 @@
+use crate::{
+    annotation::{self, Item, Malformed},
+    binder::SymbolKey,
+    document::Document,
+    helpers,
+};
+use wat_syntax::{AmberNode, AmberToken, SyntaxKind, TextRange};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Code metadata annotation from the compilation hints and branch hinting proposals.
+pub(crate) struct CodeMetadata {
+    /// Range of the whole annotation.
+    pub range: TextRange,
+    pub kind: MetadataKind,
+    pub payload: Result<Payload, Malformed>,
+    /// Node that directly contains the annotation.
+    pub parent: SymbolKey,
+    /// Node that the annotation is placed directly before.
+    pub target: Option<SymbolKey>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MetadataKind {
+    BranchHint,
+    InstrFreq,
+    CallTargets,
+    CompilationPriority,
+}
+
+impl MetadataKind {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "metadata.code.branch_hint" => Some(MetadataKind::BranchHint),
+            "metadata.code.instr_freq" => Some(MetadataKind::InstrFreq),
+            "metadata.code.call_targets" => Some(MetadataKind::CallTargets),
+            "metadata.code.compilation_priority" => Some(MetadataKind::CompilationPriority),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Payload {
+    /// Whether the branch is likely taken.
+    BranchHint(bool),
+    InstrFreq(u32),
+    CallTargets(Vec<CallTarget>),
+    CompilationPriority {
+        compilation: Option<u32>,
+        optimization: Option<u32>,
+        run_once: bool,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CallTarget {
+    /// Function index or identifier as written.
+    pub idx: String,
+    pub range: TextRange,
+    pub freq: u32,
+}
+
+#[salsa::tracked(returns(ref))]
+/// Collect and parse all code metadata annotations in the document.
+pub(crate) fn get_code_metadata(db: &dyn salsa::Database, document: Document) -> Vec<CodeMetadata> {
+    let root = AmberNode::new_root(document.root(db));
+    let mut metadata = Vec::<CodeMetadata>::new();
+    // indices of annotations that are waiting for the next non-trivia token
+    let mut pending = Vec::<usize>::new();
+    let mut tokens = root.descendant_tokens();
+    while let Some((token, parent, _)) = tokens.next() {
+        if token.kind() != SyntaxKind::ANNOT_START {
+            if !token.kind().is_trivia() {
+                let target =
+                    (parent.text_range().start() == token.text_range().start()).then(|| SymbolKey::from(parent));
+                pending.drain(..).for_each(|index| {
+                    if let Some(metadata) = metadata.get_mut(index) {
+                        metadata.target = target;
+                    }
+                });
+            }
+            continue;
+        }
+        let Some(kind) = token
+            .text()
+            .strip_prefix("(@")
+            .map(|s| s.strip_prefix('"').and_then(|s| s.strip_suffix('"')).unwrap_or(s))
+            .and_then(MetadataKind::from_name)
+        else {
+            continue;
+        };
+        let start = token.text_range().start();
+        let (items, end) =
+            annotation::collect_items(tokens.by_ref().map(|(token, ..)| token), token.text_range().end());
+        let range = TextRange::new(start, end);
+        pending.push(metadata.len());
+        metadata.push(CodeMetadata {
+            range,
+            kind,
+            payload: parse_payload(kind, &items, range),
+            parent: SymbolKey::from(parent),
+            target: None,
+        });
+    }
+    metadata
+}
+
+fn parse_payload(kind: MetadataKind, items: &[Item], range: TextRange) -> Result<Payload, Malformed> {
+    // report at the first item if exists, otherwise at the whole annotation
+    let first = items.first().map_or(range, Item::range);
+    match kind {
+        MetadataKind::BranchHint => match items {
+            [Item::Atom(token)] if token.text().starts_with('"') => {
+                let mut bytes = Vec::with_capacity(1);
+                helpers::decode_string(token.text(), token.text_range().start(), &mut bytes, &mut vec![]);
+                match &*bytes {
+                    [0] => Ok(Payload::BranchHint(false)),
+                    [1] => Ok(Payload::BranchHint(true)),
+                    _ => Err(malformed(token.text_range(), r#"branch hint must be "\00" or "\01""#)),
+                }
+            }
+            _ => Err(malformed(first, r#"expected "\00" or "\01" as branch hint"#)),
+        },
+        MetadataKind::InstrFreq => match items {
+            [Item::List(list, _)] => match &**list {
+                [Item::Atom(keyword), Item::Atom(freq)] if keyword.text() == "freq" => {
+                    parse_u32(freq).map(Payload::InstrFreq)
+                }
+                _ => Err(malformed(first, "expected `(freq <u32>)`")),
+            },
+            _ => Err(malformed(first, "expected `(freq <u32>)`")),
+        },
+        MetadataKind::CallTargets => {
+            if items.is_empty() {
+                return Err(malformed(range, "expected at least one `(target <funcidx> <u32>)`"));
+            }
+            items
+                .iter()
+                .map(|item| match item {
+                    Item::List(list, _) => match &**list {
+                        [Item::Atom(keyword), Item::Atom(idx), Item::Atom(freq)] if keyword.text() == "target" => {
+                            Ok(CallTarget {
+                                idx: idx.text().to_string(),
+                                range: idx.text_range(),
+                                freq: parse_u32(freq)?,
+                            })
+                        }
+                        _ => Err(malformed(item.range(), "expected `(target <funcidx> <u32>)`")),
+                    },
+                    Item::Atom(..) => Err(malformed(item.range(), "expected `(target <funcidx> <u32>)`")),
+                })
+                .collect::<Result<_, _>>()
+                .map(Payload::CallTargets)
+        }
+        MetadataKind::CompilationPriority => {
+            let mut compilation = None;
+            let mut optimization = None;
+            let mut run_once = false;
+            for item in items {
+                match item {
+                    Item::List(list, _) => match &**list {
+                        [Item::Atom(keyword), Item::Atom(value)] if keyword.text() == "compilation" => {
+                            compilation = Some(parse_u32(value)?);
+                        }
+                        [Item::Atom(keyword), Item::Atom(value)] if keyword.text() == "optimization" => {
+                            optimization = Some(parse_u32(value)?);
+                        }
+                        [Item::Atom(keyword)] if keyword.text() == "run_once" => run_once = true,
+                        _ => return Err(malformed(item.range(), COMPILATION_PRIORITY_EXPECTED)),
+                    },
+                    Item::Atom(..) => return Err(malformed(item.range(), COMPILATION_PRIORITY_EXPECTED)),
+                }
+            }
+            Ok(Payload::CompilationPriority {
+                compilation,
+                optimization,
+                run_once,
+            })
+        }
+    }
+}
+
+const COMPILATION_PRIORITY_EXPECTED: &str = "expected `(compilation <u32>)`, `(optimization <u32>)` or `(run_once)`";
+
+fn parse_u32(token: &AmberToken) -> Result<u32, Malformed> {
+    helpers::parse_u32(token.text()).map_err(|_| Malformed {
+        range: token.text_range(),
+        message: format!("expected unsigned integer, found `{}`", token.text()),
+    })
+}
+
+fn malformed(range: TextRange, message: &str) -> Malformed {
+    Malformed {
+        range,
+        message: message.into(),
+    }
+}
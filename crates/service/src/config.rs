@@ -1,6 +1,6 @@
 use crate::LanguageService;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[serde(default)]
@@ -13,6 +13,12 @@ pub struct ServiceConfig {
     #[serde(alias = "inlayHint")]
     /// Configuration about inlay hints.
     pub inlay_hint: InlayHintOptions,
+    /// Mapping from module names of imports to files that provide these modules.
+    ///
+    /// Paths are relative to the importing document, unless they're absolute URIs.
+    pub imports: BTreeMap<String, String>,
+    /// WebAssembly features supported by target engines.
+    pub features: Features,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -57,6 +63,29 @@ pub struct Lints {
     #[serde(alias = "omittedIdxInInstr")]
     /// Lint for omitted idx in some memory and table instructions, such as `i32.load` and `table.get`.
     pub omitted_idx_in_instr: LintLevel,
+
+    #[serde(alias = "unusedSuppression")]
+    /// Lint for suppression comments and annotations that don't suppress any diagnostics.
+    pub unused_suppression: LintLevel,
+
+    #[serde(alias = "constFold")]
+    /// Lint for detecting constant expressions that can be folded and operations that have no effect.
+    pub const_fold: LintLevel,
+
+    /// Lint for detecting instructions that will always trap with constant operands.
+    pub trap: LintLevel,
+
+    #[serde(alias = "segmentBounds")]
+    /// Lint for detecting active data and element segments that exceed initial size of memory or table.
+    pub segment_bounds: LintLevel,
+
+    #[serde(alias = "nameAnnotation")]
+    /// Lint for detecting name annotations that conflict with identifiers.
+    pub name_annotation: LintLevel,
+
+    #[serde(alias = "codeMetadata")]
+    /// Lint for detecting misplaced or malformed code metadata annotations, such as branch hints.
+    pub code_metadata: LintLevel,
 }
 
 impl Default for Lints {
@@ -73,6 +102,12 @@ impl Default for Lints {
             needless_try_table: LintLevel::Warn,
             useless_catch: LintLevel::Warn,
             omitted_idx_in_instr: LintLevel::Allow,
+            unused_suppression: LintLevel::Warn,
+            const_fold: LintLevel::Hint,
+            trap: LintLevel::Warn,
+            segment_bounds: LintLevel::Warn,
+            name_annotation: LintLevel::Warn,
+            code_metadata: LintLevel::Warn,
         }
     }
 }
@@ -102,6 +137,10 @@ pub struct InlayHintOptions {
 
     /// Inlay hints for showing idx.
     pub index: bool,
+
+    #[serde(alias = "codeOffset")]
+    /// Inlay hints for showing byte offset of instructions in binary.
+    pub code_offset: bool,
 }
 
 impl Default for InlayHintOptions {
@@ -110,6 +149,205 @@ impl Default for InlayHintOptions {
             types: true,
             ending: true,
             index: true,
+            code_offset: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+/// WebAssembly features supported by target engines.
+///
+/// Features are enabled by the preset, and each proposal can be enabled or disabled individually.
+pub struct Features {
+    /// Base set of enabled features.
+    pub preset: FeaturePreset,
+
+    /// Sign-extension operators, which are part of WebAssembly 2.0.
+    #[serde(alias = "signExtension")]
+    pub sign_extension: Option<bool>,
+
+    /// Non-trapping float-to-int conversions, which are part of WebAssembly 2.0.
+    #[serde(alias = "nonTrappingFloatToInt")]
+    pub non_trapping_float_to_int: Option<bool>,
+
+    /// Bulk memory operations, which are part of WebAssembly 2.0.
+    #[serde(alias = "bulkMemory")]
+    pub bulk_memory: Option<bool>,
+
+    /// Reference types, which are part of WebAssembly 2.0.
+    #[serde(alias = "referenceTypes")]
+    pub reference_types: Option<bool>,
+
+    /// Fixed-width SIMD, which is part of WebAssembly 2.0.
+    pub simd: Option<bool>,
+
+    /// Tail calls, which are part of WebAssembly 3.0.
+    #[serde(alias = "tailCall")]
+    pub tail_call: Option<bool>,
+
+    /// Typed function references, which are part of WebAssembly 3.0.
+    #[serde(alias = "functionReferences")]
+    pub function_references: Option<bool>,
+
+    /// Garbage collection, which is part of WebAssembly 3.0.
+    pub gc: Option<bool>,
+
+    /// Exception handling, which is part of WebAssembly 3.0.
+    #[serde(alias = "exceptionHandling")]
+    pub exception_handling: Option<bool>,
+
+    /// 64-bit memories and tables, which are part of WebAssembly 3.0.
+    pub memory64: Option<bool>,
+
+    /// Multiple memories, which are part of WebAssembly 3.0.
+    #[serde(alias = "multiMemory")]
+    pub multi_memory: Option<bool>,
+
+    /// Relaxed SIMD, which is part of WebAssembly 3.0.
+    #[serde(alias = "relaxedSimd")]
+    pub relaxed_simd: Option<bool>,
+
+    /// Threads and atomics.
+    pub threads: Option<bool>,
+
+    /// Stack switching.
+    #[serde(alias = "stackSwitching")]
+    pub stack_switching: Option<bool>,
+
+    /// Wide arithmetic.
+    #[serde(alias = "wideArithmetic")]
+    pub wide_arithmetic: Option<bool>,
+}
+
+impl Features {
+    /// Check if the given proposal is enabled by individual option or the preset.
+    pub fn is_enabled(&self, proposal: Proposal) -> bool {
+        let enabled = match proposal {
+            Proposal::SignExtension => self.sign_extension,
+            Proposal::NonTrappingFloatToInt => self.non_trapping_float_to_int,
+            Proposal::BulkMemory => self.bulk_memory,
+            Proposal::ReferenceTypes => self.reference_types,
+            Proposal::Simd => self.simd,
+            Proposal::TailCall => self.tail_call,
+            Proposal::FunctionReferences => self.function_references,
+            Proposal::Gc => self.gc,
+            Proposal::ExceptionHandling => self.exception_handling,
+            Proposal::Memory64 => self.memory64,
+            Proposal::MultiMemory => self.multi_memory,
+            Proposal::RelaxedSimd => self.relaxed_simd,
+            Proposal::Threads => self.threads,
+            Proposal::StackSwitching => self.stack_switching,
+            Proposal::WideArithmetic => self.wide_arithmetic,
+        };
+        enabled.unwrap_or_else(|| self.preset.includes(proposal))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+/// Preset of WebAssembly features.
+pub enum FeaturePreset {
+    #[serde(rename = "mvp", alias = "1.0")]
+    /// WebAssembly 1.0, also known as MVP.
+    Mvp,
+    #[serde(rename = "2.0")]
+    /// WebAssembly 2.0.
+    V2,
+    #[serde(rename = "3.0")]
+    /// WebAssembly 3.0.
+    V3,
+    #[default]
+    #[serde(rename = "latest")]
+    /// All features that are supported by language service, including proposals that aren't standardized yet.
+    Latest,
+}
+
+impl FeaturePreset {
+    fn includes(self, proposal: Proposal) -> bool {
+        let v2 = matches!(
+            proposal,
+            Proposal::SignExtension
+                | Proposal::NonTrappingFloatToInt
+                | Proposal::BulkMemory
+                | Proposal::ReferenceTypes
+                | Proposal::Simd
+        );
+        let v3 = matches!(
+            proposal,
+            Proposal::TailCall
+                | Proposal::FunctionReferences
+                | Proposal::Gc
+                | Proposal::ExceptionHandling
+                | Proposal::Memory64
+                | Proposal::MultiMemory
+                | Proposal::RelaxedSimd
+        );
+        match self {
+            FeaturePreset::Mvp => false,
+            FeaturePreset::V2 => v2,
+            FeaturePreset::V3 => v2 || v3,
+            FeaturePreset::Latest => true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// WebAssembly proposals that can be enabled or disabled.
+pub enum Proposal {
+    SignExtension,
+    NonTrappingFloatToInt,
+    BulkMemory,
+    ReferenceTypes,
+    Simd,
+    TailCall,
+    FunctionReferences,
+    Gc,
+    ExceptionHandling,
+    Memory64,
+    MultiMemory,
+    RelaxedSimd,
+    Threads,
+    StackSwitching,
+    WideArithmetic,
+}
+
+impl Proposal {
+    pub(crate) const ALL: [Proposal; 15] = [
+        Proposal::SignExtension,
+        Proposal::NonTrappingFloatToInt,
+        Proposal::BulkMemory,
+        Proposal::ReferenceTypes,
+        Proposal::Simd,
+        Proposal::TailCall,
+        Proposal::FunctionReferences,
+        Proposal::Gc,
+        Proposal::ExceptionHandling,
+        Proposal::Memory64,
+        Proposal::MultiMemory,
+        Proposal::RelaxedSimd,
+        Proposal::Threads,
+        Proposal::StackSwitching,
+        Proposal::WideArithmetic,
+    ];
+
+    /// Human-readable name of the proposal.
+    pub fn name(self) -> &'static str {
+        match self {
+            Proposal::SignExtension => "sign extension",
+            Proposal::NonTrappingFloatToInt => "non-trapping float-to-int conversions",
+            Proposal::BulkMemory => "bulk memory",
+            Proposal::ReferenceTypes => "reference types",
+            Proposal::Simd => "SIMD",
+            Proposal::TailCall => "tail call",
+            Proposal::FunctionReferences => "typed function references",
+            Proposal::Gc => "GC",
+            Proposal::ExceptionHandling => "exception handling",
+            Proposal::Memory64 => "memory64",
+            Proposal::MultiMemory => "multi-memory",
+            Proposal::RelaxedSimd => "relaxed SIMD",
+            Proposal::Threads => "threads",
+            Proposal::StackSwitching => "stack switching",
+            Proposal::WideArithmetic => "wide arithmetic",
         }
     }
 }
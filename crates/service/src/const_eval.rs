@@ -0,0 +1,181 @@
+use crate::{
+    binder::{SymbolKey, SymbolKind, SymbolTable},
+    const_fold,
+    document::Document,
+    encoder::num::{parse_f32, parse_f64, parse_int},
+    mutability,
+    types_analyzer::{self, CompositeType},
+};
+use rustc_hash::FxHashMap;
+use std::fmt;
+use wat_syntax::{
+    AmberNode, SyntaxKind,
+    ast::{AstNode, Instr},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Value of constant expression.
+pub(crate) enum ConstValue {
+    I32(i32),
+    I64(i64),
+    /// Bits of 32-bit float.
+    F32(u32),
+    /// Bits of 64-bit float.
+    F64(u64),
+    /// Null reference with its heap type.
+    RefNull(String),
+    RefFunc(String),
+    /// Unboxed 31-bit scalar, which is stored as sign-extended.
+    RefI31(i32),
+    Struct {
+        ty: String,
+        fields: Vec<ConstValue>,
+    },
+}
+
+impl fmt::Display for ConstValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstValue::I32(value) => write!(f, "(i32.const {value})"),
+            ConstValue::I64(value) => write!(f, "(i64.const {value})"),
+            ConstValue::F32(bits) => write!(f, "(f32.const {})", f32::from_bits(*bits)),
+            ConstValue::F64(bits) => write!(f, "(f64.const {})", f64::from_bits(*bits)),
+            ConstValue::RefNull(heap_ty) => write!(f, "(ref.null {heap_ty})"),
+            ConstValue::RefFunc(func) => write!(f, "(ref.func {func})"),
+            ConstValue::RefI31(value) => write!(f, "(ref.i31 (i32.const {value}))"),
+            ConstValue::Struct { ty, fields } => {
+                write!(f, "(struct.new {ty}")?;
+                fields.iter().try_for_each(|field| write!(f, " {field}"))?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+#[salsa::tracked(returns(ref))]
+/// Evaluate initial values of all globals in the document.
+pub(crate) fn get_global_values(db: &dyn salsa::Database, document: Document) -> FxHashMap<SymbolKey, ConstValue> {
+    let symbol_table = SymbolTable::of(db, document);
+    let mut values = FxHashMap::default();
+    // globals can only refer to previous globals, so evaluating in order is enough
+    symbol_table
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.kind == SymbolKind::GlobalDef)
+        .for_each(|symbol| {
+            let node = symbol.amber();
+            if node.kind() == SyntaxKind::MODULE_FIELD_GLOBAL
+                && let Some(value) = eval_with(db, document, symbol_table, &values, node)
+            {
+                values.insert(symbol.key, value);
+            }
+        });
+    values
+}
+
+/// Evaluate constant expression consisting of instructions under the given node,
+/// such as global, offset and element expression.
+pub(crate) fn eval(db: &dyn salsa::Database, document: Document, node: AmberNode) -> Option<ConstValue> {
+    eval_with(
+        db,
+        document,
+        SymbolTable::of(db, document),
+        get_global_values(db, document),
+        node,
+    )
+}
+
+fn eval_with(
+    db: &dyn salsa::Database,
+    document: Document,
+    symbol_table: &SymbolTable,
+    globals: &FxHashMap<SymbolKey, ConstValue>,
+    node: AmberNode,
+) -> Option<ConstValue> {
+    let mut evaluator = Evaluator {
+        db,
+        document,
+        symbol_table,
+        globals,
+        stack: Vec::with_capacity(2),
+    };
+    node.children_by_kind(Instr::can_cast)
+        .try_for_each(|instr| evaluator.eval_instr(instr))?;
+    let value = evaluator.stack.pop()?;
+    evaluator.stack.is_empty().then_some(value)
+}
+
+struct Evaluator<'a, 'db> {
+    db: &'db dyn salsa::Database,
+    document: Document,
+    symbol_table: &'a SymbolTable<'db>,
+    globals: &'a FxHashMap<SymbolKey, ConstValue>,
+    stack: Vec<ConstValue>,
+}
+impl Evaluator<'_, '_> {
+    fn eval_instr(&mut self, instr: AmberNode) -> Option<()> {
+        if instr.kind() != SyntaxKind::PLAIN_INSTR {
+            return None;
+        }
+        instr
+            .children_by_kind(Instr::can_cast)
+            .try_for_each(|child| self.eval_instr(child))?;
+        let mut immediates = instr.children_by_kind(SyntaxKind::IMMEDIATE);
+        let name = instr.tokens_by_kind(SyntaxKind::INSTR_NAME).next()?;
+        let value = match name.text() {
+            "i32.const" => ConstValue::I32(parse_int(number(immediates.next()?)?, 32)? as i32),
+            "i64.const" => ConstValue::I64(parse_int(number(immediates.next()?)?, 64)? as i64),
+            "f32.const" => ConstValue::F32(parse_f32(number(immediates.next()?)?)?),
+            "f64.const" => ConstValue::F64(parse_f64(number(immediates.next()?)?)?),
+            "i32.add" | "i32.sub" | "i32.mul" | "i64.add" | "i64.sub" | "i64.mul" => {
+                let rhs = self.stack.pop()?;
+                let lhs = self.stack.pop()?;
+                const_fold::eval_op(name.text(), &[lhs, rhs])?
+            }
+            "global.get" => {
+                let global = self.symbol_table.find_def(immediates.next()?.into())?;
+                // mutable globals aren't allowed in constant expressions
+                if mutability::get_mutabilities(self.db, self.document)
+                    .get(&global.key)
+                    .is_none_or(|mutability| mutability.mut_keyword.is_some())
+                {
+                    return None;
+                }
+                self.globals.get(&global.key)?.clone()
+            }
+            "ref.null" => ConstValue::RefNull(immediates.next()?.green().to_string()),
+            "ref.func" => ConstValue::RefFunc(immediates.next()?.green().to_string()),
+            "ref.i31" => {
+                let ConstValue::I32(value) = self.stack.pop()? else {
+                    return None;
+                };
+                ConstValue::RefI31(value << 1 >> 1)
+            }
+            "struct.new" => {
+                let immediate = immediates.next()?;
+                let def = self.symbol_table.find_def(immediate.into())?;
+                let CompositeType::Struct(fields) = &types_analyzer::get_def_types(self.db, self.document)
+                    .get(&def.key)?
+                    .comp
+                else {
+                    return None;
+                };
+                let fields = self.stack.split_off(self.stack.len().checked_sub(fields.0.len())?);
+                ConstValue::Struct {
+                    ty: immediate.green().to_string(),
+                    fields,
+                }
+            }
+            _ => return None,
+        };
+        self.stack.push(value);
+        Some(())
+    }
+}
+
+pub(crate) fn number(immediate: AmberNode<'_>) -> Option<&str> {
+    immediate
+        .tokens_by_kind([SyntaxKind::INT, SyntaxKind::UNSIGNED_INT, SyntaxKind::FLOAT])
+        .next()
+        .map(|token| token.text())
+}
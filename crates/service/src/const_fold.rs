@@ -0,0 +1,565 @@
+use crate::{
+    const_eval::{self, ConstValue},
+    encoder::num::{parse_f32, parse_f64, parse_int},
+    helpers, mutability,
+    types_analyzer::{self, InstrSigResolverCtx},
+};
+use std::{convert::Infallible, iter, ops::ControlFlow};
+use wat_syntax::{
+    AmberNode, SyntaxKind, SyntaxNode, TextRange,
+    ast::{AstNode, Instr},
+};
+
+#[derive(Clone, Debug)]
+/// Instruction sequence that can be replaced with a simpler one without changing its behavior.
+pub(crate) struct Foldable {
+    /// Range of instructions to be replaced, including operands in flat style.
+    pub range: TextRange,
+    pub kind: FoldKind,
+    pub replacement: String,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum FoldKind {
+    /// All operands are constants, so it can be evaluated to a constant.
+    Const(ConstValue),
+    /// Constant operand doesn't change the other operand, such as adding 0 or multiplying by 1.
+    Identity { instr_name: String, operand: ConstValue },
+}
+
+#[derive(Clone, Debug, Default)]
+/// Value on the simulated stack.
+struct Slot {
+    /// Range of instructions that produce this value,
+    /// only if they're contiguous and produce nothing else.
+    range: Option<TextRange>,
+    value: Option<ConstValue>,
+    /// Index of foldable that produces this value.
+    folded: Option<usize>,
+}
+
+/// Find out foldable instruction sequences directly in the given block, including folded instructions.
+/// Nested blocks won't be looked into.
+///
+/// Only the outermost foldable is returned if foldables are nested.
+pub(crate) fn find_foldables<'db: 'bump, 'bump>(
+    outer_block: &'db SyntaxNode,
+    ctx: &InstrSigResolverCtx<'db, 'bump>,
+) -> Vec<Foldable> {
+    let text = ctx.document.text(ctx.db);
+    let comments = outer_block
+        .amber()
+        .descendant_tokens()
+        .filter(|(token, ..)| matches!(token.kind(), SyntaxKind::LINE_COMMENT | SyntaxKind::BLOCK_COMMENT))
+        .map(|(token, ..)| token.text_range())
+        .collect::<Vec<_>>();
+    let mut foldables = Vec::<Option<Foldable>>::new();
+    let mut slots = Vec::<Slot>::with_capacity(4);
+    // values produced before this position can't be folded with later instructions,
+    // because there're other instructions between them
+    let mut barrier = outer_block.text_range().start();
+    types_analyzer::simulate::<Infallible>(outer_block, ctx, |node, sig, _| {
+        // simulated slots may be fewer than values on type stack if there're block params,
+        // which are treated as unknown values
+        let operands = slots.split_off(slots.len().saturating_sub(sig.params.len()));
+        let instr_name = node
+            .tokens_by_kind(SyntaxKind::INSTR_NAME)
+            .next()
+            .map(|token| token.text());
+        let slot = instr_name
+            .filter(|_| {
+                sig.results.len() == 1
+                    && operands.len() == sig.params.len()
+                    && operands
+                        .iter()
+                        .all(|operand| operand.range.is_some_and(|range| range.start() >= barrier))
+            })
+            .map(|instr_name| {
+                let range = operands
+                    .iter()
+                    .filter_map(|operand| operand.range)
+                    .fold(node.text_range(), TextRange::cover);
+                let Some(foldable) = fold(node, instr_name, &operands, range, text, &comments, &foldables) else {
+                    return Slot {
+                        range: Some(range),
+                        value: eval_const_instr(node, instr_name),
+                        folded: None,
+                    };
+                };
+                // inner foldables are covered by this one
+                operands.iter().filter_map(|operand| operand.folded).for_each(|index| {
+                    foldables[index] = None;
+                });
+                let value = match &foldable.kind {
+                    FoldKind::Const(value) => Some(value.clone()),
+                    FoldKind::Identity { .. } => None,
+                };
+                foldables.push(Some(foldable));
+                Slot {
+                    range: Some(range),
+                    value,
+                    folded: Some(foldables.len() - 1),
+                }
+            });
+        if instr_name.is_some_and(helpers::is_stack_polymorphic) {
+            slots.clear();
+        }
+        if let Some(slot) = slot {
+            slots.push(slot);
+        } else {
+            barrier = node.text_range().end();
+            slots.extend(sig.results.iter().map(|_| Slot::default()));
+        }
+        ControlFlow::Continue(sig)
+    });
+    foldables.into_iter().flatten().collect()
+}
+
+/// Propagate constant values through operand stack in the given block.
+///
+/// `visit` is called with each plain instruction and constant values of its operands before it takes effect.
+/// Unlike finding foldables, operands don't need to be produced by adjacent instructions,
+/// and values of immutable globals are also propagated.
+pub(crate) fn propagate<'db: 'bump, 'bump>(
+    outer_block: &'db SyntaxNode,
+    ctx: &InstrSigResolverCtx<'db, 'bump>,
+    mut visit: impl FnMut(AmberNode<'db>, &str, &[Option<ConstValue>]),
+) {
+    let globals = const_eval::get_global_values(ctx.db, ctx.document);
+    let mutabilities = mutability::get_mutabilities(ctx.db, ctx.document);
+    let mut values = Vec::<Option<ConstValue>>::with_capacity(4);
+    types_analyzer::simulate::<Infallible>(outer_block, ctx, |node, sig, _| {
+        // values may be fewer than params if there're block params, which are unknown
+        let mut operands = values.split_off(values.len().saturating_sub(sig.params.len()));
+        if operands.len() < sig.params.len() {
+            operands.splice(0..0, iter::repeat_n(None, sig.params.len() - operands.len()));
+        }
+        let instr_name = node
+            .tokens_by_kind(SyntaxKind::INSTR_NAME)
+            .next()
+            .map(|token| token.text());
+        let value = instr_name.and_then(|instr_name| {
+            visit(node, instr_name, &operands);
+            match instr_name {
+                "ref.null" => Some(ConstValue::RefNull(
+                    node.children_by_kind(SyntaxKind::IMMEDIATE).next()?.green().to_string(),
+                )),
+                "global.get" => {
+                    let global = ctx
+                        .symbol_table
+                        .find_def(node.children_by_kind(SyntaxKind::IMMEDIATE).next()?.into())?;
+                    if mutabilities
+                        .get(&global.key)
+                        .is_none_or(|mutability| mutability.mut_keyword.is_some())
+                    {
+                        None
+                    } else {
+                        globals.get(&global.key).cloned()
+                    }
+                }
+                _ if operands.is_empty() => eval_const_instr(node, instr_name),
+                _ => eval_op(instr_name, &operands.iter().cloned().collect::<Option<Vec<_>>>()?),
+            }
+        });
+        if instr_name.is_some_and(helpers::is_stack_polymorphic) {
+            values.clear();
+        }
+        if sig.results.len() == 1 {
+            values.push(value);
+        } else {
+            values.extend(sig.results.iter().map(|_| None));
+        }
+        ControlFlow::Continue(sig)
+    });
+}
+
+fn fold(
+    node: AmberNode,
+    instr_name: &str,
+    operands: &[Slot],
+    range: TextRange,
+    text: &str,
+    comments: &[TextRange],
+    foldables: &[Option<Foldable>],
+) -> Option<Foldable> {
+    if operands.is_empty() {
+        return None;
+    }
+    if let Some(values) = operands
+        .iter()
+        .map(|operand| operand.value.clone())
+        .collect::<Option<Vec<_>>>()
+    {
+        let value = eval_op(instr_name, &values)?;
+        let replacement = match &value {
+            ConstValue::I32(value) => format!("i32.const {value}"),
+            ConstValue::I64(value) => format!("i64.const {value}"),
+            ConstValue::F32(bits) => format!("f32.const {}", f32::from_bits(*bits)),
+            ConstValue::F64(bits) => format!("f64.const {}", f64::from_bits(*bits)),
+            _ => return None,
+        };
+        let replacement = if node.children_by_kind(Instr::can_cast).next().is_some() {
+            format!("({replacement})")
+        } else {
+            replacement
+        };
+        return Some(Foldable {
+            range,
+            kind: FoldKind::Const(value),
+            replacement: with_comments(replacement, range, None, comments, text),
+        });
+    }
+
+    let [lhs, rhs] = operands else {
+        return None;
+    };
+    let (operand, other) = match (&lhs.value, &rhs.value) {
+        (_, Some(value)) if is_identity(instr_name, value, true) => (value, lhs),
+        (Some(value), _) if is_identity(instr_name, value, false) => (value, rhs),
+        _ => return None,
+    };
+    let replacement = match other.folded {
+        Some(index) => foldables.get(index)?.as_ref()?.replacement.clone(),
+        None => text[other.range?].to_string(),
+    };
+    Some(Foldable {
+        range,
+        kind: FoldKind::Identity {
+            instr_name: instr_name.to_string(),
+            operand: operand.clone(),
+        },
+        replacement: with_comments(replacement, range, other.range, comments, text),
+    })
+}
+
+/// Put comments in the replaced range before the replacement, so they won't be lost.
+/// Comments in the kept range are already in the replacement.
+fn with_comments(
+    replacement: String,
+    range: TextRange,
+    kept: Option<TextRange>,
+    comments: &[TextRange],
+    text: &str,
+) -> String {
+    let line_start = text[..usize::from(range.start())].rfind('\n').map_or(0, |i| i + 1);
+    let indent = text[line_start..]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect::<String>();
+    let mut result = String::with_capacity(replacement.len());
+    comments
+        .iter()
+        .filter(|comment| range.contains_range(**comment) && kept.is_none_or(|kept| !kept.contains_range(**comment)))
+        .for_each(|comment| {
+            let comment = &text[*comment];
+            result.push_str(comment);
+            if comment.starts_with(";;") {
+                result.push('\n');
+                result.push_str(&indent);
+            } else {
+                result.push(' ');
+            }
+        });
+    result.push_str(&replacement);
+    result
+}
+
+fn eval_const_instr(node: AmberNode, instr_name: &str) -> Option<ConstValue> {
+    let number = const_eval::number(node.children_by_kind(SyntaxKind::IMMEDIATE).next()?)?;
+    match instr_name {
+        "i32.const" => parse_int(number, 32).map(|value| ConstValue::I32(value as i32)),
+        "i64.const" => parse_int(number, 64).map(|value| ConstValue::I64(value as i64)),
+        "f32.const" => parse_f32(number).map(ConstValue::F32),
+        "f64.const" => parse_f64(number).map(ConstValue::F64),
+        _ => None,
+    }
+}
+
+/// Check if the constant operand makes integer operation return the other operand as is.
+fn is_identity(instr_name: &str, operand: &ConstValue, is_rhs: bool) -> bool {
+    let Some((ty, op)) = instr_name.split_once('.') else {
+        return false;
+    };
+    let Some((value, bits)) = int(operand) else {
+        return false;
+    };
+    if ty != int_type(bits) {
+        return false;
+    }
+    match op {
+        "add" | "or" | "xor" => value == 0,
+        "sub" => is_rhs && value == 0,
+        "mul" => value == 1,
+        "div_s" | "div_u" => is_rhs && value == 1,
+        "and" => value == mask(bits),
+        "shl" | "shr_s" | "shr_u" | "rotl" | "rotr" => is_rhs && value % bits as u64 == 0,
+        _ => false,
+    }
+}
+
+/// Evaluate numeric instruction with constant operands.
+///
+/// It returns `None` if the instruction isn't pure numeric operation,
+/// or it traps or produces NaN which may be nondeterministic.
+pub(crate) fn eval_op(instr_name: &str, operands: &[ConstValue]) -> Option<ConstValue> {
+    let (ty, op) = instr_name.split_once('.')?;
+    match (ty, operands) {
+        ("i32" | "i64", [lhs, rhs]) => {
+            let (lhs, bits) = int(lhs)?;
+            let (rhs, rhs_bits) = int(rhs)?;
+            if ty != int_type(bits) || bits != rhs_bits {
+                return None;
+            }
+            eval_int_binary(op, lhs, rhs, bits)
+        }
+        ("i32" | "i64", [operand]) => eval_int_unary(ty, op, operand),
+        ("f32" | "f64", [lhs, rhs]) => {
+            let (lhs, bits) = float(lhs)?;
+            let (rhs, rhs_bits) = float(rhs)?;
+            if ty != float_type(bits) || bits != rhs_bits {
+                return None;
+            }
+            eval_float_binary(op, lhs, rhs, bits)
+        }
+        ("f32" | "f64", [operand]) => eval_float_unary(ty, op, operand),
+        _ => None,
+    }
+}
+
+fn eval_int_binary(op: &str, lhs: u64, rhs: u64, bits: u32) -> Option<ConstValue> {
+    let (signed_lhs, signed_rhs) = (signed(lhs, bits), signed(rhs, bits));
+    let shift = (rhs % bits as u64) as u32;
+    let value = match op {
+        "add" => lhs.wrapping_add(rhs),
+        "sub" => lhs.wrapping_sub(rhs),
+        "mul" => lhs.wrapping_mul(rhs),
+        "div_s" => {
+            if signed_rhs == 0 || (signed_lhs == signed(1 << (bits - 1), bits) && signed_rhs == -1) {
+                return None;
+            }
+            (signed_lhs / signed_rhs) as u64
+        }
+        "div_u" => lhs.checked_div(rhs)?,
+        "rem_s" => {
+            if signed_rhs == 0 {
+                return None;
+            }
+            signed_lhs.wrapping_rem(signed_rhs) as u64
+        }
+        "rem_u" => lhs.checked_rem(rhs)?,
+        "and" => lhs & rhs,
+        "or" => lhs | rhs,
+        "xor" => lhs ^ rhs,
+        "shl" => lhs << shift,
+        "shr_s" => (signed_lhs >> shift) as u64,
+        "shr_u" => lhs >> shift,
+        "rotl" => lhs << shift | lhs >> ((bits - shift) % bits),
+        "rotr" => lhs >> shift | lhs << ((bits - shift) % bits),
+        "eq" => (lhs == rhs) as u64,
+        "ne" => (lhs != rhs) as u64,
+        "lt_s" => (signed_lhs < signed_rhs) as u64,
+        "lt_u" => (lhs < rhs) as u64,
+        "gt_s" => (signed_lhs > signed_rhs) as u64,
+        "gt_u" => (lhs > rhs) as u64,
+        "le_s" => (signed_lhs <= signed_rhs) as u64,
+        "le_u" => (lhs <= rhs) as u64,
+        "ge_s" => (signed_lhs >= signed_rhs) as u64,
+        "ge_u" => (lhs >= rhs) as u64,
+        _ => return None,
+    };
+    if matches!(
+        op,
+        "eq" | "ne" | "lt_s" | "lt_u" | "gt_s" | "gt_u" | "le_s" | "le_u" | "ge_s" | "ge_u"
+    ) {
+        Some(ConstValue::I32(value as i32))
+    } else {
+        Some(to_int(value, bits))
+    }
+}
+
+fn eval_int_unary(ty: &str, op: &str, operand: &ConstValue) -> Option<ConstValue> {
+    let bits = if ty == "i32" { 32 } else { 64 };
+    if let Some((value, operand_bits)) = int(operand) {
+        let value = match op {
+            _ if operand_bits != bits => match (ty, op) {
+                ("i32", "wrap_i64") => value,
+                ("i64", "extend_i32_s") => signed(value, 32) as u64,
+                ("i64", "extend_i32_u") => value,
+                _ => return None,
+            },
+            "clz" => (value.leading_zeros() - (64 - bits)) as u64,
+            "ctz" => value.trailing_zeros().min(bits) as u64,
+            "popcnt" => value.count_ones() as u64,
+            "eqz" => return Some(ConstValue::I32((value == 0) as i32)),
+            "extend8_s" => signed(value & 0xff, 8) as u64,
+            "extend16_s" => signed(value & 0xffff, 16) as u64,
+            "extend32_s" if bits == 64 => signed(value & 0xffff_ffff, 32) as u64,
+            _ => return None,
+        };
+        return Some(to_int(value, bits));
+    }
+
+    let (value, operand_bits) = float(operand)?;
+    if op == format!("reinterpret_{}", float_type(operand_bits)) {
+        return match operand {
+            ConstValue::F32(value) if bits == 32 => Some(ConstValue::I32(*value as i32)),
+            ConstValue::F64(value) if bits == 64 => Some(ConstValue::I64(*value as i64)),
+            _ => None,
+        };
+    }
+    let (saturating, rest) = op
+        .strip_prefix("trunc_sat_")
+        .map(|rest| (true, rest))
+        .or_else(|| op.strip_prefix("trunc_").map(|rest| (false, rest)))?;
+    let is_signed = match rest.strip_prefix(float_type(operand_bits))? {
+        "_s" => true,
+        "_u" => false,
+        _ => return None,
+    };
+    if !saturating {
+        let truncated = value.trunc();
+        let (min, max) = if is_signed {
+            (-(2f64.powi(bits as i32 - 1)), 2f64.powi(bits as i32 - 1))
+        } else {
+            (0.0, 2f64.powi(bits as i32))
+        };
+        // NaN and values out of range trap
+        if !(truncated >= min && truncated < max) {
+            return None;
+        }
+    }
+    // float to int casts in Rust are saturating, which is the same as `trunc_sat`
+    let value = match (bits, is_signed) {
+        (32, true) => value as i32 as u32 as u64,
+        (32, false) => value as u32 as u64,
+        (_, true) => value as i64 as u64,
+        (_, false) => value as u64,
+    };
+    Some(to_int(value, bits))
+}
+
+fn eval_float_binary(op: &str, lhs: f64, rhs: f64, bits: u32) -> Option<ConstValue> {
+    let value = match op {
+        "add" => lhs + rhs,
+        "sub" => lhs - rhs,
+        "mul" => lhs * rhs,
+        "div" => lhs / rhs,
+        "min" | "max" => {
+            if lhs == rhs {
+                // distinguish `-0` and `+0`
+                let (lhs, rhs) = (lhs.to_bits(), rhs.to_bits());
+                f64::from_bits(if op == "min" { lhs | rhs } else { lhs & rhs })
+            } else if (lhs < rhs) == (op == "min") {
+                lhs
+            } else {
+                rhs
+            }
+        }
+        "copysign" => lhs.copysign(rhs),
+        "eq" => return Some(ConstValue::I32((lhs == rhs) as i32)),
+        "ne" => return Some(ConstValue::I32((lhs != rhs) as i32)),
+        "lt" => return Some(ConstValue::I32((lhs < rhs) as i32)),
+        "gt" => return Some(ConstValue::I32((lhs > rhs) as i32)),
+        "le" => return Some(ConstValue::I32((lhs <= rhs) as i32)),
+        "ge" => return Some(ConstValue::I32((lhs >= rhs) as i32)),
+        _ => return None,
+    };
+    // computing `f32` in `f64` then rounding gives the same result for these operations
+    to_float(value, bits)
+}
+
+fn eval_float_unary(ty: &str, op: &str, operand: &ConstValue) -> Option<ConstValue> {
+    let bits = if ty == "f32" { 32 } else { 64 };
+    if let Some((value, operand_bits)) = int(operand) {
+        if op == format!("reinterpret_{}", int_type(operand_bits)) {
+            let value = match operand {
+                ConstValue::I32(value) if bits == 32 => ConstValue::F32(*value as u32),
+                ConstValue::I64(value) if bits == 64 => ConstValue::F64(*value as u64),
+                _ => return None,
+            };
+            // NaN can't be written as decimal literal
+            return float(&value).is_some().then_some(value);
+        }
+        let is_signed = match op.strip_prefix("convert_")?.strip_prefix(int_type(operand_bits))? {
+            "_s" => true,
+            "_u" => false,
+            _ => return None,
+        };
+        // convert directly to avoid rounding twice
+        return Some(match (bits, is_signed) {
+            (32, true) => ConstValue::F32((signed(value, operand_bits) as f32).to_bits()),
+            (32, false) => ConstValue::F32((value as f32).to_bits()),
+            (_, true) => ConstValue::F64((signed(value, operand_bits) as f64).to_bits()),
+            (_, false) => ConstValue::F64((value as f64).to_bits()),
+        });
+    }
+
+    let (value, operand_bits) = float(operand)?;
+    let value = match op {
+        _ if operand_bits != bits => match (ty, op) {
+            ("f32", "demote_f64") | ("f64", "promote_f32") => value,
+            _ => return None,
+        },
+        "abs" => value.abs(),
+        "neg" => -value,
+        "sqrt" => value.sqrt(),
+        "ceil" => value.ceil(),
+        "floor" => value.floor(),
+        "trunc" => value.trunc(),
+        "nearest" => value.round_ties_even(),
+        _ => return None,
+    };
+    to_float(value, bits)
+}
+
+fn int(value: &ConstValue) -> Option<(u64, u32)> {
+    match value {
+        ConstValue::I32(value) => Some((*value as u32 as u64, 32)),
+        ConstValue::I64(value) => Some((*value as u64, 64)),
+        _ => None,
+    }
+}
+
+/// Get float value which is exactly represented in `f64`, except NaN.
+fn float(value: &ConstValue) -> Option<(f64, u32)> {
+    let (value, bits) = match value {
+        ConstValue::F32(bits) => (f32::from_bits(*bits) as f64, 32),
+        ConstValue::F64(bits) => (f64::from_bits(*bits), 64),
+        _ => return None,
+    };
+    (!value.is_nan()).then_some((value, bits))
+}
+
+fn to_int(value: u64, bits: u32) -> ConstValue {
+    if bits == 32 {
+        ConstValue::I32(value as u32 as i32)
+    } else {
+        ConstValue::I64(value as i64)
+    }
+}
+
+fn to_float(value: f64, bits: u32) -> Option<ConstValue> {
+    if value.is_nan() {
+        None
+    } else if bits == 32 {
+        Some(ConstValue::F32((value as f32).to_bits()))
+    } else {
+        Some(ConstValue::F64(value.to_bits()))
+    }
+}
+
+fn signed(value: u64, bits: u32) -> i64 {
+    ((value << (64 - bits)) as i64) >> (64 - bits)
+}
+
+fn mask(bits: u32) -> u64 {
+    if bits == 64 { u64::MAX } else { (1 << bits) - 1 }
+}
+
+fn int_type(bits: u32) -> &'static str {
+    if bits == 32 { "i32" } else { "i64" }
+}
+
+fn float_type(bits: u32) -> &'static str {
+    if bits == 32 { "f32" } else { "f64" }
+}
@@ -0,0 +1,191 @@
+use crate::{
+    annotation::{self, Item, Malformed},
+    binder::SymbolKey,
+    document::Document,
+    helpers,
+};
+use std::fmt;
+use wat_syntax::{AmberNode, AmberToken, SyntaxKind, TextRange};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Custom section annotation from the annotations proposal,
+/// such as `(@custom "name" (after func) "content")`.
+pub(crate) struct CustomSection {
+    /// Range of the whole annotation.
+    pub range: TextRange,
+    /// Decoded section name with range of its string literal.
+    pub name: Option<(String, TextRange)>,
+    pub placement: Option<Placement>,
+    /// Byte length of section content.
+    pub len: usize,
+    /// Node that directly contains the annotation.
+    /// For annotations at top level of implicit module, it's the implicit module.
+    pub parent: SymbolKey,
+    pub errors: Vec<Malformed>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Placement {
+    pub after: bool,
+    /// Section kind or `first` and `last`.
+    pub section: String,
+    pub range: TextRange,
+}
+impl fmt::Display for Placement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", if self.after { "after" } else { "before" }, self.section)
+    }
+}
+
+const SECTIONS: [&str; 13] = [
+    "type",
+    "import",
+    "func",
+    "table",
+    "memory",
+    "tag",
+    "global",
+    "export",
+    "start",
+    "elem",
+    "datacount",
+    "code",
+    "data",
+];
+
+#[salsa::tracked(returns(ref))]
+/// Collect and parse all custom section annotations in the document.
+pub(crate) fn get_custom_sections(db: &dyn salsa::Database, document: Document) -> Vec<CustomSection> {
+    let root = AmberNode::new_root(document.root(db));
+    // module fields in implicit module aren't wrapped,
+    // so annotations before the first field or after the last field are placed in root
+    let implicit_module = root
+        .children_by_kind(SyntaxKind::MODULE)
+        .find(|module| module.tokens_by_kind(SyntaxKind::L_PAREN).next().is_none());
+    let mut sections = vec![];
+    let mut tokens = root.descendant_tokens();
+    while let Some((token, parent, _)) = tokens.next() {
+        if token.kind() != SyntaxKind::ANNOT_START
+            || token
+                .text()
+                .strip_prefix("(@")
+                .map(|s| s.strip_prefix('"').and_then(|s| s.strip_suffix('"')).unwrap_or(s))
+                .is_none_or(|name| name != "custom")
+        {
+            continue;
+        }
+        let start = token.text_range().start();
+        let (items, end) =
+            annotation::collect_items(tokens.by_ref().map(|(token, ..)| token), token.text_range().end());
+        let parent = match implicit_module {
+            Some(module) if parent.kind() == SyntaxKind::ROOT => module,
+            _ => parent,
+        };
+        sections.push(parse(&items, TextRange::new(start, end), SymbolKey::from(parent)));
+    }
+    sections
+}
+
+fn parse(items: &[Item], range: TextRange, parent: SymbolKey) -> CustomSection {
+    let mut errors = vec![];
+    let mut items = items.iter();
+    let name = match items.next() {
+        Some(Item::Atom(token)) if token.text().starts_with('"') => {
+            Some((String::from_utf8_lossy(&decode(token)).into_owned(), token.text_range()))
+        }
+        Some(Item::Atom(token)) => {
+            errors.push(Malformed {
+                range: token.text_range(),
+                message: format!("expected custom section name as string, found `{}`", token.text()),
+            });
+            None
+        }
+        Some(Item::List(_, range)) => {
+            errors.push(Malformed {
+                range: *range,
+                message: "expected custom section name as string".into(),
+            });
+            None
+        }
+        None => {
+            errors.push(Malformed {
+                range,
+                message: "missing custom section name".into(),
+            });
+            None
+        }
+    };
+
+    let mut placement = None;
+    let mut len = 0;
+    for item in items {
+        match item {
+            Item::Atom(token) if token.text().starts_with('"') => len += decode(token).len(),
+            Item::Atom(token) => errors.push(Malformed {
+                range: token.text_range(),
+                message: format!("expected string as custom section content, found `{}`", token.text()),
+            }),
+            Item::List(..) if placement.is_some() => errors.push(Malformed {
+                range: item.range(),
+                message: "placement of custom section is already specified".into(),
+            }),
+            Item::List(..) if len > 0 => errors.push(Malformed {
+                range: item.range(),
+                message: "placement of custom section must come before its content".into(),
+            }),
+            Item::List(list, range) => match parse_placement(list, *range) {
+                Ok(parsed) => placement = Some(parsed),
+                Err(malformed) => errors.push(malformed),
+            },
+        }
+    }
+    CustomSection {
+        range,
+        name,
+        placement,
+        len,
+        parent,
+        errors,
+    }
+}
+
+fn parse_placement(list: &[Item], range: TextRange) -> Result<Placement, Malformed> {
+    let [Item::Atom(keyword), Item::Atom(section)] = list else {
+        return Err(Malformed {
+            range,
+            message: "expected `(before <section>)` or `(after <section>)`".into(),
+        });
+    };
+    let after = match keyword.text() {
+        "before" => false,
+        "after" => true,
+        text => {
+            return Err(Malformed {
+                range: keyword.text_range(),
+                message: format!("expected `before` or `after`, found `{text}`"),
+            });
+        }
+    };
+    match section.text() {
+        "first" if !after => {}
+        "last" if after => {}
+        text if SECTIONS.contains(&text) => {}
+        text => {
+            return Err(Malformed {
+                range: section.text_range(),
+                message: format!("unknown section `{text}`"),
+            });
+        }
+    }
+    Ok(Placement {
+        after,
+        section: section.text().into(),
+        range,
+    })
+}
+
+fn decode(token: &AmberToken) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(token.text().len());
+    helpers::decode_string(token.text(), token.text_range().start(), &mut bytes, &mut vec![]);
+    bytes
+}
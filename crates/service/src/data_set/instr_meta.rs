@@ -3,7 +3,7 @@ use rustc_hash::{FxBuildHasher, FxHashMap};
 use std::{collections::HashMap, sync::LazyLock};
 
 pub(crate) static INSTR_SIG: LazyLock<FxHashMap<&'static str, ResolvedSig>> = LazyLock::new(|| {
-    let mut map = HashMap::with_capacity_and_hasher(458, FxBuildHasher);
+    let mut map = HashMap::with_capacity_and_hasher(459, FxBuildHasher);
     map.insert(
         "unreachable",
         ResolvedSig {
@@ -18,6 +18,13 @@ pub(crate) static INSTR_SIG: LazyLock<FxHashMap<&'static str, ResolvedSig>> = La
             results: vec![],
         },
     );
+    map.insert(
+        "rethrow",
+        ResolvedSig {
+            params: vec![],
+            results: vec![],
+        },
+    );
     map.insert(
         "throw_ref",
         ResolvedSig {
@@ -2970,7 +2977,7 @@ pub(crate) static INSTR_SIG: LazyLock<FxHashMap<&'static str, ResolvedSig>> = La
 });
 
 pub(crate) static INSTR_OP_CODES: LazyLock<FxHashMap<&'static str, u32>> = LazyLock::new(|| {
-    let mut map = HashMap::with_capacity_and_hasher(577, FxBuildHasher);
+    let mut map = HashMap::with_capacity_and_hasher(578, FxBuildHasher);
     map.insert("unreachable", 0x00);
     map.insert("nop", 0x01);
     map.insert("block", 0x02);
@@ -2978,6 +2985,7 @@ pub(crate) static INSTR_OP_CODES: LazyLock<FxHashMap<&'static str, u32>> = LazyL
     map.insert("if", 0x04);
     map.insert("else", 0x05);
     map.insert("throw", 0x08);
+    map.insert("rethrow", 0x09);
     map.insert("throw_ref", 0x0A);
     map.insert("end", 0x0B);
     map.insert("br", 0x0C);
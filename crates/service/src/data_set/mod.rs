@@ -1,3 +1,4 @@
+use crate::config::Proposal;
 pub(crate) use instr_meta::*;
 
 mod instr_meta;
@@ -620,3 +621,43 @@ pub(crate) fn get_value_type_description(value_type: &str) -> Option<&'static st
 }
 
 pub(crate) static EXTERNS: [&str; 5] = ["func", "table", "memory", "global", "tag"];
+
+/// Get proposals that the instruction comes from.
+///
+/// It returns an empty slice for instructions in WebAssembly 1.0.
+pub(crate) fn get_instr_proposals(name: &str) -> &'static [Proposal] {
+    match name {
+        "i32.extend8_s" | "i32.extend16_s" | "i64.extend8_s" | "i64.extend16_s" | "i64.extend32_s" => {
+            &[Proposal::SignExtension]
+        }
+        "memory.init" | "data.drop" | "memory.copy" | "memory.fill" | "table.init" | "elem.drop" | "table.copy" => {
+            &[Proposal::BulkMemory]
+        }
+        "ref.null" | "ref.is_null" | "ref.func" | "table.get" | "table.set" | "table.grow" | "table.size"
+        | "table.fill" => &[Proposal::ReferenceTypes],
+        "return_call" | "return_call_indirect" => &[Proposal::TailCall],
+        "return_call_ref" => &[Proposal::TailCall, Proposal::FunctionReferences],
+        "call_ref" | "ref.as_non_null" | "br_on_null" | "br_on_non_null" => &[Proposal::FunctionReferences],
+        "ref.eq" | "ref.test" | "ref.cast" | "br_on_cast" | "br_on_cast_fail" | "any.convert_extern"
+        | "extern.convert_any" | "ref.i31" | "i31.get_s" | "i31.get_u" => &[Proposal::Gc],
+        "throw" | "rethrow" | "throw_ref" | "try_table" => &[Proposal::ExceptionHandling],
+        "cont.new" | "cont.bind" | "suspend" | "resume" | "resume_throw" | "resume_throw_ref" | "switch" => {
+            &[Proposal::StackSwitching]
+        }
+        "i64.add128" | "i64.sub128" | "i64.mul_wide_s" | "i64.mul_wide_u" => &[Proposal::WideArithmetic],
+        "atomic.fence" => &[Proposal::Threads],
+        _ if name.starts_with("struct.") || name.starts_with("array.") => &[Proposal::Gc],
+        _ if name.starts_with("i32.trunc_sat_") || name.starts_with("i64.trunc_sat_") => {
+            &[Proposal::NonTrappingFloatToInt]
+        }
+        _ if name.contains(".atomic.") => &[Proposal::Threads],
+        _ if name.contains(".relaxed_") => &[Proposal::RelaxedSimd],
+        _ if ["v128.", "i8x16.", "i16x8.", "i32x4.", "i64x2.", "f32x4.", "f64x2."]
+            .iter()
+            .any(|prefix| name.starts_with(prefix)) =>
+        {
+            &[Proposal::Simd]
+        }
+        _ => &[],
+    }
+}
@@ -0,0 +1,434 @@
+use super::{DecodeError, DecodeResult, Decoder, Space, reader::Reader};
+use crate::{data_set::INSTR_OP_CODES, helpers};
+use rustc_hash::FxHashMap;
+use std::sync::LazyLock;
+
+static OP_CODE_NAMES: LazyLock<FxHashMap<u32, &'static str>> =
+    LazyLock::new(|| INSTR_OP_CODES.iter().map(|(name, op_code)| (*op_code, *name)).collect());
+
+impl Decoder {
+    /// Decode instructions until the `end` of function body.
+    pub(super) fn decode_expr(&mut self, reader: &mut Reader) -> DecodeResult<()> {
+        let depth = self.labels.len();
+        loop {
+            let offset = reader.offset();
+            let op_code = read_op_code(reader)?;
+            match op_code {
+                // end
+                0x0B => {
+                    if self.labels.len() == depth {
+                        return Ok(());
+                    }
+                    self.labels.pop();
+                    self.line("end");
+                }
+                // else
+                0x05 => self.line("else"),
+                // block, loop, if, try_table
+                0x02 | 0x03 | 0x04 | 0x1F => {
+                    self.mark(offset);
+                    let mut text = match op_code {
+                        0x02 => "block",
+                        0x03 => "loop",
+                        0x04 => "if",
+                        _ => "try_table",
+                    }
+                    .to_string();
+                    let label = self
+                        .names
+                        .labels
+                        .get(&self.func_idx)
+                        .and_then(|labels| labels.get(&self.label_count))
+                        .cloned();
+                    self.label_count += 1;
+                    if let Some(label) = &label {
+                        text.push(' ');
+                        text.push_str(label);
+                    }
+                    text.push_str(&self.read_block_type(reader)?);
+                    if op_code == 0x1F {
+                        // catch clauses are resolved outside the block
+                        let count = reader.read_len()?;
+                        for _ in 0..count {
+                            let offset = reader.offset();
+                            let clause = match reader.read_u8()? {
+                                0x00 => {
+                                    let tag = reader.read_u32()?;
+                                    let label = self.read_label(reader)?;
+                                    format!(" (catch {} {label})", self.names.get(Space::Tag, tag))
+                                }
+                                0x01 => {
+                                    let tag = reader.read_u32()?;
+                                    let label = self.read_label(reader)?;
+                                    format!(" (catch_ref {} {label})", self.names.get(Space::Tag, tag))
+                                }
+                                0x02 => format!(" (catch_all {})", self.read_label(reader)?),
+                                0x03 => format!(" (catch_all_ref {})", self.read_label(reader)?),
+                                byte => {
+                                    return Err(DecodeError {
+                                        offset,
+                                        message: format!("unknown catch clause 0x{byte:02X}"),
+                                    });
+                                }
+                            };
+                            text.push_str(&clause);
+                        }
+                    }
+                    self.labels.push(label);
+                    self.line(text);
+                }
+                _ => {
+                    self.mark(offset);
+                    let text = self.decode_plain_instr(reader, op_code, offset)?;
+                    if self.folded {
+                        self.out.push_str(&format!(" ({text})"));
+                    } else {
+                        self.line(text);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Decode constant expression that is terminated by `end`.
+    ///
+    /// Instructions are printed in folded form, so they can be put in one line with module field.
+    pub(super) fn decode_const_expr(&mut self, reader: &mut Reader) -> DecodeResult<()> {
+        self.folded = true;
+        let result = self.decode_expr(reader);
+        self.folded = false;
+        result
+    }
+
+    fn read_block_type(&self, reader: &mut Reader) -> DecodeResult<String> {
+        match reader.peek_u8() {
+            Some(0x40) => {
+                reader.read_u8()?;
+                Ok(String::new())
+            }
+            Some(0x7F | 0x7E | 0x7D | 0x7C | 0x7B | 0x64 | 0x63 | 0x68..=0x75) => {
+                self.read_val_type(reader).map(|ty| format!(" (result {ty})"))
+            }
+            _ => {
+                let offset = reader.offset();
+                let idx = u32::try_from(reader.read_s33()?).map_err(|_| DecodeError {
+                    offset,
+                    message: "malformed block type".into(),
+                })?;
+                Ok(format!(" (type {})", self.names.get(Space::Type, idx)))
+            }
+        }
+    }
+
+    fn read_label(&self, reader: &mut Reader) -> DecodeResult<String> {
+        let depth = reader.read_u32()?;
+        Ok(self
+            .labels
+            .iter()
+            .rev()
+            .nth(depth as usize)
+            .and_then(|label| label.clone())
+            .unwrap_or_else(|| depth.to_string()))
+    }
+
+    fn read_local(&self, reader: &mut Reader) -> DecodeResult<String> {
+        let idx = reader.read_u32()?;
+        Ok(self.locals.get(&idx).cloned().unwrap_or_else(|| idx.to_string()))
+    }
+
+    fn read_idx(&self, reader: &mut Reader, space: Space) -> DecodeResult<String> {
+        reader.read_u32().map(|idx| self.names.get(space, idx))
+    }
+
+    fn read_field(&self, reader: &mut Reader) -> DecodeResult<String> {
+        let ty = reader.read_u32()?;
+        let field = reader.read_u32()?;
+        let field = self
+            .names
+            .fields
+            .get(&ty)
+            .and_then(|fields| fields.get(&field))
+            .cloned()
+            .unwrap_or_else(|| field.to_string());
+        Ok(format!("{} {field}", self.names.get(Space::Type, ty)))
+    }
+
+    fn decode_plain_instr(&self, reader: &mut Reader, op_code: u32, offset: usize) -> DecodeResult<String> {
+        let name = match op_code {
+            0x1C => "select",
+            0xFB15 => "ref.test",
+            0xFB17 => "ref.cast",
+            _ => OP_CODE_NAMES.get(&op_code).copied().ok_or_else(|| DecodeError {
+                offset,
+                message: format!("unknown instruction 0x{op_code:02X}"),
+            })?,
+        };
+        let mut text = name.to_string();
+        let mut push = |immediate: &str| {
+            text.push(' ');
+            text.push_str(immediate);
+        };
+        match name {
+            "br" | "br_if" | "br_on_null" | "br_on_non_null" | "rethrow" => push(&self.read_label(reader)?),
+            "br_table" => {
+                let count = reader.read_len()?;
+                for _ in 0..=count {
+                    push(&self.read_label(reader)?);
+                }
+            }
+            "br_on_cast" | "br_on_cast_fail" => {
+                let flags = reader.read_u8()?;
+                push(&self.read_label(reader)?);
+                for nullable in [flags & 0x01 != 0, flags & 0x02 != 0] {
+                    let heap_ty = self.read_heap_type(reader)?;
+                    if nullable {
+                        push(&format!("(ref null {heap_ty})"));
+                    } else {
+                        push(&format!("(ref {heap_ty})"));
+                    }
+                }
+            }
+            "local.get" | "local.set" | "local.tee" => push(&self.read_local(reader)?),
+            "call" | "return_call" | "ref.func" => push(&self.read_idx(reader, Space::Func)?),
+            "global.get" | "global.set" => push(&self.read_idx(reader, Space::Global)?),
+            "throw" | "suspend" => push(&self.read_idx(reader, Space::Tag)?),
+            "elem.drop" => push(&self.read_idx(reader, Space::Elem)?),
+            "data.drop" => push(&self.read_idx(reader, Space::Data)?),
+            "call_ref" | "return_call_ref" | "struct.new" | "struct.new_default" | "array.new"
+            | "array.new_default" | "array.get" | "array.get_s" | "array.get_u" | "array.set" | "array.fill"
+            | "cont.new" => push(&self.read_idx(reader, Space::Type)?),
+            "struct.get" | "struct.get_s" | "struct.get_u" | "struct.set" => push(&self.read_field(reader)?),
+            "array.new_data" | "array.init_data" => {
+                push(&self.read_idx(reader, Space::Type)?);
+                push(&self.read_idx(reader, Space::Data)?);
+            }
+            "array.new_elem" | "array.init_elem" => {
+                push(&self.read_idx(reader, Space::Type)?);
+                push(&self.read_idx(reader, Space::Elem)?);
+            }
+            "array.copy" | "cont.bind" => {
+                push(&self.read_idx(reader, Space::Type)?);
+                push(&self.read_idx(reader, Space::Type)?);
+            }
+            "switch" => {
+                push(&self.read_idx(reader, Space::Type)?);
+                push(&self.read_idx(reader, Space::Tag)?);
+            }
+            "array.new_fixed" => {
+                push(&self.read_idx(reader, Space::Type)?);
+                push(&reader.read_u32()?.to_string());
+            }
+            "resume" | "resume_throw" | "resume_throw_ref" => {
+                push(&self.read_idx(reader, Space::Type)?);
+                if name == "resume_throw" {
+                    push(&self.read_idx(reader, Space::Tag)?);
+                }
+                let count = reader.read_len()?;
+                for _ in 0..count {
+                    let offset = reader.offset();
+                    match reader.read_u8()? {
+                        0x00 => {
+                            let tag = self.read_idx(reader, Space::Tag)?;
+                            push(&format!("(on {tag} {})", self.read_label(reader)?));
+                        }
+                        0x01 => push(&format!("(on {} switch)", self.read_idx(reader, Space::Tag)?)),
+                        byte => {
+                            return Err(DecodeError {
+                                offset,
+                                message: format!("unknown handler 0x{byte:02X}"),
+                            });
+                        }
+                    }
+                }
+            }
+            "call_indirect" | "return_call_indirect" => {
+                let ty = self.read_idx(reader, Space::Type)?;
+                let table = reader.read_u32()?;
+                if table != 0 {
+                    push(&self.names.get(Space::Table, table));
+                }
+                push(&format!("(type {ty})"));
+            }
+            "select" => {
+                if op_code == 0x1C {
+                    let count = reader.read_len()?;
+                    let mut result = "(result".to_string();
+                    for _ in 0..count {
+                        result.push(' ');
+                        result.push_str(&self.read_val_type(reader)?);
+                    }
+                    result.push(')');
+                    push(&result);
+                }
+            }
+            "table.get" | "table.set" | "table.size" | "table.grow" | "table.fill" => {
+                let table = reader.read_u32()?;
+                if table != 0 {
+                    push(&self.names.get(Space::Table, table));
+                }
+            }
+            "memory.size" | "memory.grow" | "memory.fill" => {
+                let memory = reader.read_u32()?;
+                if memory != 0 {
+                    push(&self.names.get(Space::Memory, memory));
+                }
+            }
+            "table.copy" | "memory.copy" => {
+                let space = if name == "table.copy" {
+                    Space::Table
+                } else {
+                    Space::Memory
+                };
+                let dst = reader.read_u32()?;
+                let src = reader.read_u32()?;
+                if dst != 0 || src != 0 {
+                    push(&self.names.get(space, dst));
+                    push(&self.names.get(space, src));
+                }
+            }
+            "table.init" | "memory.init" => {
+                let (space, segment_space) = if name == "table.init" {
+                    (Space::Table, Space::Elem)
+                } else {
+                    (Space::Memory, Space::Data)
+                };
+                let segment = self.read_idx(reader, segment_space)?;
+                let target = reader.read_u32()?;
+                if target != 0 {
+                    push(&self.names.get(space, target));
+                }
+                push(&segment);
+            }
+            "i32.const" => push(&reader.read_i32()?.to_string()),
+            "i64.const" => push(&reader.read_i64()?.to_string()),
+            "f32.const" => {
+                let bytes = reader.read_bytes(4)?;
+                push(&format_f32(u32::from_le_bytes([
+                    bytes[0], bytes[1], bytes[2], bytes[3],
+                ])));
+            }
+            "f64.const" => {
+                let mut bits = [0; 8];
+                bits.copy_from_slice(reader.read_bytes(8)?);
+                push(&format_f64(u64::from_le_bytes(bits)));
+            }
+            "v128.const" => {
+                push("i32x4");
+                for lane in reader.read_bytes(16)?.chunks_exact(4) {
+                    push(&format!(
+                        "0x{:08x}",
+                        u32::from_le_bytes([lane[0], lane[1], lane[2], lane[3]])
+                    ));
+                }
+            }
+            "i8x16.shuffle" => {
+                for lane in reader.read_bytes(16)? {
+                    push(&lane.to_string());
+                }
+            }
+            "ref.null" => push(&self.read_heap_type(reader)?),
+            "ref.test" | "ref.cast" => {
+                let heap_ty = self.read_heap_type(reader)?;
+                if op_code & 1 == 1 {
+                    push(&format!("(ref null {heap_ty})"));
+                } else {
+                    push(&format!("(ref {heap_ty})"));
+                }
+            }
+            _ if name.ends_with("_lane") || name.ends_with("_lane_s") || name.ends_with("_lane_u") => {
+                if let Some(natural_align) = helpers::natural_align(name) {
+                    self.read_mem_arg(reader, natural_align, &mut push)?;
+                }
+                push(&reader.read_u8()?.to_string());
+            }
+            _ => {
+                if let Some(natural_align) = helpers::natural_align(name) {
+                    self.read_mem_arg(reader, natural_align, &mut push)?;
+                }
+            }
+        }
+        Ok(text)
+    }
+
+    fn read_mem_arg(&self, reader: &mut Reader, natural_align: u32, push: &mut impl FnMut(&str)) -> DecodeResult<()> {
+        let offset = reader.offset();
+        let flags = reader.read_u32()?;
+        if flags & 0x40 != 0 {
+            push(&self.read_idx(reader, Space::Memory)?);
+        }
+        let align = flags & !0x40;
+        if align >= 64 {
+            return Err(DecodeError {
+                offset,
+                message: "malformed memory alignment".into(),
+            });
+        }
+        let mem_offset = reader.read_u64()?;
+        if mem_offset != 0 {
+            push(&format!("offset={mem_offset}"));
+        }
+        if align != natural_align {
+            push(&format!("align={}", 1u64 << align));
+        }
+        Ok(())
+    }
+}
+
+/// Read op code, including the prefix and the LEB128 encoded sub op code as what `INSTR_OP_CODES` does.
+fn read_op_code(reader: &mut Reader) -> DecodeResult<u32> {
+    let offset = reader.offset();
+    let prefix = reader.read_u8()?;
+    if !matches!(prefix, 0xFB..=0xFE) {
+        return Ok(prefix as u32);
+    }
+    let mut op_code = prefix as u32;
+    for _ in 0..3 {
+        let byte = reader.read_u8()?;
+        op_code = op_code << 8 | byte as u32;
+        if byte & 0x80 == 0 {
+            // `atomic.fence` has a reserved byte which is included in its op code
+            if op_code == 0xFE03 {
+                op_code = op_code << 8 | reader.read_u8()? as u32;
+            }
+            return Ok(op_code);
+        }
+    }
+    Err(DecodeError {
+        offset,
+        message: "malformed op code".into(),
+    })
+}
+
+fn format_f32(bits: u32) -> String {
+    let value = f32::from_bits(bits);
+    let sign = if bits >> 31 == 1 { "-" } else { "" };
+    if value.is_nan() {
+        let payload = bits & 0x7F_FFFF;
+        if payload == 0x40_0000 {
+            format!("{sign}nan")
+        } else {
+            format!("{sign}nan:0x{payload:x}")
+        }
+    } else if value.is_infinite() {
+        format!("{sign}inf")
+    } else {
+        format!("{value:?}")
+    }
+}
+
+fn format_f64(bits: u64) -> String {
+    let value = f64::from_bits(bits);
+    let sign = if bits >> 63 == 1 { "-" } else { "" };
+    if value.is_nan() {
+        let payload = bits & 0xF_FFFF_FFFF_FFFF;
+        if payload == 0x8_0000_0000_0000 {
+            format!("{sign}nan")
+        } else {
+            format!("{sign}nan:0x{payload:x}")
+        }
+    } else if value.is_infinite() {
+        format!("{sign}inf")
+    } else {
+        format!("{value:?}")
+    }
+}
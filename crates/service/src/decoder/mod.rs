@@ -0,0 +1,913 @@
+//! Lift WebAssembly binary format to module in text format.
+
+use self::reader::Reader;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+mod instr;
+mod reader;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Error that prevents a binary from being disassembled.
+pub struct DecodeError {
+    /// Byte offset in the binary.
+    pub offset: usize,
+    pub message: String,
+}
+type DecodeResult<T> = Result<T, DecodeError>;
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at offset 0x{:x})", self.message, self.offset)
+    }
+}
+impl std::error::Error for DecodeError {}
+
+/// Unformatted module in text format decoded from binary.
+pub(crate) struct Decoded {
+    pub text: String,
+    /// Byte offsets of type definitions, module fields and instructions,
+    /// in the same order as they appear in text.
+    pub offsets: Vec<u32>,
+}
+
+/// Decode WebAssembly binary to module in text format.
+///
+/// Identifiers are recovered from the name section if present.
+/// The result isn't formatted and it's expected to be formatted by formatter.
+pub(crate) fn decode(binary: &[u8]) -> DecodeResult<Decoded> {
+    let mut reader = Reader::new(binary, 0);
+    if reader.read_bytes(4).ok() != Some(b"\0asm") {
+        return Err(DecodeError {
+            offset: 0,
+            message: "bad magic number".into(),
+        });
+    }
+    if reader.read_bytes(4).ok() != Some(&[1, 0, 0, 0]) {
+        return Err(DecodeError {
+            offset: 4,
+            message: "bad version".into(),
+        });
+    }
+
+    let mut sections = Vec::new();
+    let mut names = Names::default();
+    while !reader.is_empty() {
+        let offset = reader.offset();
+        let id = reader.read_u8()?;
+        let mut section = reader.read_sized()?;
+        if id == 0 {
+            // malformed name section should be ignored as the spec says
+            if section.read_name().is_ok_and(|name| name == "name") {
+                names = decode_names(section);
+            }
+        } else if id <= 13 {
+            sections.push((id, offset, section));
+        } else {
+            return Err(DecodeError {
+                offset,
+                message: format!("unknown section id {id}"),
+            });
+        }
+    }
+
+    let mut decoder = Decoder {
+        out: String::new(),
+        offsets: Vec::new(),
+        names,
+        types: Vec::new(),
+        func_types: Vec::new(),
+        imported_funcs: 0,
+        tables: 0,
+        memories: 0,
+        globals: 0,
+        tags: 0,
+        func_idx: 0,
+        locals: FxHashMap::default(),
+        labels: Vec::new(),
+        label_count: 0,
+        folded: false,
+    };
+    decoder.out.push_str("(module");
+    if let Some(name) = &decoder.names.module {
+        decoder.out.push(' ');
+        decoder.out.push_str(name);
+    }
+    for (id, offset, mut section) in sections {
+        match id {
+            1 => decoder.decode_types(&mut section)?,
+            2 => decoder.decode_imports(&mut section)?,
+            3 => decoder.decode_funcs(&mut section)?,
+            4 => decoder.decode_tables(&mut section)?,
+            5 => decoder.decode_memories(&mut section)?,
+            6 => decoder.decode_globals(&mut section)?,
+            7 => decoder.decode_exports(&mut section)?,
+            8 => {
+                decoder.mark(offset);
+                let func = section.read_u32()?;
+                decoder.line(format!("(start {})", decoder.names.get(Space::Func, func)));
+            }
+            9 => decoder.decode_elems(&mut section)?,
+            10 => decoder.decode_codes(&mut section)?,
+            11 => decoder.decode_datas(&mut section)?,
+            12 => {
+                section.read_u32()?;
+            }
+            _ => decoder.decode_tags(&mut section)?,
+        }
+        if !section.is_empty() {
+            return Err(section.error(format!("section {id} has unexpected trailing bytes")));
+        }
+    }
+    decoder.out.push(')');
+    Ok(Decoded {
+        text: decoder.out,
+        offsets: decoder.offsets,
+    })
+}
+
+struct Decoder {
+    out: String,
+    offsets: Vec<u32>,
+    names: Names,
+    /// Function signatures of defined types, which are used for printing params of functions.
+    types: Vec<Option<FuncType>>,
+    /// Type indices of all functions, including imported functions.
+    func_types: Vec<u32>,
+    imported_funcs: u32,
+    tables: u32,
+    memories: u32,
+    globals: u32,
+    tags: u32,
+    /// Index of current function.
+    func_idx: u32,
+    /// Local names of current function.
+    locals: FxHashMap<u32, String>,
+    /// Label identifiers of current function from outermost to innermost.
+    labels: Vec<Option<String>>,
+    /// Number of labels that have been seen in current function, which is used by label names.
+    label_count: u32,
+    /// Whether instructions should be printed in folded form.
+    folded: bool,
+}
+
+struct FuncType {
+    params: Vec<String>,
+    results: Vec<String>,
+}
+
+impl Decoder {
+    /// Record byte offset of the node that will be printed next.
+    fn mark(&mut self, offset: usize) {
+        self.offsets.push(offset as u32);
+    }
+
+    fn line(&mut self, text: impl AsRef<str>) {
+        self.out.push('\n');
+        self.out.push_str(text.as_ref());
+    }
+
+    fn decode_types(&mut self, reader: &mut Reader) -> DecodeResult<()> {
+        let count = reader.read_len()?;
+        for _ in 0..count {
+            if reader.peek_u8() == Some(0x4E) {
+                reader.read_u8()?;
+                self.line("(rec");
+                let count = reader.read_len()?;
+                for _ in 0..count {
+                    self.decode_sub_type(reader)?;
+                }
+                self.out.push(')');
+            } else {
+                self.decode_sub_type(reader)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn decode_sub_type(&mut self, reader: &mut Reader) -> DecodeResult<()> {
+        let idx = self.types.len() as u32;
+        self.mark(reader.offset());
+        self.line(format!("(type{}", self.names.def(Space::Type, idx)));
+        let sub = match reader.peek_u8() {
+            Some(byte @ (0x50 | 0x4F)) => {
+                reader.read_u8()?;
+                let mut text = if byte == 0x50 {
+                    " (sub".to_string()
+                } else {
+                    " (sub final".to_string()
+                };
+                let count = reader.read_len()?;
+                for _ in 0..count {
+                    let super_type = reader.read_u32()?;
+                    text.push(' ');
+                    text.push_str(&self.names.get(Space::Type, super_type));
+                }
+                self.out.push_str(&text);
+                true
+            }
+            _ => false,
+        };
+        let func_type = self.decode_comp_type(reader, idx)?;
+        self.types.push(func_type);
+        if sub {
+            self.out.push(')');
+        }
+        self.out.push(')');
+        Ok(())
+    }
+
+    fn decode_comp_type(&mut self, reader: &mut Reader, idx: u32) -> DecodeResult<Option<FuncType>> {
+        let offset = reader.offset();
+        match reader.read_u8()? {
+            0x60 => {
+                let count = reader.read_len()?;
+                let params = (0..count)
+                    .map(|_| self.read_val_type(reader))
+                    .collect::<DecodeResult<Vec<_>>>()?;
+                let count = reader.read_len()?;
+                let results = (0..count)
+                    .map(|_| self.read_val_type(reader))
+                    .collect::<DecodeResult<Vec<_>>>()?;
+                let func_type = FuncType { params, results };
+                self.out.push_str(" (func");
+                self.out.push_str(&sig_text(&func_type, |_| None));
+                self.out.push(')');
+                Ok(Some(func_type))
+            }
+            0x5F => {
+                self.out.push_str(" (struct");
+                let count = reader.read_len()?;
+                for field in 0..count {
+                    let ty = self.read_field_type(reader)?;
+                    self.out.push_str(" (field");
+                    if let Some(name) = self.names.fields.get(&idx).and_then(|fields| fields.get(&field)) {
+                        self.out.push(' ');
+                        self.out.push_str(name);
+                    }
+                    self.out.push(' ');
+                    self.out.push_str(&ty);
+                    self.out.push(')');
+                }
+                self.out.push(')');
+                Ok(None)
+            }
+            0x5E => {
+                let ty = self.read_field_type(reader)?;
+                self.out.push_str(&format!(" (array {ty})"));
+                Ok(None)
+            }
+            0x5D => {
+                let func_type = reader.read_u32()?;
+                self.out
+                    .push_str(&format!(" (cont {})", self.names.get(Space::Type, func_type)));
+                Ok(None)
+            }
+            byte => Err(DecodeError {
+                offset,
+                message: format!("unknown composite type 0x{byte:02X}"),
+            }),
+        }
+    }
+
+    fn read_field_type(&self, reader: &mut Reader) -> DecodeResult<String> {
+        let ty = match reader.peek_u8() {
+            Some(0x78) => {
+                reader.read_u8()?;
+                "i8".to_string()
+            }
+            Some(0x77) => {
+                reader.read_u8()?;
+                "i16".to_string()
+            }
+            _ => self.read_val_type(reader)?,
+        };
+        match reader.read_u8()? {
+            0 => Ok(ty),
+            1 => Ok(format!("(mut {ty})")),
+            _ => Err(reader.error("malformed mutability")),
+        }
+    }
+
+    fn read_val_type(&self, reader: &mut Reader) -> DecodeResult<String> {
+        let offset = reader.offset();
+        match reader.peek_u8() {
+            Some(0x7F) => reader.read_u8().map(|_| "i32".into()),
+            Some(0x7E) => reader.read_u8().map(|_| "i64".into()),
+            Some(0x7D) => reader.read_u8().map(|_| "f32".into()),
+            Some(0x7C) => reader.read_u8().map(|_| "f64".into()),
+            Some(0x7B) => reader.read_u8().map(|_| "v128".into()),
+            _ => self.read_ref_type(reader).map_err(|_| DecodeError {
+                offset,
+                message: "malformed value type".into(),
+            }),
+        }
+    }
+
+    fn read_ref_type(&self, reader: &mut Reader) -> DecodeResult<String> {
+        match reader.peek_u8() {
+            Some(byte @ (0x64 | 0x63)) => {
+                reader.read_u8()?;
+                let heap_ty = self.read_heap_type(reader)?;
+                if byte == 0x64 {
+                    Ok(format!("(ref {heap_ty})"))
+                } else {
+                    Ok(format!("(ref null {heap_ty})"))
+                }
+            }
+            Some(byte) => {
+                let heap_ty = abstract_heap_type(byte).ok_or_else(|| reader.error("malformed reference type"))?;
+                reader.read_u8()?;
+                Ok(match heap_ty {
+                    "none" => "nullref".into(),
+                    "noextern" => "nullexternref".into(),
+                    "nofunc" => "nullfuncref".into(),
+                    "noexn" => "nullexnref".into(),
+                    "nocont" => "nullcontref".into(),
+                    _ => format!("{heap_ty}ref"),
+                })
+            }
+            None => Err(reader.error("unexpected end of binary")),
+        }
+    }
+
+    fn read_heap_type(&self, reader: &mut Reader) -> DecodeResult<String> {
+        if let Some(heap_ty) = reader.peek_u8().and_then(abstract_heap_type) {
+            reader.read_u8()?;
+            return Ok(heap_ty.into());
+        }
+        let offset = reader.offset();
+        let idx = reader.read_s33()?;
+        u32::try_from(idx)
+            .map(|idx| self.names.get(Space::Type, idx))
+            .map_err(|_| DecodeError {
+                offset,
+                message: "malformed heap type".into(),
+            })
+    }
+
+    /// Print type use with inline params and results,
+    /// so it's more readable and parameter names can be attached.
+    fn type_use_text(&self, type_idx: u32, locals: Option<&FxHashMap<u32, String>>) -> String {
+        let mut text = format!(" (type {})", self.names.get(Space::Type, type_idx));
+        if let Some(Some(func_type)) = self.types.get(type_idx as usize) {
+            text.push_str(&sig_text(func_type, |i| locals.and_then(|locals| locals.get(&i))));
+        }
+        text
+    }
+
+    fn decode_imports(&mut self, reader: &mut Reader) -> DecodeResult<()> {
+        let count = reader.read_len()?;
+        for _ in 0..count {
+            self.mark(reader.offset());
+            let module = reader.read_name()?;
+            let name = reader.read_name()?;
+            let offset = reader.offset();
+            let desc = match reader.read_u8()? {
+                0x00 => {
+                    let type_idx = reader.read_u32()?;
+                    let idx = self.func_types.len() as u32;
+                    self.func_types.push(type_idx);
+                    self.imported_funcs += 1;
+                    let locals = self.names.locals.get(&idx);
+                    format!(
+                        "(func{}{})",
+                        self.names.def(Space::Func, idx),
+                        self.type_use_text(type_idx, locals)
+                    )
+                }
+                0x01 => {
+                    let ty = self.read_table_type(reader)?;
+                    self.tables += 1;
+                    format!("(table{} {ty})", self.names.def(Space::Table, self.tables - 1))
+                }
+                0x02 => {
+                    let ty = self.read_mem_type(reader)?;
+                    self.memories += 1;
+                    format!("(memory{} {ty})", self.names.def(Space::Memory, self.memories - 1))
+                }
+                0x03 => {
+                    let ty = self.read_global_type(reader)?;
+                    self.globals += 1;
+                    format!("(global{} {ty})", self.names.def(Space::Global, self.globals - 1))
+                }
+                0x04 => {
+                    let ty = self.read_tag_type(reader)?;
+                    self.tags += 1;
+                    format!("(tag{}{ty})", self.names.def(Space::Tag, self.tags - 1))
+                }
+                byte => {
+                    return Err(DecodeError {
+                        offset,
+                        message: format!("unknown import kind 0x{byte:02X}"),
+                    });
+                }
+            };
+            self.line(format!(
+                "(import {} {} {desc})",
+                string(module.as_bytes()),
+                string(name.as_bytes())
+            ));
+        }
+        Ok(())
+    }
+
+    fn decode_funcs(&mut self, reader: &mut Reader) -> DecodeResult<()> {
+        let count = reader.read_len()?;
+        for _ in 0..count {
+            let type_idx = reader.read_u32()?;
+            self.func_types.push(type_idx);
+        }
+        Ok(())
+    }
+
+    fn decode_tables(&mut self, reader: &mut Reader) -> DecodeResult<()> {
+        let count = reader.read_len()?;
+        for _ in 0..count {
+            self.mark(reader.offset());
+            let def = self.names.def(Space::Table, self.tables);
+            self.tables += 1;
+            if reader.peek_u8() == Some(0x40) {
+                reader.read_u8()?;
+                if reader.read_u8()? != 0 {
+                    return Err(reader.error("malformed table"));
+                }
+                let ty = self.read_table_type(reader)?;
+                self.line(format!("(table{def} {ty}"));
+                self.decode_const_expr(reader)?;
+                self.out.push(')');
+            } else {
+                let ty = self.read_table_type(reader)?;
+                self.line(format!("(table{def} {ty})"));
+            }
+        }
+        Ok(())
+    }
+
+    fn read_table_type(&self, reader: &mut Reader) -> DecodeResult<String> {
+        let ref_ty = self.read_ref_type(reader)?;
+        let (limits, _) = read_limits(reader)?;
+        Ok(format!("{limits} {ref_ty}"))
+    }
+
+    fn decode_memories(&mut self, reader: &mut Reader) -> DecodeResult<()> {
+        let count = reader.read_len()?;
+        for _ in 0..count {
+            self.mark(reader.offset());
+            let ty = self.read_mem_type(reader)?;
+            self.line(format!("(memory{} {ty})", self.names.def(Space::Memory, self.memories)));
+            self.memories += 1;
+        }
+        Ok(())
+    }
+
+    fn read_mem_type(&self, reader: &mut Reader) -> DecodeResult<String> {
+        let (mut text, flags) = read_limits(reader)?;
+        if flags & 0x02 != 0 {
+            text.push_str(" shared");
+        }
+        if flags & 0x08 != 0 {
+            let page_size = reader.read_u32()?;
+            if page_size >= 32 {
+                return Err(reader.error("malformed page size"));
+            }
+            text.push_str(&format!(" (pagesize {})", 1u32 << page_size));
+        }
+        Ok(text)
+    }
+
+    fn read_global_type(&self, reader: &mut Reader) -> DecodeResult<String> {
+        let ty = self.read_val_type(reader)?;
+        match reader.read_u8()? {
+            0 => Ok(ty),
+            1 => Ok(format!("(mut {ty})")),
+            _ => Err(reader.error("malformed mutability")),
+        }
+    }
+
+    fn read_tag_type(&self, reader: &mut Reader) -> DecodeResult<String> {
+        if reader.read_u8()? != 0 {
+            return Err(reader.error("malformed tag attribute"));
+        }
+        let type_idx = reader.read_u32()?;
+        Ok(self.type_use_text(type_idx, None))
+    }
+
+    fn decode_globals(&mut self, reader: &mut Reader) -> DecodeResult<()> {
+        let count = reader.read_len()?;
+        for _ in 0..count {
+            self.mark(reader.offset());
+            let ty = self.read_global_type(reader)?;
+            self.line(format!("(global{} {ty}", self.names.def(Space::Global, self.globals)));
+            self.globals += 1;
+            self.decode_const_expr(reader)?;
+            self.out.push(')');
+        }
+        Ok(())
+    }
+
+    fn decode_tags(&mut self, reader: &mut Reader) -> DecodeResult<()> {
+        let count = reader.read_len()?;
+        for _ in 0..count {
+            self.mark(reader.offset());
+            let ty = self.read_tag_type(reader)?;
+            self.line(format!("(tag{}{ty})", self.names.def(Space::Tag, self.tags)));
+            self.tags += 1;
+        }
+        Ok(())
+    }
+
+    fn decode_exports(&mut self, reader: &mut Reader) -> DecodeResult<()> {
+        let count = reader.read_len()?;
+        for _ in 0..count {
+            self.mark(reader.offset());
+            let name = reader.read_name()?;
+            let offset = reader.offset();
+            let (keyword, space) = match reader.read_u8()? {
+                0x00 => ("func", Space::Func),
+                0x01 => ("table", Space::Table),
+                0x02 => ("memory", Space::Memory),
+                0x03 => ("global", Space::Global),
+                0x04 => ("tag", Space::Tag),
+                byte => {
+                    return Err(DecodeError {
+                        offset,
+                        message: format!("unknown export kind 0x{byte:02X}"),
+                    });
+                }
+            };
+            let idx = reader.read_u32()?;
+            self.line(format!(
+                "(export {} ({keyword} {}))",
+                string(name.as_bytes()),
+                self.names.get(space, idx)
+            ));
+        }
+        Ok(())
+    }
+
+    fn decode_elems(&mut self, reader: &mut Reader) -> DecodeResult<()> {
+        let count = reader.read_len()?;
+        for idx in 0..count {
+            self.mark(reader.offset());
+            self.line(format!("(elem{}", self.names.def(Space::Elem, idx)));
+            let flags = reader.read_u32()?;
+            if flags > 7 {
+                return Err(reader.error(format!("malformed element segment flags {flags}")));
+            }
+            match flags & 0b011 {
+                // active
+                0b000 | 0b010 => {
+                    if flags & 0b010 != 0 {
+                        let table = reader.read_u32()?;
+                        self.out
+                            .push_str(&format!(" (table {})", self.names.get(Space::Table, table)));
+                    }
+                    self.out.push_str(" (offset");
+                    self.decode_const_expr(reader)?;
+                    self.out.push(')');
+                }
+                0b011 => self.out.push_str(" declare"),
+                _ => {}
+            }
+            let uses_exprs = flags & 0b100 != 0;
+            // flags 0 and 4 don't have element kind or reference type
+            if flags & 0b011 != 0 {
+                if uses_exprs {
+                    let ty = self.read_ref_type(reader)?;
+                    self.out.push(' ');
+                    self.out.push_str(&ty);
+                } else if reader.read_u8()? != 0 {
+                    return Err(reader.error("malformed element kind"));
+                }
+            } else if uses_exprs {
+                self.out.push_str(" funcref");
+            }
+            let count = reader.read_len()?;
+            if uses_exprs {
+                for _ in 0..count {
+                    self.out.push_str(" (item");
+                    self.decode_const_expr(reader)?;
+                    self.out.push(')');
+                }
+            } else {
+                self.out.push_str(" func");
+                for _ in 0..count {
+                    let func = reader.read_u32()?;
+                    self.out.push(' ');
+                    self.out.push_str(&self.names.get(Space::Func, func));
+                }
+            }
+            self.out.push(')');
+        }
+        Ok(())
+    }
+
+    fn decode_codes(&mut self, reader: &mut Reader) -> DecodeResult<()> {
+        let count = reader.read_len()?;
+        for i in 0..count {
+            let offset = reader.offset();
+            let mut body = reader.read_sized()?;
+            let func_idx = self.imported_funcs + i;
+            let type_idx = *self.func_types.get(func_idx as usize).ok_or_else(|| DecodeError {
+                offset,
+                message: "function and code section have inconsistent lengths".into(),
+            })?;
+            self.func_idx = func_idx;
+            self.locals = self.names.locals.get(&func_idx).cloned().unwrap_or_default();
+            self.labels.clear();
+            self.label_count = 0;
+
+            self.mark(offset);
+            self.line(format!(
+                "(func{}{}",
+                self.names.def(Space::Func, func_idx),
+                self.type_use_text(type_idx, Some(&self.locals))
+            ));
+            let mut local_idx = match self.types.get(type_idx as usize) {
+                Some(Some(func_type)) => func_type.params.len() as u32,
+                _ => 0,
+            };
+            let count = body.read_len()?;
+            for _ in 0..count {
+                let offset = body.offset();
+                let n = body.read_u32()?;
+                let ty = self.read_val_type(&mut body)?;
+                if local_idx.checked_add(n).is_none_or(|count| count > 50000) {
+                    return Err(DecodeError {
+                        offset,
+                        message: "too many locals".into(),
+                    });
+                }
+                for _ in 0..n {
+                    if let Some(name) = self.locals.get(&local_idx) {
+                        self.line(format!("(local {name} {ty})"));
+                    } else {
+                        self.line(format!("(local {ty})"));
+                    }
+                    local_idx += 1;
+                }
+            }
+            self.decode_expr(&mut body)?;
+            if !body.is_empty() {
+                return Err(body.error("function body has unexpected trailing bytes"));
+            }
+            self.out.push(')');
+        }
+        Ok(())
+    }
+
+    fn decode_datas(&mut self, reader: &mut Reader) -> DecodeResult<()> {
+        let count = reader.read_len()?;
+        for idx in 0..count {
+            self.mark(reader.offset());
+            self.line(format!("(data{}", self.names.def(Space::Data, idx)));
+            match reader.read_u32()? {
+                0 => {
+                    self.out.push_str(" (offset");
+                    self.decode_const_expr(reader)?;
+                    self.out.push(')');
+                }
+                1 => {}
+                2 => {
+                    let memory = reader.read_u32()?;
+                    self.out
+                        .push_str(&format!(" (memory {})", self.names.get(Space::Memory, memory)));
+                    self.out.push_str(" (offset");
+                    self.decode_const_expr(reader)?;
+                    self.out.push(')');
+                }
+                flags => return Err(reader.error(format!("malformed data segment flags {flags}"))),
+            }
+            let len = reader.read_u32()?;
+            let bytes = reader.read_bytes(len as usize)?;
+            self.out.push(' ');
+            self.out.push_str(&string(bytes));
+            self.out.push(')');
+        }
+        Ok(())
+    }
+}
+
+fn sig_text<'a>(func_type: &FuncType, param_name: impl Fn(u32) -> Option<&'a String>) -> String {
+    let mut text = String::new();
+    for (i, param) in func_type.params.iter().enumerate() {
+        if let Some(name) = param_name(i as u32) {
+            text.push_str(&format!(" (param {name} {param})"));
+        } else {
+            text.push_str(&format!(" (param {param})"));
+        }
+    }
+    if !func_type.results.is_empty() {
+        text.push_str(" (result");
+        for result in &func_type.results {
+            text.push(' ');
+            text.push_str(result);
+        }
+        text.push(')');
+    }
+    text
+}
+
+/// Read limits and return its text and flags.
+fn read_limits(reader: &mut Reader) -> DecodeResult<(String, u8)> {
+    let flags = reader.read_u8()?;
+    if flags & !0x0F != 0 {
+        return Err(reader.error(format!("malformed limits flags 0x{flags:02X}")));
+    }
+    let is_64 = flags & 0x04 != 0;
+    let mut text = if is_64 { "i64 ".to_string() } else { String::new() };
+    let min = if is_64 {
+        reader.read_u64()?
+    } else {
+        reader.read_u32()? as u64
+    };
+    text.push_str(&min.to_string());
+    if flags & 0x01 != 0 {
+        let max = if is_64 {
+            reader.read_u64()?
+        } else {
+            reader.read_u32()? as u64
+        };
+        text.push(' ');
+        text.push_str(&max.to_string());
+    }
+    Ok((text, flags))
+}
+
+fn abstract_heap_type(byte: u8) -> Option<&'static str> {
+    match byte {
+        0x70 => Some("func"),
+        0x6F => Some("extern"),
+        0x6E => Some("any"),
+        0x6D => Some("eq"),
+        0x6C => Some("i31"),
+        0x6B => Some("struct"),
+        0x6A => Some("array"),
+        0x71 => Some("none"),
+        0x72 => Some("noextern"),
+        0x73 => Some("nofunc"),
+        0x69 => Some("exn"),
+        0x74 => Some("noexn"),
+        0x68 => Some("cont"),
+        0x75 => Some("nocont"),
+        _ => None,
+    }
+}
+
+/// Print bytes as string literal.
+fn string(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len() + 2);
+    text.push('"');
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '"' => text.push_str("\\\""),
+                '\\' => text.push_str("\\\\"),
+                '\t' => text.push_str("\\t"),
+                '\n' => text.push_str("\\n"),
+                '\r' => text.push_str("\\r"),
+                c if c.is_control() => {
+                    let mut buf = [0; 4];
+                    c.encode_utf8(&mut buf)
+                        .bytes()
+                        .for_each(|byte| text.push_str(&format!("\\{byte:02x}")));
+                }
+                c => text.push(c),
+            }
+        }
+        chunk
+            .invalid()
+            .iter()
+            .for_each(|byte| text.push_str(&format!("\\{byte:02x}")));
+    }
+    text.push('"');
+    text
+}
+
+#[derive(Clone, Copy)]
+enum Space {
+    Func,
+    Table,
+    Memory,
+    Global,
+    Type,
+    Elem,
+    Data,
+    Tag,
+}
+
+#[derive(Default)]
+/// Identifiers recovered from the name section.
+struct Names {
+    module: Option<String>,
+    funcs: FxHashMap<u32, String>,
+    locals: FxHashMap<u32, FxHashMap<u32, String>>,
+    labels: FxHashMap<u32, FxHashMap<u32, String>>,
+    types: FxHashMap<u32, String>,
+    tables: FxHashMap<u32, String>,
+    memories: FxHashMap<u32, String>,
+    globals: FxHashMap<u32, String>,
+    elems: FxHashMap<u32, String>,
+    datas: FxHashMap<u32, String>,
+    fields: FxHashMap<u32, FxHashMap<u32, String>>,
+    tags: FxHashMap<u32, String>,
+}
+
+impl Names {
+    fn map(&self, space: Space) -> &FxHashMap<u32, String> {
+        match space {
+            Space::Func => &self.funcs,
+            Space::Table => &self.tables,
+            Space::Memory => &self.memories,
+            Space::Global => &self.globals,
+            Space::Type => &self.types,
+            Space::Elem => &self.elems,
+            Space::Data => &self.datas,
+            Space::Tag => &self.tags,
+        }
+    }
+
+    /// Get identifier for reference, or fall back to numeric index.
+    fn get(&self, space: Space, idx: u32) -> String {
+        self.map(space).get(&idx).cloned().unwrap_or_else(|| idx.to_string())
+    }
+
+    /// Get identifier and index comment for definition.
+    fn def(&self, space: Space, idx: u32) -> String {
+        if let Some(name) = self.map(space).get(&idx) {
+            format!(" {name} (;{idx};)")
+        } else {
+            format!(" (;{idx};)")
+        }
+    }
+}
+
+fn decode_names(mut reader: Reader) -> Names {
+    let mut names = Names::default();
+    while !reader.is_empty() {
+        let Ok(id) = reader.read_u8() else {
+            break;
+        };
+        let Ok(mut subsection) = reader.read_sized() else {
+            break;
+        };
+        let reader = &mut subsection;
+        // malformed subsection is ignored
+        let _ = match id {
+            0 => reader.read_name().map(|name| names.module = ident(name)),
+            1 => read_name_map(reader).map(|map| names.funcs = map),
+            2 => read_indirect_name_map(reader).map(|map| names.locals = map),
+            3 => read_indirect_name_map(reader).map(|map| names.labels = map),
+            4 => read_name_map(reader).map(|map| names.types = map),
+            5 => read_name_map(reader).map(|map| names.tables = map),
+            6 => read_name_map(reader).map(|map| names.memories = map),
+            7 => read_name_map(reader).map(|map| names.globals = map),
+            8 => read_name_map(reader).map(|map| names.elems = map),
+            9 => read_name_map(reader).map(|map| names.datas = map),
+            10 => read_indirect_name_map(reader).map(|map| names.fields = map),
+            11 => read_name_map(reader).map(|map| names.tags = map),
+            _ => Ok(()),
+        };
+    }
+    names
+}
+
+/// Read name map and convert names to identifiers.
+///
+/// Names that can't be identifiers or are duplicated will be dropped,
+/// so those items will be referenced by numeric indices.
+fn read_name_map(reader: &mut Reader) -> DecodeResult<FxHashMap<u32, String>> {
+    let count = reader.read_len()?;
+    let mut map = FxHashMap::default();
+    let mut seen = FxHashSet::default();
+    for _ in 0..count {
+        let idx = reader.read_u32()?;
+        let name = reader.read_name()?;
+        if let Some(ident) = ident(name)
+            && seen.insert(ident.clone())
+        {
+            map.insert(idx, ident);
+        }
+    }
+    Ok(map)
+}
+
+fn read_indirect_name_map(reader: &mut Reader) -> DecodeResult<FxHashMap<u32, FxHashMap<u32, String>>> {
+    let count = reader.read_len()?;
+    let mut map = FxHashMap::default();
+    for _ in 0..count {
+        let idx = reader.read_u32()?;
+        map.insert(idx, read_name_map(reader)?);
+    }
+    Ok(map)
+}
+
+fn ident(name: &str) -> Option<String> {
+    if name.is_empty() {
+        None
+    } else if name.chars().all(wat_parser::is_id_char) {
+        Some(format!("${name}"))
+    } else {
+        Some(format!("${}", string(name.as_bytes())))
+    }
+}
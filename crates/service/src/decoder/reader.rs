@@ -0,0 +1,140 @@
+use super::{DecodeError, DecodeResult};
+
+/// Cursor over binary bytes, keeping track of offset for error reporting and offset mapping.
+pub(super) struct Reader<'a> {
+    bytes: &'a [u8],
+    /// Offset of the first byte in the whole binary.
+    base: usize,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8], base: usize) -> Self {
+        Self { bytes, base, pos: 0 }
+    }
+
+    /// Offset in the whole binary.
+    pub fn offset(&self) -> usize {
+        self.base + self.pos
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> DecodeError {
+        DecodeError {
+            offset: self.offset(),
+            message: message.into(),
+        }
+    }
+
+    pub fn read_u8(&mut self) -> DecodeResult<u8> {
+        let byte = *self
+            .bytes
+            .get(self.pos)
+            .ok_or_else(|| self.error("unexpected end of binary"))?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    pub fn peek_u8(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> DecodeResult<&'a [u8]> {
+        let bytes = self
+            .bytes
+            .get(self.pos..self.pos.saturating_add(len))
+            .ok_or_else(|| self.error("unexpected end of binary"))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    /// Split out a sub reader with length-prefixed bytes, such as section content.
+    pub fn read_sized(&mut self) -> DecodeResult<Reader<'a>> {
+        let len = self.read_u32()? as usize;
+        let base = self.offset();
+        self.read_bytes(len).map(|bytes| Reader::new(bytes, base))
+    }
+
+    pub fn read_u32(&mut self) -> DecodeResult<u32> {
+        let value = self.read_unsigned(32)?;
+        Ok(value as u32)
+    }
+
+    pub fn read_u64(&mut self) -> DecodeResult<u64> {
+        self.read_unsigned(64)
+    }
+
+    fn read_unsigned(&mut self, bits: u32) -> DecodeResult<u64> {
+        let mut value = 0u64;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            // unused bits of the last byte must be zero
+            if shift >= bits || bits - shift < 7 && (byte & 0x7f) >> (bits - shift) != 0 {
+                return Err(self.error("integer too large"));
+            }
+            value |= ((byte & 0x7f) as u64) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+
+    pub fn read_i32(&mut self) -> DecodeResult<i32> {
+        let value = self.read_signed(32)?;
+        Ok(value as i32)
+    }
+
+    pub fn read_i64(&mut self) -> DecodeResult<i64> {
+        self.read_signed(64)
+    }
+
+    pub fn read_s33(&mut self) -> DecodeResult<i64> {
+        self.read_signed(33)
+    }
+
+    fn read_signed(&mut self, bits: u32) -> DecodeResult<i64> {
+        let mut value = 0i64;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            if shift >= bits {
+                return Err(self.error("integer too large"));
+            }
+            value |= ((byte & 0x7f) as i64) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                if shift < 64 && byte & 0x40 != 0 {
+                    value |= -1 << shift;
+                }
+                return Ok(value);
+            }
+        }
+    }
+
+    pub fn read_name(&mut self) -> DecodeResult<&'a str> {
+        let len = self.read_u32()? as usize;
+        let offset = self.offset();
+        let bytes = self.read_bytes(len)?;
+        std::str::from_utf8(bytes).map_err(|_| DecodeError {
+            offset,
+            message: "malformed UTF-8 encoding".into(),
+        })
+    }
+
+    /// Read length of vector.
+    ///
+    /// Each item takes at least one byte, so this can reject bogus length before allocating.
+    pub fn read_len(&mut self) -> DecodeResult<u32> {
+        let len = self.read_u32()?;
+        if len as usize > self.bytes.len() - self.pos {
+            Err(self.error("length out of bounds"))
+        } else {
+            Ok(len)
+        }
+    }
+}
@@ -5,7 +5,7 @@ use wat_syntax::{SyntaxKind, SyntaxNode};
 #[salsa::tracked]
 pub(crate) fn get_deprecation(db: &dyn salsa::Database, document: Document) -> FxHashMap<SymbolKey, Option<String>> {
     let root = SyntaxNode::new_root(document.root(db));
-    root.children()
+    root.children_by_kind(SyntaxKind::MODULE)
         .flat_map(|module| module.children())
         .filter(|node| {
             matches!(
@@ -22,13 +22,13 @@ pub(crate) fn get_deprecation(db: &dyn salsa::Database, document: Document) -> F
             )
         })
         .chain(
-            root.children()
+            root.children_by_kind(SyntaxKind::MODULE)
                 .flat_map(|module| module.children())
                 .filter(|node| node.kind() == SyntaxKind::REC_TYPE)
                 .flat_map(|node| node.children()),
         )
         .chain(
-            root.children()
+            root.children_by_kind(SyntaxKind::MODULE)
                 .flat_map(|module| module.children())
                 .filter(|node| node.kind() == SyntaxKind::MODULE_FIELD_IMPORT)
                 .flat_map(|node| node.children())
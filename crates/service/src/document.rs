@@ -1,11 +1,21 @@
-use crate::{LanguageService, config::ConfigState, helpers::LineIndexExt};
+use crate::{
+    LanguageService,
+    config::ConfigState,
+    decoder::{self, DecodeError},
+    helpers::LineIndexExt,
+};
 use line_index::LineIndex;
 use lspt::{
     DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams, TextDocumentContentChangeEvent,
 };
 use salsa::Setter;
+use std::sync::Arc;
 use std::{cmp::Ordering, ops::Range};
-use wat_syntax::{GreenNode, SyntaxKind, SyntaxNode, TextRange, TextSize};
+use wat_formatter::config::FormatOptions;
+use wat_syntax::{
+    GreenNode, SyntaxKind, SyntaxNode, TextRange, TextSize,
+    ast::{AstNode, Instr, ModuleField},
+};
 
 #[salsa::input(debug)]
 pub(crate) struct Document {
@@ -19,6 +29,7 @@ impl LanguageService {
     #[inline]
     /// Commit a document to the service.
     pub fn commit(&mut self, uri: String, text: String) {
+        self.binary_offsets.write().remove(&uri);
         let line_index = LineIndex::new(&text);
         let (green, errors) = wat_parser::parse(&text);
         if let Some(document) = self.get_document(&uri) {
@@ -36,8 +47,66 @@ impl LanguageService {
         };
     }
 
+    /// Disassemble WebAssembly binary and commit the text as a read-only document.
+    ///
+    /// The text is formatted with global format config, and it will be returned if succeeded.
+    /// Byte offsets in the binary are kept, so they can be mapped back to ranges in the document later.
+    pub fn disassemble(&mut self, uri: String, binary: &[u8]) -> Result<String, DecodeError> {
+        let decoded = decoder::decode(binary)?;
+        let (green, _) = wat_parser::parse(&decoded.text);
+        let text = wat_formatter::format(
+            &green,
+            &FormatOptions {
+                language: self.global_config.format.clone(),
+                ..Default::default()
+            },
+        );
+        self.commit(uri.clone(), text.clone());
+        if let Some(document) = self.get_document(&uri) {
+            // formatter only changes whitespaces and comments,
+            // so nodes are in the same order as they're printed by decoder
+            let mut offsets = decoded
+                .offsets
+                .into_iter()
+                .zip(
+                    SyntaxNode::new_root(document.root(self))
+                        .descendants()
+                        .filter(|node| {
+                            let kind = node.kind();
+                            ModuleField::can_cast(kind) && kind != SyntaxKind::REC_TYPE || Instr::can_cast(kind)
+                        })
+                        .map(|node| node.text_range()),
+                )
+                .collect::<Vec<_>>();
+            offsets.sort_by_key(|(offset, _)| *offset);
+            self.binary_offsets.write().insert(uri, offsets.into());
+        }
+        Ok(text)
+    }
+
+    /// Check if the document is disassembled from binary, which shouldn't be edited.
+    pub(crate) fn is_read_only(&self, uri: &str) -> bool {
+        self.binary_offsets.read().contains_key(uri)
+    }
+
+    /// Find the range of module field or instruction which the byte offset in binary belongs to.
+    ///
+    /// Bytes like `end` of block are considered as part of the last instruction before them.
+    pub(crate) fn resolve_binary_offset(&self, uri: &str, offset: u32) -> Option<TextRange> {
+        let binary_offsets = Arc::clone(self.binary_offsets.read().get(uri)?);
+        let i = binary_offsets.partition_point(|(start, _)| *start <= offset);
+        binary_offsets.get(i.checked_sub(1)?).map(|(_, range)| *range)
+    }
+
     /// Handler for `textDocument/didOpen` notification.
     pub fn did_open(&mut self, params: DidOpenTextDocumentParams) {
+        // client may open the disassembled document with the same text
+        if self
+            .get_document(&params.text_document.uri)
+            .is_some_and(|document| document.text(self) != &params.text_document.text)
+        {
+            self.binary_offsets.write().remove(&params.text_document.uri);
+        }
         let line_index = LineIndex::new(&params.text_document.text);
         let (green, errors) = wat_parser::parse(&params.text_document.text);
         self.documents.write().insert(
@@ -57,6 +126,8 @@ impl LanguageService {
         let Some(document) = self.get_document(uri) else {
             return;
         };
+        // edited document no longer matches the binary
+        self.binary_offsets.write().remove(uri);
         'single: {
             // only do incremental parsing for single change
             if let [TextDocumentContentChangeEvent::Partial(partial)] = &*params.content_changes {
@@ -159,6 +230,7 @@ impl LanguageService {
     pub fn did_close(&mut self, params: DidCloseTextDocumentParams) {
         self.documents.write().remove(&params.text_document.uri);
         self.configs.write().remove(&params.text_document.uri);
+        self.binary_offsets.write().remove(&params.text_document.uri);
     }
 
     #[inline]
@@ -179,6 +251,11 @@ fn find_deepest_parseable_node(root: SyntaxNode, range: TextRange) -> SyntaxNode
         && range.end() < it.text_range().end()
     {
         node = it;
+        if node.kind() == SyntaxKind::ASSERT_RETURN {
+            // NaN patterns in expected results can't be parsed without the whole assertion
+            last_parseable = node;
+            break;
+        }
         let parseable = match node.kind() {
             SyntaxKind::MODULE_NAME
             | SyntaxKind::NAME
@@ -199,6 +276,7 @@ fn find_deepest_parseable_node(root: SyntaxNode, range: TextRange) -> SyntaxNode
             | SyntaxKind::BLOCK_BLOCK
             | SyntaxKind::BLOCK_LOOP
             | SyntaxKind::BLOCK_IF
+            | SyntaxKind::BLOCK_TRY
             | SyntaxKind::BLOCK_TRY_TABLE
             | SyntaxKind::CATCH
             | SyntaxKind::CATCH_ALL
@@ -223,6 +301,8 @@ fn find_deepest_parseable_node(root: SyntaxNode, range: TextRange) -> SyntaxNode
             | SyntaxKind::TABLE_USE
             | SyntaxKind::DATA
             | SyntaxKind::MODULE
+            | SyntaxKind::MODULE_BINARY
+            | SyntaxKind::MODULE_QUOTE
             | SyntaxKind::MODULE_FIELD_DATA
             | SyntaxKind::MODULE_FIELD_ELEM
             | SyntaxKind::MODULE_FIELD_EXPORT
@@ -235,6 +315,14 @@ fn find_deepest_parseable_node(root: SyntaxNode, range: TextRange) -> SyntaxNode
             | SyntaxKind::MODULE_FIELD_TAG
             | SyntaxKind::TYPE_DEF
             | SyntaxKind::REC_TYPE
+            | SyntaxKind::ACTION_GET
+            | SyntaxKind::ACTION_INVOKE
+            | SyntaxKind::ASSERT_EXHAUSTION
+            | SyntaxKind::ASSERT_INVALID
+            | SyntaxKind::ASSERT_MALFORMED
+            | SyntaxKind::ASSERT_TRAP
+            | SyntaxKind::ASSERT_UNLINKABLE
+            | SyntaxKind::REGISTER
             | SyntaxKind::ROOT => true,
             SyntaxKind::PLAIN_INSTR => {
                 // avoid code like `(param)` being parsed as unknown plain instr
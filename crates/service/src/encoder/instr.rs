@@ -0,0 +1,567 @@
+use super::{
+    EncodeError, EncodeResult, Encoder, InstrCode,
+    num::{parse_f32, parse_f64, parse_int, write_i64, write_u32, write_u64},
+};
+use crate::{
+    binder::SymbolKey,
+    data_set::INSTR_OP_CODES,
+    helpers,
+    idx::Idx,
+    types_analyzer::{HeapType, RefType, Sig},
+};
+use smallvec::SmallVec;
+use wat_syntax::{
+    AmberNode, AmberToken, SyntaxKind,
+    ast::{AstNode, Instr, ValType as AstValType},
+};
+
+impl Encoder<'_> {
+    pub(super) fn encode_instr(&mut self, out: &mut Vec<u8>, instr: AmberNode) -> EncodeResult<()> {
+        match instr.kind() {
+            SyntaxKind::PLAIN_INSTR => {
+                // folded operands
+                for child in instr.children_by_kind(Instr::can_cast) {
+                    self.encode_instr(out, child)?;
+                }
+                let start = out.len();
+                self.encode_plain_instr(out, instr)?;
+                self.record_instr(instr, start, out.len());
+            }
+            SyntaxKind::BLOCK_BLOCK | SyntaxKind::BLOCK_LOOP => {
+                let start = out.len();
+                out.push(if instr.kind() == SyntaxKind::BLOCK_BLOCK {
+                    0x02
+                } else {
+                    0x03
+                });
+                self.encode_block_type(out, instr)?;
+                self.record_instr(instr, start, out.len());
+                self.encode_block_body(out, instr)?;
+            }
+            SyntaxKind::BLOCK_IF => {
+                // folded condition
+                for child in instr.children_by_kind(Instr::can_cast) {
+                    self.encode_instr(out, child)?;
+                }
+                let start = out.len();
+                out.push(0x04);
+                self.encode_block_type(out, instr)?;
+                self.record_instr(instr, start, out.len());
+                self.labels.push(instr.into());
+                if let Some(then_block) = instr.children_by_kind(SyntaxKind::BLOCK_IF_THEN).next() {
+                    for child in then_block.children_by_kind(Instr::can_cast) {
+                        self.encode_instr(out, child)?;
+                    }
+                }
+                if let Some(else_block) = instr.children_by_kind(SyntaxKind::BLOCK_IF_ELSE).next() {
+                    out.push(0x05);
+                    self.record_instr(instr, out.len() - 1, out.len());
+                    for child in else_block.children_by_kind(Instr::can_cast) {
+                        self.encode_instr(out, child)?;
+                    }
+                }
+                self.labels.pop();
+                out.push(0x0B);
+                self.record_instr(instr, out.len() - 1, out.len());
+            }
+            SyntaxKind::BLOCK_TRY => {
+                let start = out.len();
+                out.push(0x06);
+                self.encode_block_type(out, instr)?;
+                self.record_instr(instr, start, out.len());
+                self.labels.push(instr.into());
+                if let Some(do_block) = instr.children_by_kind(SyntaxKind::BLOCK_TRY_DO).next() {
+                    for child in do_block.children_by_kind(Instr::can_cast) {
+                        self.encode_instr(out, child)?;
+                    }
+                }
+                for handler in instr.children_by_kind([SyntaxKind::BLOCK_TRY_CATCH, SyntaxKind::BLOCK_TRY_CATCH_ALL]) {
+                    let start = out.len();
+                    if handler.kind() == SyntaxKind::BLOCK_TRY_CATCH {
+                        out.push(0x07);
+                        let tag = expect(handler.children_by_kind(SyntaxKind::INDEX).next(), handler, "tag index")?;
+                        write_u32(out, self.resolve_idx(tag)?);
+                    } else {
+                        out.push(0x19);
+                    }
+                    self.record_instr(instr, start, out.len());
+                    for child in handler.children_by_kind(Instr::can_cast) {
+                        self.encode_instr(out, child)?;
+                    }
+                }
+                self.labels.pop();
+                if let Some(delegate) = instr.children_by_kind(SyntaxKind::BLOCK_TRY_DELEGATE).next() {
+                    // label of `delegate` is resolved outside the `try` block
+                    let start = out.len();
+                    out.push(0x18);
+                    let label = expect(
+                        delegate.children_by_kind(SyntaxKind::INDEX).next(),
+                        delegate,
+                        "label index",
+                    )?;
+                    write_u32(out, self.resolve_label(label)?);
+                    self.record_instr(instr, start, out.len());
+                } else {
+                    out.push(0x0B);
+                    self.record_instr(instr, out.len() - 1, out.len());
+                }
+            }
+            SyntaxKind::BLOCK_TRY_TABLE => {
+                let start = out.len();
+                out.push(0x1F);
+                self.encode_block_type(out, instr)?;
+                let catches = instr
+                    .children_by_kind([SyntaxKind::CATCH, SyntaxKind::CATCH_ALL])
+                    .collect::<SmallVec<[_; 2]>>();
+                write_u32(out, catches.len() as u32);
+                for catch in catches {
+                    let mut indexes = catch.children_by_kind(SyntaxKind::INDEX);
+                    let keyword = catch.tokens_by_kind(SyntaxKind::KEYWORD).next();
+                    match keyword.map(|token| token.text()) {
+                        Some("catch") => out.push(0x00),
+                        Some("catch_ref") => out.push(0x01),
+                        Some("catch_all") => out.push(0x02),
+                        _ => out.push(0x03),
+                    }
+                    if catch.kind() == SyntaxKind::CATCH {
+                        let tag = expect(indexes.next(), catch, "tag index")?;
+                        write_u32(out, self.resolve_idx(tag)?);
+                    }
+                    let label = expect(indexes.next(), catch, "label index")?;
+                    write_u32(out, self.resolve_label(label)?);
+                }
+                self.record_instr(instr, start, out.len());
+                self.encode_block_body(out, instr)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn encode_block_body(&mut self, out: &mut Vec<u8>, block: AmberNode) -> EncodeResult<()> {
+        self.labels.push(block.into());
+        for child in block.children_by_kind(Instr::can_cast) {
+            self.encode_instr(out, child)?;
+        }
+        self.labels.pop();
+        out.push(0x0B);
+        self.record_instr(block, out.len() - 1, out.len());
+        Ok(())
+    }
+
+    /// Record encoded bytes of instruction in `out[start..end]` if encoding function body.
+    fn record_instr(&mut self, instr: AmberNode, start: usize, end: usize) {
+        if let Some(instrs) = &mut self.instrs {
+            instrs.push(InstrCode {
+                range: instr.text_range(),
+                offset: start as u32,
+                size: (end - start) as u32,
+            });
+        }
+    }
+
+    fn encode_block_type(&mut self, out: &mut Vec<u8>, block: AmberNode) -> EncodeResult<()> {
+        let Some(type_use) = block.children_by_kind(SyntaxKind::TYPE_USE).next() else {
+            out.push(0x40);
+            return Ok(());
+        };
+        if type_use.children_by_kind(SyntaxKind::INDEX).next().is_none() {
+            let sig = Sig::from_type_use(self.db, self.document, type_use);
+            if sig.params.is_empty() {
+                match &*sig.results {
+                    [] => {
+                        out.push(0x40);
+                        return Ok(());
+                    }
+                    [ty] => return self.encode_val_type(out, ty, type_use.text_range()),
+                    _ => {}
+                }
+            }
+        }
+        let idx = self.encode_type_use(Some(type_use))?;
+        write_i64(out, idx as i64);
+        Ok(())
+    }
+
+    fn encode_plain_instr(&mut self, out: &mut Vec<u8>, instr: AmberNode) -> EncodeResult<()> {
+        let name_token = instr
+            .tokens_by_kind(SyntaxKind::INSTR_NAME)
+            .next()
+            .ok_or_else(|| EncodeError {
+                range: instr.text_range(),
+                message: "missing instruction name".into(),
+            })?;
+        let name = name_token.text();
+        let immediates = instr
+            .children_by_kind(SyntaxKind::IMMEDIATE)
+            .collect::<SmallVec<[_; 4]>>();
+        let op_code = match name {
+            "select" if !immediates.is_empty() => Some(0x1C),
+            "ref.test" | "ref.cast" => INSTR_OP_CODES.get(name).map(|op_code| {
+                if self.immediate_ref_type(&immediates, 0).is_some_and(|ty| ty.nullable) {
+                    op_code + 1
+                } else {
+                    *op_code
+                }
+            }),
+            _ => INSTR_OP_CODES.get(name).copied(),
+        };
+        let Some(op_code) = op_code else {
+            return Err(EncodeError {
+                range: name_token.text_range(),
+                message: format!("unknown instruction `{name}`"),
+            });
+        };
+        let bytes = op_code.to_be_bytes();
+        let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len() - 1);
+        out.extend_from_slice(&bytes[start..]);
+
+        let immediate = |i: usize| expect(immediates.get(i).copied(), instr, "immediate");
+        match name {
+            "br" | "br_if" | "br_on_null" | "br_on_non_null" | "rethrow" => {
+                write_u32(out, self.resolve_label(immediate(0)?)?);
+            }
+            "br_table" => {
+                let (default, labels) = immediates.split_last().ok_or_else(|| EncodeError {
+                    range: instr.text_range(),
+                    message: "missing immediate".into(),
+                })?;
+                write_u32(out, labels.len() as u32);
+                for label in labels {
+                    write_u32(out, self.resolve_label(*label)?);
+                }
+                write_u32(out, self.resolve_label(*default)?);
+            }
+            "br_on_cast" | "br_on_cast_fail" => {
+                let label = self.resolve_label(immediate(0)?)?;
+                let from = self.immediate_ref_type(&immediates, 1);
+                let to = self.immediate_ref_type(&immediates, 2);
+                let (Some(from), Some(to)) = (from, to) else {
+                    return Err(EncodeError {
+                        range: instr.text_range(),
+                        message: "missing reference type".into(),
+                    });
+                };
+                out.push(from.nullable as u8 | (to.nullable as u8) << 1);
+                write_u32(out, label);
+                self.encode_heap_type(out, &from.heap_ty, immediates[1].text_range())?;
+                self.encode_heap_type(out, &to.heap_ty, immediates[2].text_range())?;
+            }
+            "call" | "return_call" | "ref.func" | "local.get" | "local.set" | "local.tee" | "global.get"
+            | "global.set" | "call_ref" | "return_call_ref" | "throw" | "elem.drop" | "data.drop" | "struct.new"
+            | "struct.new_default" | "array.new" | "array.new_default" | "array.get" | "array.get_s"
+            | "array.get_u" | "array.set" | "array.fill" | "cont.new" | "suspend" => {
+                write_u32(out, self.resolve_idx(immediate(0)?)?);
+            }
+            "struct.get" | "struct.get_s" | "struct.get_u" | "struct.set" | "array.new_data" | "array.init_data"
+            | "array.new_elem" | "array.init_elem" | "array.copy" | "cont.bind" | "switch" => {
+                write_u32(out, self.resolve_idx(immediate(0)?)?);
+                write_u32(out, self.resolve_idx(immediate(1)?)?);
+            }
+            "array.new_fixed" => {
+                write_u32(out, self.resolve_idx(immediate(0)?)?);
+                write_u32(out, immediate_u32(immediate(1)?)?);
+            }
+            "resume" | "resume_throw" | "resume_throw_ref" => {
+                write_u32(out, self.resolve_idx(immediate(0)?)?);
+                let mut rest = &immediates[1..];
+                if name == "resume_throw" {
+                    write_u32(out, self.resolve_idx(immediate(1)?)?);
+                    rest = &immediates[2..];
+                }
+                let handlers = rest
+                    .iter()
+                    .filter_map(|immediate| immediate.children_by_kind(SyntaxKind::ON_CLAUSE).next())
+                    .collect::<SmallVec<[_; 2]>>();
+                write_u32(out, handlers.len() as u32);
+                for handler in handlers {
+                    let mut indexes = handler.children_by_kind(SyntaxKind::INDEX);
+                    let tag = expect(indexes.next(), handler, "tag index")?;
+                    if let Some(label) = indexes.next() {
+                        out.push(0x00);
+                        write_u32(out, self.resolve_idx(tag)?);
+                        write_u32(out, self.resolve_label(label)?);
+                    } else {
+                        out.push(0x01);
+                        write_u32(out, self.resolve_idx(tag)?);
+                    }
+                }
+            }
+            "call_indirect" | "return_call_indirect" => {
+                let type_use = immediates
+                    .iter()
+                    .find_map(|immediate| immediate.children_by_kind(SyntaxKind::TYPE_USE).next());
+                let type_idx = self.encode_type_use(type_use)?;
+                write_u32(out, type_idx);
+                let table = immediates
+                    .iter()
+                    .find(|immediate| is_idx_immediate(**immediate))
+                    .map(|immediate| self.resolve_idx(*immediate))
+                    .transpose()?;
+                write_u32(out, table.unwrap_or_default());
+            }
+            "select" => {
+                if let Some(type_use) = immediates
+                    .first()
+                    .and_then(|immediate| immediate.children_by_kind(SyntaxKind::TYPE_USE).next())
+                {
+                    let types = type_use
+                        .children_by_kind(SyntaxKind::RESULT)
+                        .flat_map(|result| result.children_by_kind(AstValType::can_cast))
+                        .collect::<SmallVec<[_; 1]>>();
+                    write_u32(out, types.len() as u32);
+                    for ty in types {
+                        self.encode_val_type_node(out, ty)?;
+                    }
+                }
+            }
+            "table.get" | "table.set" | "table.size" | "table.grow" | "table.fill" | "memory.size" | "memory.grow"
+            | "memory.fill" | "memory.discard" => {
+                write_u32(out, self.optional_idx(&immediates, 0)?);
+            }
+            "table.copy" | "memory.copy" => {
+                write_u32(out, self.optional_idx(&immediates, 0)?);
+                write_u32(out, self.optional_idx(&immediates, 1)?);
+            }
+            "table.init" | "memory.init" => {
+                if let [target, segment] = &*immediates {
+                    write_u32(out, self.resolve_idx(*segment)?);
+                    write_u32(out, self.resolve_idx(*target)?);
+                } else {
+                    write_u32(out, self.resolve_idx(immediate(0)?)?);
+                    out.push(0);
+                }
+            }
+            "i32.const" => {
+                let token = immediate_token(immediate(0)?)?;
+                let value = parse_int(token.text(), 32).ok_or_else(|| invalid_literal(token))?;
+                write_i64(out, value as u32 as i32 as i64);
+            }
+            "i64.const" => {
+                let token = immediate_token(immediate(0)?)?;
+                let value = parse_int(token.text(), 64).ok_or_else(|| invalid_literal(token))?;
+                write_i64(out, value as i64);
+            }
+            "f32.const" => {
+                let token = immediate_token(immediate(0)?)?;
+                let value = parse_f32(token.text()).ok_or_else(|| invalid_literal(token))?;
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+            "f64.const" => {
+                let token = immediate_token(immediate(0)?)?;
+                let value = parse_f64(token.text()).ok_or_else(|| invalid_literal(token))?;
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+            "v128.const" => {
+                let shape = immediate(0)?
+                    .tokens_by_kind(SyntaxKind::SHAPE_DESCRIPTOR)
+                    .next()
+                    .ok_or_else(|| EncodeError {
+                        range: immediates[0].text_range(),
+                        message: "missing shape descriptor".into(),
+                    })?;
+                let lanes = immediates[1..]
+                    .iter()
+                    .map(|immediate| immediate_token(*immediate))
+                    .collect::<EncodeResult<SmallVec<[_; 16]>>>()?;
+                let (count, width) = match shape.text() {
+                    "i8x16" => (16, 1),
+                    "i16x8" => (8, 2),
+                    "i32x4" | "f32x4" => (4, 4),
+                    _ => (2, 8),
+                };
+                if lanes.len() != count {
+                    return Err(EncodeError {
+                        range: instr.text_range(),
+                        message: format!("expected {count} lanes, found {}", lanes.len()),
+                    });
+                }
+                for lane in lanes {
+                    let value = match shape.text() {
+                        "f32x4" => parse_f32(lane.text()).map(u64::from),
+                        "f64x2" => parse_f64(lane.text()),
+                        _ => parse_int(lane.text(), width * 8),
+                    }
+                    .ok_or_else(|| invalid_literal(lane))?;
+                    out.extend_from_slice(&value.to_le_bytes()[..width as usize]);
+                }
+            }
+            "i8x16.shuffle" => {
+                if immediates.len() != 16 {
+                    return Err(EncodeError {
+                        range: instr.text_range(),
+                        message: format!("expected 16 lane indices, found {}", immediates.len()),
+                    });
+                }
+                for immediate in &immediates {
+                    out.push(immediate_lane(*immediate)?);
+                }
+            }
+            "ref.null" => {
+                let immediate = immediate(0)?;
+                let heap_ty = immediate
+                    .children_by_kind(SyntaxKind::HEAP_TYPE)
+                    .next()
+                    .and_then(|heap_ty| HeapType::from_green(heap_ty.green(), self.db))
+                    .or_else(|| Idx::from_green_for_ref(immediate.green(), self.db).map(HeapType::Type))
+                    .ok_or_else(|| EncodeError {
+                        range: immediate.text_range(),
+                        message: "invalid heap type".into(),
+                    })?;
+                self.encode_heap_type(out, &heap_ty, immediate.text_range())?;
+            }
+            "ref.test" | "ref.cast" => {
+                let immediate = immediate(0)?;
+                let ty = self.immediate_ref_type(&immediates, 0).ok_or_else(|| EncodeError {
+                    range: immediate.text_range(),
+                    message: "invalid reference type".into(),
+                })?;
+                self.encode_heap_type(out, &ty.heap_ty, immediate.text_range())?;
+            }
+            _ if name.ends_with("_lane") || name.ends_with("_lane_s") || name.ends_with("_lane_u") => {
+                if let Some(natural_align) = helpers::natural_align(name) {
+                    let (lane, rest) = immediates.split_last().ok_or_else(|| EncodeError {
+                        range: instr.text_range(),
+                        message: "missing lane index".into(),
+                    })?;
+                    self.encode_mem_arg(out, rest, natural_align)?;
+                    out.push(immediate_lane(*lane)?);
+                } else {
+                    out.push(immediate_lane(immediate(0)?)?);
+                }
+            }
+            _ => {
+                if let Some(natural_align) = helpers::natural_align(name) {
+                    self.encode_mem_arg(out, &immediates, natural_align)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn encode_mem_arg(&self, out: &mut Vec<u8>, immediates: &[AmberNode], natural_align: u32) -> EncodeResult<()> {
+        let mem_idx = immediates
+            .iter()
+            .find(|immediate| is_idx_immediate(**immediate))
+            .map(|immediate| self.resolve_idx(*immediate))
+            .transpose()?
+            .unwrap_or_default();
+        let mut offset = 0;
+        let mut align = natural_align;
+        for mem_arg in immediates
+            .iter()
+            .filter_map(|immediate| immediate.children_by_kind(SyntaxKind::MEM_ARG).next())
+        {
+            let Some(value) = mem_arg.tokens_by_kind(SyntaxKind::UNSIGNED_INT).next() else {
+                continue;
+            };
+            match mem_arg
+                .tokens_by_kind(SyntaxKind::MEM_ARG_KEYWORD)
+                .next()
+                .map(|token| token.text())
+            {
+                Some("offset") => {
+                    offset = helpers::parse_u64(value.text()).map_err(|_| invalid_literal(value))?;
+                }
+                Some("align") => {
+                    align = helpers::parse_u32(value.text())
+                        .ok()
+                        .filter(|align| align.is_power_of_two())
+                        .ok_or_else(|| invalid_literal(value))?
+                        .trailing_zeros();
+                }
+                _ => {}
+            }
+        }
+        if mem_idx == 0 {
+            write_u32(out, align);
+        } else {
+            write_u32(out, align | 0x40);
+            write_u32(out, mem_idx);
+        }
+        write_u64(out, offset);
+        Ok(())
+    }
+
+    /// Resolve label index, which is relative to current block.
+    fn resolve_label(&self, node: AmberNode) -> EncodeResult<u32> {
+        let token = node
+            .tokens_by_kind([SyntaxKind::IDENT, SyntaxKind::INT, SyntaxKind::UNSIGNED_INT])
+            .next()
+            .ok_or_else(|| EncodeError {
+                range: node.text_range(),
+                message: "missing label".into(),
+            })?;
+        if token.kind() != SyntaxKind::IDENT {
+            return helpers::parse_u32(token.text()).map_err(|_| invalid_literal(token));
+        }
+        self.symbol_table
+            .find_def(SymbolKey::from(node))
+            .and_then(|symbol| self.labels.iter().rev().position(|label| *label == symbol.key))
+            .map(|depth| depth as u32)
+            .ok_or_else(|| EncodeError {
+                range: token.text_range(),
+                message: format!("cannot find label `{}`", token.text()),
+            })
+    }
+
+    fn optional_idx(&self, immediates: &[AmberNode], i: usize) -> EncodeResult<u32> {
+        immediates
+            .get(i)
+            .map(|immediate| self.resolve_idx(*immediate))
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    fn immediate_ref_type(&self, immediates: &[AmberNode], i: usize) -> Option<RefType<'_>> {
+        immediates
+            .get(i)
+            .and_then(|immediate| immediate.children_by_kind(SyntaxKind::REF_TYPE).next())
+            .and_then(|ref_type| RefType::from_green(ref_type.green(), self.db))
+    }
+}
+
+/// Check if the immediate is an index, instead of other immediates like memory argument or type use.
+fn is_idx_immediate(immediate: AmberNode) -> bool {
+    immediate
+        .tokens_by_kind([SyntaxKind::IDENT, SyntaxKind::INT, SyntaxKind::UNSIGNED_INT])
+        .next()
+        .is_some()
+}
+
+fn immediate_token(immediate: AmberNode) -> EncodeResult<AmberToken> {
+    immediate
+        .tokens_by_kind([SyntaxKind::INT, SyntaxKind::UNSIGNED_INT, SyntaxKind::FLOAT])
+        .next()
+        .ok_or_else(|| EncodeError {
+            range: immediate.text_range(),
+            message: "expected number".into(),
+        })
+}
+
+fn immediate_u32(immediate: AmberNode) -> EncodeResult<u32> {
+    let token = immediate_token(immediate)?;
+    helpers::parse_u32(token.text()).map_err(|_| invalid_literal(token))
+}
+
+fn immediate_lane(immediate: AmberNode) -> EncodeResult<u8> {
+    let token = immediate_token(immediate)?;
+    helpers::parse_u32(token.text())
+        .ok()
+        .and_then(|lane| u8::try_from(lane).ok())
+        .ok_or_else(|| invalid_literal(token))
+}
+
+fn invalid_literal(token: AmberToken) -> EncodeError {
+    EncodeError {
+        range: token.text_range(),
+        message: format!("invalid literal `{}`", token.text()),
+    }
+}
+
+fn expect<'a>(node: Option<AmberNode<'a>>, parent: AmberNode, name: &str) -> EncodeResult<AmberNode<'a>> {
+    node.ok_or_else(|| EncodeError {
+        range: parent.text_range(),
+        message: format!("missing {name}"),
+    })
+}
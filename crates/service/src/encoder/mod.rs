@@ -0,0 +1,1215 @@
+//! Lower module in text format to WebAssembly binary format.
+
+use self::num::{write_bytes, write_i64, write_u32, write_u64};
+use crate::{
+    binder::{SymbolKey, SymbolKind, SymbolTable},
+    document::Document,
+    helpers,
+    idx::Idx,
+    types_analyzer::{
+        CompositeType, DefTypes, FieldType, HeapType, RefType, Sig, StorageType, ValType, get_def_types,
+        get_rec_type_groups,
+    },
+};
+use rustc_hash::FxHashMap;
+use wat_syntax::{
+    AmberNode, SyntaxKind, TextRange,
+    ast::{AstNode, Instr, ValType as AstValType},
+};
+
+mod instr;
+pub(crate) mod num;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Error that prevents a module from being encoded.
+pub(crate) struct EncodeError {
+    pub range: TextRange,
+    pub message: String,
+}
+type EncodeResult<T> = Result<T, EncodeError>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Module in binary format, with layout of function bodies.
+pub(crate) struct Encoded {
+    pub bytes: Vec<u8>,
+    pub funcs: Vec<FuncCode>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Layout of function body in code section.
+pub(crate) struct FuncCode {
+    pub idx: u32,
+    pub range: TextRange,
+    /// Offset of function body (starting from locals declaration) in the whole binary.
+    pub offset: u32,
+    pub size: u32,
+    /// Sorted by offset.
+    pub instrs: Vec<InstrCode>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Encoded bytes of instruction itself, excluding folded operands and instructions in block body.
+///
+/// Blocks have multiple entries: block header, `else` and `end`.
+pub(crate) struct InstrCode {
+    pub range: TextRange,
+    /// Offset relative to the start of function body.
+    pub offset: u32,
+    pub size: u32,
+}
+
+impl FuncCode {
+    /// Find the instruction that covers the given offset relative to the start of function body.
+    pub(crate) fn instr_at(&self, offset: u32) -> Option<&InstrCode> {
+        let i = self.instrs.partition_point(|instr| instr.offset <= offset);
+        self.instrs
+            .get(i.checked_sub(1)?)
+            .filter(|instr| offset < instr.offset + instr.size)
+    }
+}
+
+#[salsa::tracked]
+/// Get layout of function bodies for each module in the document.
+///
+/// Modules that can't be encoded are `None`.
+pub(crate) fn get_code_layouts(db: &dyn salsa::Database, document: Document) -> Box<[Option<Vec<FuncCode>>]> {
+    let root = AmberNode::new_root(document.root(db));
+    (0..root.children_by_kind(SyntaxKind::MODULE).count() as u32)
+        .map(|module_id| encode(db, document, module_id).ok().map(|encoded| encoded.funcs))
+        .collect()
+}
+
+/// Encode the module with given ID in the document to WebAssembly binary format.
+///
+/// Index resolution relies on the symbol table, so it won't repeat validation done by checkers.
+/// However, any syntax error or unresolved reference will stop encoding.
+pub(crate) fn encode(db: &dyn salsa::Database, document: Document, module_id: u32) -> EncodeResult<Encoded> {
+    let root = AmberNode::new_root(document.root(db));
+    let module = root
+        .children_by_kind(SyntaxKind::MODULE)
+        .nth(module_id as usize)
+        .ok_or_else(|| EncodeError {
+            range: root.text_range(),
+            message: "module not found".into(),
+        })?;
+    // errors like missing closing parens are reported after the module node,
+    // so everything before the next module is considered as part of this module
+    let end = root
+        .children_by_kind(SyntaxKind::MODULE)
+        .nth(module_id as usize + 1)
+        .map_or(root.text_range().end(), |next| next.text_range().start());
+    if let Some(error) = document
+        .syntax_errors(db)
+        .iter()
+        .find(|error| error.range.start() >= module.text_range().start() && error.range.start() <= end)
+    {
+        return Err(EncodeError {
+            range: error.range,
+            message: format!("syntax error: {}", error.message),
+        });
+    }
+
+    let symbol_table = SymbolTable::of(db, document);
+    let mut encoder = Encoder {
+        db,
+        document,
+        symbol_table,
+        def_types: get_def_types(db, document),
+        module,
+        module_key: module.into(),
+        type_count: 0,
+        reusable_types: Vec::new(),
+        implicit_types: Vec::new(),
+        elems: FxHashMap::default(),
+        datas: FxHashMap::default(),
+        locals: FxHashMap::default(),
+        labels: Vec::new(),
+        instrs: None,
+        funcs: Vec::new(),
+    };
+    let bytes = encoder.encode_module()?;
+    Ok(Encoded {
+        bytes,
+        funcs: encoder.funcs,
+    })
+}
+
+struct Encoder<'db> {
+    db: &'db dyn salsa::Database,
+    document: Document,
+    symbol_table: &'db SymbolTable<'db>,
+    def_types: &'db DefTypes<'db>,
+    module: AmberNode<'db>,
+    module_key: SymbolKey,
+    /// Number of explicitly defined types.
+    type_count: u32,
+    /// Encoded standalone final function types which can be reused by inline type uses.
+    reusable_types: Vec<(Vec<u8>, u32)>,
+    /// Encoded function types implicitly defined by inline type uses.
+    implicit_types: Vec<Vec<u8>>,
+    /// Element segments in binary format also include declarative segments
+    /// and segments from inline table elements, which aren't counted by binder.
+    elems: FxHashMap<SymbolKey, u32>,
+    /// Data segments in binary format also include segments from inline memory data.
+    datas: FxHashMap<SymbolKey, u32>,
+    /// Local indices of current function, since locals after params from type definition can't be counted by binder.
+    locals: FxHashMap<SymbolKey, u32>,
+    /// Blocks of current function from outermost to innermost.
+    labels: Vec<SymbolKey>,
+    /// Instructions of current function body, only recorded when encoding code section.
+    instrs: Option<Vec<InstrCode>>,
+    /// Function bodies with offsets relative to the code section content, fixed up after all sections are written.
+    funcs: Vec<FuncCode>,
+}
+
+#[derive(Default)]
+struct Section {
+    count: u32,
+    bytes: Vec<u8>,
+}
+
+impl Encoder<'_> {
+    fn encode_module(&mut self) -> EncodeResult<Vec<u8>> {
+        let mut types = self.encode_types()?;
+        self.collect_segments();
+
+        let mut imports = Section::default();
+        let mut funcs = Section::default();
+        let mut tables = Section::default();
+        let mut memories = Section::default();
+        let mut globals = Section::default();
+        let mut exports = Section::default();
+        let mut start = None;
+        let mut elems = Section::default();
+        let mut codes = Section::default();
+        let mut datas = Section::default();
+        let mut tags = Section::default();
+        for field in self.module.children() {
+            match field.kind() {
+                SyntaxKind::MODULE_FIELD_IMPORT => {
+                    let module_name = field.children_by_kind(SyntaxKind::MODULE_NAME).next();
+                    let extern_type = field.children_by_kind(is_extern_type).next();
+                    if field.children_by_kind(SyntaxKind::NAME).next().is_some() {
+                        self.encode_import(&mut imports, module_name, field, extern_type)?;
+                    }
+                    for item in field.children_by_kind(SyntaxKind::IMPORT_ITEM) {
+                        let extern_type = item.children_by_kind(is_extern_type).next().or(extern_type);
+                        self.encode_import(&mut imports, module_name, item, extern_type)?;
+                    }
+                }
+                SyntaxKind::MODULE_FIELD_FUNC => {
+                    if let Some(import) = field.children_by_kind(SyntaxKind::IMPORT).next() {
+                        let module_name = import.children_by_kind(SyntaxKind::MODULE_NAME).next();
+                        self.encode_import(&mut imports, module_name, import, Some(field))?;
+                    } else {
+                        let type_idx = self.encode_type_use(field.children_by_kind(SyntaxKind::TYPE_USE).next())?;
+                        write_u32(&mut funcs.bytes, type_idx);
+                        funcs.count += 1;
+                        self.encode_code(&mut codes, field)?;
+                    }
+                    self.encode_inline_exports(&mut exports, field, 0x00)?;
+                }
+                SyntaxKind::MODULE_FIELD_TABLE => {
+                    if let Some(import) = field.children_by_kind(SyntaxKind::IMPORT).next() {
+                        let module_name = import.children_by_kind(SyntaxKind::MODULE_NAME).next();
+                        self.encode_import(&mut imports, module_name, import, Some(field))?;
+                    } else {
+                        self.encode_table(&mut tables, &mut elems, field)?;
+                    }
+                    self.encode_inline_exports(&mut exports, field, 0x01)?;
+                }
+                SyntaxKind::MODULE_FIELD_MEMORY => {
+                    if let Some(import) = field.children_by_kind(SyntaxKind::IMPORT).next() {
+                        let module_name = import.children_by_kind(SyntaxKind::MODULE_NAME).next();
+                        self.encode_import(&mut imports, module_name, import, Some(field))?;
+                    } else {
+                        self.encode_memory(&mut memories, &mut datas, field)?;
+                    }
+                    self.encode_inline_exports(&mut exports, field, 0x02)?;
+                }
+                SyntaxKind::MODULE_FIELD_GLOBAL => {
+                    if let Some(import) = field.children_by_kind(SyntaxKind::IMPORT).next() {
+                        let module_name = import.children_by_kind(SyntaxKind::MODULE_NAME).next();
+                        self.encode_import(&mut imports, module_name, import, Some(field))?;
+                    } else {
+                        let global_type = self.expect_child(field, SyntaxKind::GLOBAL_TYPE, "global type")?;
+                        self.encode_global_type(&mut globals.bytes, global_type)?;
+                        self.encode_expr(&mut globals.bytes, field)?;
+                        globals.count += 1;
+                    }
+                    self.encode_inline_exports(&mut exports, field, 0x03)?;
+                }
+                SyntaxKind::MODULE_FIELD_TAG => {
+                    if let Some(import) = field.children_by_kind(SyntaxKind::IMPORT).next() {
+                        let module_name = import.children_by_kind(SyntaxKind::MODULE_NAME).next();
+                        self.encode_import(&mut imports, module_name, import, Some(field))?;
+                    } else {
+                        tags.bytes.push(0x00);
+                        let type_idx = self.encode_type_use(field.children_by_kind(SyntaxKind::TYPE_USE).next())?;
+                        write_u32(&mut tags.bytes, type_idx);
+                        tags.count += 1;
+                    }
+                    self.encode_inline_exports(&mut exports, field, 0x04)?;
+                }
+                SyntaxKind::MODULE_FIELD_EXPORT => {
+                    let name = self.expect_child(field, SyntaxKind::NAME, "export name")?;
+                    let extern_idx = field.children().find(|child| is_extern_idx(child.kind()));
+                    let Some(extern_idx) = extern_idx else {
+                        return Err(EncodeError {
+                            range: field.text_range(),
+                            message: "missing export descriptor".into(),
+                        });
+                    };
+                    write_bytes(&mut exports.bytes, &string_bytes(name));
+                    exports.bytes.push(match extern_idx.kind() {
+                        SyntaxKind::EXTERN_IDX_FUNC => 0x00,
+                        SyntaxKind::EXTERN_IDX_TABLE => 0x01,
+                        SyntaxKind::EXTERN_IDX_MEMORY => 0x02,
+                        SyntaxKind::EXTERN_IDX_GLOBAL => 0x03,
+                        _ => 0x04,
+                    });
+                    let index = self.expect_child(extern_idx, SyntaxKind::INDEX, "index")?;
+                    write_u32(&mut exports.bytes, self.resolve_idx(index)?);
+                    exports.count += 1;
+                }
+                SyntaxKind::MODULE_FIELD_START => {
+                    let index = self.expect_child(field, SyntaxKind::INDEX, "start function")?;
+                    start = Some(self.resolve_idx(index)?);
+                }
+                SyntaxKind::MODULE_FIELD_ELEM => self.encode_elem(&mut elems, field)?,
+                SyntaxKind::MODULE_FIELD_DATA => self.encode_data(&mut datas, field)?,
+                _ => {}
+            }
+        }
+
+        self.implicit_types.iter().for_each(|comp| {
+            types.bytes.extend_from_slice(comp);
+        });
+        types.count += self.implicit_types.len() as u32;
+
+        let mut module = b"\0asm\x01\0\0\0".to_vec();
+        write_section(&mut module, 1, &types);
+        write_section(&mut module, 2, &imports);
+        write_section(&mut module, 3, &funcs);
+        write_section(&mut module, 4, &tables);
+        write_section(&mut module, 5, &memories);
+        write_section(&mut module, 13, &tags);
+        write_section(&mut module, 6, &globals);
+        write_section(&mut module, 7, &exports);
+        if let Some(start) = start {
+            module.push(8);
+            let mut bytes = Vec::with_capacity(5);
+            write_u32(&mut bytes, start);
+            write_bytes(&mut module, &bytes);
+        }
+        write_section(&mut module, 9, &elems);
+        if datas.count > 0 {
+            module.push(12);
+            let mut bytes = Vec::with_capacity(5);
+            write_u32(&mut bytes, datas.count);
+            write_bytes(&mut module, &bytes);
+        }
+        let codes_offset = module.len() + section_header_len(&codes);
+        self.funcs
+            .iter_mut()
+            .for_each(|func| func.offset += codes_offset as u32);
+        write_section(&mut module, 10, &codes);
+        write_section(&mut module, 11, &datas);
+        self.encode_names(&mut module);
+        Ok(module)
+    }
+
+    /// Encode explicitly defined types.
+    /// Implicit function types are only known after encoding other sections,
+    /// so they will be appended later.
+    fn encode_types(&mut self) -> EncodeResult<Section> {
+        let mut section = Section::default();
+        let module_range = self.module.text_range();
+        let groups = get_rec_type_groups(self.db, self.document);
+        for group in groups.iter().filter(|group| module_range.contains_range(group.range)) {
+            // rec group with single type can't be represented by a standalone type definition
+            let is_rec = group.type_defs.len() != 1 || group.type_defs[0].text_range() != group.range;
+            if is_rec {
+                section.bytes.push(0x4E);
+                write_u32(&mut section.bytes, group.type_defs.len() as u32);
+            }
+            for key in &group.type_defs {
+                let Some(def_type) = self.def_types.get(key) else {
+                    return Err(EncodeError {
+                        range: key.text_range(),
+                        message: "invalid type definition".into(),
+                    });
+                };
+                let mut comp = Vec::new();
+                self.encode_comp_type(&mut comp, &def_type.comp, key.text_range())?;
+                if def_type.is_final && def_type.inherits.is_none() {
+                    if !is_rec && matches!(def_type.comp, CompositeType::Func(..)) {
+                        self.reusable_types.push((comp.clone(), self.type_count));
+                    }
+                } else {
+                    section.bytes.push(if def_type.is_final { 0x4F } else { 0x50 });
+                    if let Some(inherits) = &def_type.inherits {
+                        section.bytes.push(1);
+                        let idx = self
+                            .symbol_table
+                            .symbols
+                            .get(inherits.symbol)
+                            .and_then(|symbol| symbol.idx.num)
+                            .unwrap_or_default();
+                        write_u32(&mut section.bytes, idx);
+                    } else {
+                        section.bytes.push(0);
+                    }
+                }
+                section.bytes.extend_from_slice(&comp);
+                self.type_count += 1;
+            }
+            section.count += 1;
+        }
+        Ok(section)
+    }
+
+    fn encode_comp_type(&self, out: &mut Vec<u8>, comp: &CompositeType, range: TextRange) -> EncodeResult<()> {
+        match comp {
+            CompositeType::Func(sig) => {
+                out.push(0x60);
+                write_u32(out, sig.params.len() as u32);
+                sig.params
+                    .iter()
+                    .try_for_each(|(ty, _)| self.encode_val_type(out, ty, range))?;
+                write_u32(out, sig.results.len() as u32);
+                sig.results
+                    .iter()
+                    .try_for_each(|ty| self.encode_val_type(out, ty, range))?;
+            }
+            CompositeType::Struct(fields) => {
+                out.push(0x5F);
+                write_u32(out, fields.0.len() as u32);
+                fields
+                    .0
+                    .iter()
+                    .try_for_each(|(field, _)| self.encode_field_type(out, field, range))?;
+            }
+            CompositeType::Array(field) => {
+                out.push(0x5E);
+                let Some(field) = field else {
+                    return Err(EncodeError {
+                        range,
+                        message: "missing array field type".into(),
+                    });
+                };
+                self.encode_field_type(out, field, range)?;
+            }
+            CompositeType::Cont(heap_ty) => {
+                out.push(0x5D);
+                self.encode_heap_type(out, heap_ty, range)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn encode_field_type(&self, out: &mut Vec<u8>, field: &FieldType, range: TextRange) -> EncodeResult<()> {
+        match &field.storage {
+            StorageType::Val(ty) => self.encode_val_type(out, ty, range)?,
+            StorageType::PackedI8 => out.push(0x78),
+            StorageType::PackedI16 => out.push(0x77),
+        }
+        out.push(field.mutable as u8);
+        Ok(())
+    }
+
+    fn encode_val_type(&self, out: &mut Vec<u8>, ty: &ValType, range: TextRange) -> EncodeResult<()> {
+        match ty {
+            ValType::I32 => out.push(0x7F),
+            ValType::I64 => out.push(0x7E),
+            ValType::F32 => out.push(0x7D),
+            ValType::F64 => out.push(0x7C),
+            ValType::V128 => out.push(0x7B),
+            ValType::Ref(ref_type) => self.encode_ref_type(out, ref_type, range)?,
+        }
+        Ok(())
+    }
+
+    fn encode_ref_type(&self, out: &mut Vec<u8>, ty: &RefType, range: TextRange) -> EncodeResult<()> {
+        if !ty.nullable {
+            out.push(0x64);
+        } else if matches!(ty.heap_ty, HeapType::Type(..)) {
+            out.push(0x63);
+        }
+        self.encode_heap_type(out, &ty.heap_ty, range)
+    }
+
+    fn encode_heap_type(&self, out: &mut Vec<u8>, ty: &HeapType, range: TextRange) -> EncodeResult<()> {
+        match ty {
+            HeapType::Type(idx) => {
+                let num = idx.num.or_else(|| {
+                    self.symbol_table
+                        .find_def_by_idx(*idx, SymbolKind::Type, self.module_key)
+                        .and_then(|symbol| symbol.idx.num)
+                });
+                let Some(num) = num else {
+                    return Err(EncodeError {
+                        range,
+                        message: format!(
+                            "cannot find type `{}`",
+                            idx.name.map(|name| name.ident(self.db).as_str()).unwrap_or_default()
+                        ),
+                    });
+                };
+                write_i64(out, num as i64);
+            }
+            HeapType::Any => out.push(0x6E),
+            HeapType::Eq => out.push(0x6D),
+            HeapType::I31 => out.push(0x6C),
+            HeapType::Struct => out.push(0x6B),
+            HeapType::Array => out.push(0x6A),
+            HeapType::None => out.push(0x71),
+            HeapType::Func => out.push(0x70),
+            HeapType::NoFunc => out.push(0x73),
+            HeapType::Exn => out.push(0x69),
+            HeapType::NoExn => out.push(0x74),
+            HeapType::Extern => out.push(0x6F),
+            HeapType::NoExtern => out.push(0x72),
+            HeapType::Cont => out.push(0x68),
+            HeapType::NoCont => out.push(0x75),
+            HeapType::Rec(..) | HeapType::DefFunc(..) => {
+                return Err(EncodeError {
+                    range,
+                    message: "invalid heap type".into(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Encode value type node in syntax tree.
+    fn encode_val_type_node(&self, out: &mut Vec<u8>, node: AmberNode) -> EncodeResult<()> {
+        let Some(ty) = ValType::from_green(node.green(), self.db) else {
+            return Err(EncodeError {
+                range: node.text_range(),
+                message: "invalid value type".into(),
+            });
+        };
+        self.encode_val_type(out, &ty, node.text_range())
+    }
+
+    /// Get the function type index of a type use.
+    /// Function types from inline params and results will be appended to the type section if needed.
+    fn encode_type_use(&mut self, type_use: Option<AmberNode>) -> EncodeResult<u32> {
+        if let Some(index) = type_use.and_then(|type_use| type_use.children_by_kind(SyntaxKind::INDEX).next()) {
+            return self.resolve_idx(index);
+        }
+        let sig = type_use
+            .map(|type_use| Sig::from_type_use(self.db, self.document, type_use))
+            .unwrap_or_default();
+        let range = type_use.map_or(self.module.text_range(), |type_use| type_use.text_range());
+        let mut comp = vec![0x60];
+        write_u32(&mut comp, sig.params.len() as u32);
+        sig.params
+            .iter()
+            .try_for_each(|ty| self.encode_val_type(&mut comp, ty, range))?;
+        write_u32(&mut comp, sig.results.len() as u32);
+        sig.results
+            .iter()
+            .try_for_each(|ty| self.encode_val_type(&mut comp, ty, range))?;
+        if let Some((_, idx)) = self.reusable_types.iter().find(|(bytes, _)| *bytes == comp) {
+            Ok(*idx)
+        } else if let Some(i) = self.implicit_types.iter().position(|bytes| *bytes == comp) {
+            Ok(self.type_count + i as u32)
+        } else {
+            self.implicit_types.push(comp);
+            Ok(self.type_count + self.implicit_types.len() as u32 - 1)
+        }
+    }
+
+    fn encode_import(
+        &mut self,
+        section: &mut Section,
+        module_name: Option<AmberNode>,
+        node: AmberNode,
+        desc: Option<AmberNode>,
+    ) -> EncodeResult<()> {
+        let (Some(module_name), Some(name), Some(desc)) =
+            (module_name, node.children_by_kind(SyntaxKind::NAME).next(), desc)
+        else {
+            return Err(EncodeError {
+                range: node.text_range(),
+                message: "incomplete import".into(),
+            });
+        };
+        write_bytes(&mut section.bytes, &string_bytes(module_name));
+        write_bytes(&mut section.bytes, &string_bytes(name));
+        let keyword = desc
+            .tokens_by_kind(SyntaxKind::KEYWORD)
+            .next()
+            .map(|token| token.text())
+            .unwrap_or_default();
+        match keyword {
+            "func" => {
+                section.bytes.push(0x00);
+                let type_idx = self.encode_type_use(desc.children_by_kind(SyntaxKind::TYPE_USE).next())?;
+                write_u32(&mut section.bytes, type_idx);
+            }
+            "table" => {
+                section.bytes.push(0x01);
+                let table_type = self.expect_child(desc, SyntaxKind::TABLE_TYPE, "table type")?;
+                self.encode_table_type(&mut section.bytes, table_type)?;
+            }
+            "memory" => {
+                section.bytes.push(0x02);
+                let mem_type = self.expect_child(desc, SyntaxKind::MEM_TYPE, "memory type")?;
+                self.encode_mem_type(&mut section.bytes, mem_type)?;
+            }
+            "global" => {
+                section.bytes.push(0x03);
+                let global_type = self.expect_child(desc, SyntaxKind::GLOBAL_TYPE, "global type")?;
+                self.encode_global_type(&mut section.bytes, global_type)?;
+            }
+            _ => {
+                section.bytes.extend_from_slice(&[0x04, 0x00]);
+                let type_idx = self.encode_type_use(desc.children_by_kind(SyntaxKind::TYPE_USE).next())?;
+                write_u32(&mut section.bytes, type_idx);
+            }
+        }
+        section.count += 1;
+        Ok(())
+    }
+
+    fn encode_inline_exports(&self, section: &mut Section, field: AmberNode, kind: u8) -> EncodeResult<()> {
+        let Some(idx) = self
+            .symbol_table
+            .symbols
+            .get(SymbolKey::from(field))
+            .and_then(|symbol| symbol.idx.num)
+        else {
+            return Ok(());
+        };
+        for export in field.children_by_kind(SyntaxKind::EXPORT) {
+            let name = self.expect_child(export, SyntaxKind::NAME, "export name")?;
+            write_bytes(&mut section.bytes, &string_bytes(name));
+            section.bytes.push(kind);
+            write_u32(&mut section.bytes, idx);
+            section.count += 1;
+        }
+        Ok(())
+    }
+
+    fn encode_table(&mut self, section: &mut Section, elems: &mut Section, field: AmberNode) -> EncodeResult<()> {
+        if let Some(table_type) = field.children_by_kind(SyntaxKind::TABLE_TYPE).next() {
+            if field.children_by_kind(Instr::can_cast).next().is_some() {
+                section.bytes.extend_from_slice(&[0x40, 0x00]);
+                self.encode_table_type(&mut section.bytes, table_type)?;
+                self.encode_expr(&mut section.bytes, field)?;
+            } else {
+                self.encode_table_type(&mut section.bytes, table_type)?;
+            }
+        } else {
+            // abbreviation with inline elements
+            let ref_type = self.expect_child(field, SyntaxKind::REF_TYPE, "reference type")?;
+            let elem = self.expect_child(field, SyntaxKind::ELEM, "table elements")?;
+            let count = elem
+                .children_by_kind([SyntaxKind::INDEX, SyntaxKind::ELEM_EXPR])
+                .count() as u32;
+            self.encode_val_type_node(&mut section.bytes, ref_type)?;
+            section.bytes.push(0x01);
+            write_u32(&mut section.bytes, count);
+            write_u32(&mut section.bytes, count);
+
+            let table_idx = self
+                .symbol_table
+                .symbols
+                .get(SymbolKey::from(field))
+                .and_then(|symbol| symbol.idx.num);
+            let mode = ElemMode::Active {
+                table: table_idx.unwrap_or_default(),
+                offset: None,
+            };
+            self.encode_elem_segment(elems, mode, Some(ref_type), Some(elem))?;
+        }
+        section.count += 1;
+        Ok(())
+    }
+
+    fn encode_table_type(&self, out: &mut Vec<u8>, table_type: AmberNode) -> EncodeResult<()> {
+        let ref_type = self.expect_child(table_type, SyntaxKind::REF_TYPE, "reference type")?;
+        self.encode_val_type_node(out, ref_type)?;
+        let limits = self.expect_child(table_type, SyntaxKind::LIMITS, "limits")?;
+        self.encode_limits(out, limits, is_64(table_type), 0, None)
+    }
+
+    fn encode_mem_type(&self, out: &mut Vec<u8>, mem_type: AmberNode) -> EncodeResult<()> {
+        let limits = self.expect_child(mem_type, SyntaxKind::LIMITS, "limits")?;
+        let shared = mem_type
+            .tokens_by_kind(SyntaxKind::KEYWORD)
+            .any(|token| token.text() == "shared");
+        let page_size = mem_type
+            .children_by_kind(SyntaxKind::MEM_PAGE_SIZE)
+            .next()
+            .and_then(|page_size| page_size.tokens_by_kind(SyntaxKind::UNSIGNED_INT).next())
+            .map(|token| match helpers::parse_u32(token.text()) {
+                Ok(size) if size.is_power_of_two() => Ok(size.trailing_zeros()),
+                _ => Err(EncodeError {
+                    range: token.text_range(),
+                    message: "invalid page size".into(),
+                }),
+            })
+            .transpose()?;
+        self.encode_limits(out, limits, is_64(mem_type), if shared { 0x02 } else { 0 }, page_size)
+    }
+
+    fn encode_limits(
+        &self,
+        out: &mut Vec<u8>,
+        limits: AmberNode,
+        is_64: bool,
+        flags: u8,
+        page_size_log2: Option<u32>,
+    ) -> EncodeResult<()> {
+        let mut numbers = limits.tokens_by_kind(SyntaxKind::UNSIGNED_INT).map(|token| {
+            helpers::parse_u64(token.text()).map_err(|_| EncodeError {
+                range: token.text_range(),
+                message: "invalid limit".into(),
+            })
+        });
+        let min = numbers.next().transpose()?.unwrap_or_default();
+        let max = numbers.next().transpose()?;
+        let mut flags = flags;
+        if max.is_some() {
+            flags |= 0x01;
+        }
+        if is_64 {
+            flags |= 0x04;
+        }
+        if page_size_log2.is_some() {
+            flags |= 0x08;
+        }
+        out.push(flags);
+        write_u64(out, min);
+        if let Some(max) = max {
+            write_u64(out, max);
+        }
+        if let Some(page_size_log2) = page_size_log2 {
+            write_u32(out, page_size_log2);
+        }
+        Ok(())
+    }
+
+    fn encode_global_type(&self, out: &mut Vec<u8>, global_type: AmberNode) -> EncodeResult<()> {
+        let ty = self.expect_child(global_type, AstValType::can_cast, "value type")?;
+        self.encode_val_type_node(out, ty)?;
+        out.push(
+            global_type
+                .tokens_by_kind(SyntaxKind::KEYWORD)
+                .any(|token| token.text() == "mut") as u8,
+        );
+        Ok(())
+    }
+
+    fn encode_memory(&mut self, section: &mut Section, datas: &mut Section, field: AmberNode) -> EncodeResult<()> {
+        if let Some(mem_type) = field.children_by_kind(SyntaxKind::MEM_TYPE).next() {
+            self.encode_mem_type(&mut section.bytes, mem_type)?;
+        } else {
+            // abbreviation with inline data
+            let data = self.expect_child(field, SyntaxKind::DATA, "memory data")?;
+            let bytes = string_bytes(data);
+            let pages = (bytes.len() as u64).div_ceil(65536);
+            let is_64 = field
+                .children_by_kind(SyntaxKind::ADDR_TYPE)
+                .next()
+                .is_some_and(|addr_type| {
+                    addr_type
+                        .tokens_by_kind(SyntaxKind::TYPE_KEYWORD)
+                        .any(|token| token.text() == "i64")
+                });
+            section.bytes.push(if is_64 { 0x05 } else { 0x01 });
+            write_u64(&mut section.bytes, pages);
+            write_u64(&mut section.bytes, pages);
+
+            let mem_idx = self
+                .symbol_table
+                .symbols
+                .get(SymbolKey::from(field))
+                .and_then(|symbol| symbol.idx.num)
+                .unwrap_or_default();
+            if mem_idx == 0 {
+                datas.bytes.push(0x00);
+            } else {
+                datas.bytes.push(0x02);
+                write_u32(&mut datas.bytes, mem_idx);
+            }
+            datas.bytes.push(if is_64 { 0x42 } else { 0x41 });
+            datas.bytes.extend_from_slice(&[0x00, 0x0B]);
+            write_bytes(&mut datas.bytes, &bytes);
+            datas.count += 1;
+        }
+        section.count += 1;
+        Ok(())
+    }
+
+    /// Record indices of element segments and data segments, since they can be referenced before defined.
+    fn collect_segments(&mut self) {
+        let mut elem_idx = 0;
+        let mut data_idx = 0;
+        for field in self.module.children() {
+            match field.kind() {
+                SyntaxKind::MODULE_FIELD_ELEM => {
+                    self.elems.insert(field.into(), elem_idx);
+                    elem_idx += 1;
+                }
+                SyntaxKind::MODULE_FIELD_TABLE if field.children_by_kind(SyntaxKind::ELEM).next().is_some() => {
+                    elem_idx += 1;
+                }
+                SyntaxKind::MODULE_FIELD_DATA => {
+                    self.datas.insert(field.into(), data_idx);
+                    data_idx += 1;
+                }
+                SyntaxKind::MODULE_FIELD_MEMORY if field.children_by_kind(SyntaxKind::DATA).next().is_some() => {
+                    data_idx += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn encode_elem(&mut self, section: &mut Section, field: AmberNode) -> EncodeResult<()> {
+        // element list can be omitted in legacy syntax, which means an empty vector of function indices
+        let elem_list = field.children_by_kind(SyntaxKind::ELEM_LIST).next();
+        let mode = if field
+            .tokens_by_kind(SyntaxKind::MODIFIER_KEYWORD)
+            .any(|token| token.text() == "declare")
+        {
+            ElemMode::Declarative
+        } else if let Some(offset) = field.children_by_kind(SyntaxKind::OFFSET).next() {
+            let table = field
+                .children_by_kind(SyntaxKind::TABLE_USE)
+                .next()
+                .and_then(|table_use| table_use.children_by_kind(SyntaxKind::INDEX).next())
+                .map(|index| self.resolve_idx(index))
+                .transpose()?
+                .unwrap_or_default();
+            ElemMode::Active {
+                table,
+                offset: Some(offset),
+            }
+        } else {
+            ElemMode::Passive
+        };
+        let ref_type = elem_list.and_then(|elem_list| elem_list.children_by_kind(SyntaxKind::REF_TYPE).next());
+        self.encode_elem_segment(section, mode, ref_type, elem_list)
+    }
+
+    fn encode_elem_segment(
+        &mut self,
+        section: &mut Section,
+        mode: ElemMode,
+        ref_type: Option<AmberNode>,
+        elem_list: Option<AmberNode>,
+    ) -> EncodeResult<()> {
+        let out = &mut section.bytes;
+        let exprs = elem_list
+            .iter()
+            .flat_map(|elem_list| elem_list.children_by_kind(SyntaxKind::ELEM_EXPR))
+            .collect::<Vec<_>>();
+        let indexes = elem_list
+            .iter()
+            .flat_map(|elem_list| elem_list.children_by_kind(SyntaxKind::INDEX))
+            .collect::<Vec<_>>();
+        let is_exprs = !exprs.is_empty() || ref_type.is_some() && indexes.is_empty();
+        let is_funcref = ref_type.is_none_or(|ref_type| {
+            RefType::from_green(ref_type.green(), self.db).is_some_and(|ty| {
+                ty == RefType {
+                    heap_ty: HeapType::Func,
+                    nullable: true,
+                }
+            })
+        });
+        let exprs_flag = if is_exprs { 0x04 } else { 0 };
+        match &mode {
+            ElemMode::Active { table: 0, offset } if !is_exprs || is_funcref => {
+                out.push(exprs_flag);
+                self.encode_offset(out, *offset)?;
+            }
+            ElemMode::Active { table, offset } => {
+                out.push(exprs_flag | 0x02);
+                write_u32(out, *table);
+                self.encode_offset(out, *offset)?;
+                self.encode_elem_kind(out, ref_type, is_exprs)?;
+            }
+            ElemMode::Passive => {
+                out.push(exprs_flag | 0x01);
+                self.encode_elem_kind(out, ref_type, is_exprs)?;
+            }
+            ElemMode::Declarative => {
+                out.push(exprs_flag | 0x03);
+                self.encode_elem_kind(out, ref_type, is_exprs)?;
+            }
+        }
+        if is_exprs {
+            write_u32(out, exprs.len() as u32);
+            for expr in exprs {
+                self.encode_expr(out, expr)?;
+            }
+        } else {
+            write_u32(out, indexes.len() as u32);
+            for index in indexes {
+                // inline table elements aren't recorded by binder, so look up functions directly
+                let idx = Idx::from_green_for_ref(index.green(), self.db)
+                    .and_then(|idx| {
+                        idx.num.or_else(|| {
+                            self.symbol_table
+                                .find_def_by_idx(idx, SymbolKind::Func, self.module_key)
+                                .and_then(|symbol| symbol.idx.num)
+                        })
+                    })
+                    .ok_or_else(|| EncodeError {
+                        range: index.text_range(),
+                        message: format!("cannot find `{}`", index.green()),
+                    })?;
+                write_u32(out, idx);
+            }
+        }
+        section.count += 1;
+        Ok(())
+    }
+
+    fn encode_elem_kind(&self, out: &mut Vec<u8>, ref_type: Option<AmberNode>, is_exprs: bool) -> EncodeResult<()> {
+        match ref_type {
+            Some(ref_type) if is_exprs => self.encode_val_type_node(out, ref_type),
+            Some(..) => {
+                out.push(0x00);
+                Ok(())
+            }
+            None if is_exprs => {
+                out.push(0x70);
+                Ok(())
+            }
+            None => {
+                out.push(0x00);
+                Ok(())
+            }
+        }
+    }
+
+    /// Encode offset expression. If absent, it's from abbreviation and means offset zero.
+    fn encode_offset(&mut self, out: &mut Vec<u8>, offset: Option<AmberNode>) -> EncodeResult<()> {
+        if let Some(offset) = offset {
+            self.encode_expr(out, offset)
+        } else {
+            out.extend_from_slice(&[0x41, 0x00, 0x0B]);
+            Ok(())
+        }
+    }
+
+    fn encode_data(&mut self, section: &mut Section, field: AmberNode) -> EncodeResult<()> {
+        let out = &mut section.bytes;
+        if let Some(offset) = field.children_by_kind(SyntaxKind::OFFSET).next() {
+            let mem_idx = field
+                .children_by_kind(SyntaxKind::MEM_USE)
+                .next()
+                .and_then(|mem_use| mem_use.children_by_kind(SyntaxKind::INDEX).next())
+                .map(|index| self.resolve_idx(index))
+                .transpose()?
+                .unwrap_or_default();
+            if mem_idx == 0 {
+                out.push(0x00);
+            } else {
+                out.push(0x02);
+                write_u32(out, mem_idx);
+            }
+            self.encode_expr(out, offset)?;
+        } else {
+            out.push(0x01);
+        }
+        write_bytes(out, &string_bytes(field));
+        section.count += 1;
+        Ok(())
+    }
+
+    fn encode_code(&mut self, section: &mut Section, func: AmberNode) -> EncodeResult<()> {
+        let func_key = SymbolKey::from(func);
+        let mut local_idx = Sig::from_func(self.db, self.document, func).params.len() as u32;
+        self.locals.clear();
+        let mut locals = Vec::<(u32, Vec<u8>)>::new();
+        for local in func.children_by_kind(SyntaxKind::LOCAL) {
+            let named = local.tokens_by_kind(SyntaxKind::IDENT).next().is_some();
+            for ty in local.children_by_kind(AstValType::can_cast) {
+                self.locals
+                    .insert(if named { local.into() } else { ty.into() }, local_idx);
+                local_idx += 1;
+                let mut bytes = Vec::with_capacity(1);
+                self.encode_val_type_node(&mut bytes, ty)?;
+                match locals.last_mut() {
+                    Some((count, last)) if *last == bytes => *count += 1,
+                    _ => locals.push((1, bytes)),
+                }
+            }
+        }
+
+        let mut body = Vec::new();
+        write_u32(&mut body, locals.len() as u32);
+        for (count, ty) in locals {
+            write_u32(&mut body, count);
+            body.extend_from_slice(&ty);
+        }
+        self.labels.clear();
+        self.labels.push(func_key);
+        self.instrs = Some(Vec::new());
+        self.encode_expr(&mut body, func)?;
+        let mut size = Vec::with_capacity(5);
+        write_u32(&mut size, body.len() as u32);
+        self.funcs.push(FuncCode {
+            idx: self
+                .symbol_table
+                .symbols
+                .get(func_key)
+                .and_then(|symbol| symbol.idx.num)
+                .unwrap_or_default(),
+            range: func.text_range(),
+            offset: (section.bytes.len() + size.len()) as u32,
+            size: body.len() as u32,
+            instrs: self.instrs.take().unwrap_or_default(),
+        });
+        write_bytes(&mut section.bytes, &body);
+        section.count += 1;
+        Ok(())
+    }
+
+    /// Encode instructions in the given node, followed by `end`.
+    fn encode_expr(&mut self, out: &mut Vec<u8>, node: AmberNode) -> EncodeResult<()> {
+        for instr in node.children_by_kind(Instr::can_cast) {
+            self.encode_instr(out, instr)?;
+        }
+        out.push(0x0B);
+        Ok(())
+    }
+
+    fn encode_names(&self, out: &mut Vec<u8>) {
+        let mut names = Vec::new();
+        write_bytes(&mut names, b"name");
+        if let Some(ident) = self.module.tokens_by_kind(SyntaxKind::IDENT).next() {
+            let mut bytes = Vec::new();
+            write_bytes(&mut bytes, &ident_bytes(ident.text()));
+            names.push(0);
+            write_bytes(&mut names, &bytes);
+        }
+
+        self.encode_name_map(&mut names, 1, SymbolKind::Func);
+
+        let mut func_locals = Vec::new();
+        for func in self.module.children_by_kind(SyntaxKind::MODULE_FIELD_FUNC) {
+            let Some(func_idx) = self
+                .symbol_table
+                .symbols
+                .get(SymbolKey::from(func))
+                .and_then(|symbol| symbol.idx.num)
+            else {
+                continue;
+            };
+            let mut local_idx = 0;
+            let mut locals = Vec::new();
+            for param in func
+                .children_by_kind(SyntaxKind::TYPE_USE)
+                .flat_map(|type_use| type_use.children_by_kind(SyntaxKind::PARAM))
+            {
+                if let Some(ident) = param.tokens_by_kind(SyntaxKind::IDENT).next() {
+                    locals.push((local_idx, ident.text()));
+                    local_idx += 1;
+                } else {
+                    local_idx += param.children_by_kind(AstValType::can_cast).count() as u32;
+                }
+            }
+            local_idx = Sig::from_func(self.db, self.document, func).params.len() as u32;
+            for local in func.children_by_kind(SyntaxKind::LOCAL) {
+                if let Some(ident) = local.tokens_by_kind(SyntaxKind::IDENT).next() {
+                    locals.push((local_idx, ident.text()));
+                    local_idx += 1;
+                } else {
+                    local_idx += local.children_by_kind(AstValType::can_cast).count() as u32;
+                }
+            }
+            if !locals.is_empty() {
+                func_locals.push((func_idx, locals));
+            }
+        }
+        if !func_locals.is_empty() {
+            let mut bytes = Vec::new();
+            write_u32(&mut bytes, func_locals.len() as u32);
+            for (func_idx, locals) in func_locals {
+                write_u32(&mut bytes, func_idx);
+                write_u32(&mut bytes, locals.len() as u32);
+                for (idx, name) in locals {
+                    write_u32(&mut bytes, idx);
+                    write_bytes(&mut bytes, &ident_bytes(name));
+                }
+            }
+            names.push(2);
+            write_bytes(&mut names, &bytes);
+        }
+
+        self.encode_name_map(&mut names, 4, SymbolKind::Type);
+        self.encode_name_map(&mut names, 5, SymbolKind::TableDef);
+        self.encode_name_map(&mut names, 6, SymbolKind::MemoryDef);
+        self.encode_name_map(&mut names, 7, SymbolKind::GlobalDef);
+        self.encode_name_map(&mut names, 8, SymbolKind::ElemDef);
+        self.encode_name_map(&mut names, 9, SymbolKind::DataDef);
+        self.encode_name_map(&mut names, 11, SymbolKind::TagDef);
+
+        // only contains the section name
+        if names.len() > 5 {
+            out.push(0);
+            write_bytes(out, &names);
+        }
+    }
+
+    fn encode_name_map(&self, names: &mut Vec<u8>, id: u8, kind: SymbolKind) {
+        let mut entries = self
+            .symbol_table
+            .symbols
+            .iter()
+            .filter(|symbol| symbol.kind == kind && symbol.region == self.module_key)
+            .filter_map(|symbol| {
+                let idx = match kind {
+                    SymbolKind::ElemDef => self.elems.get(&symbol.key).copied(),
+                    SymbolKind::DataDef => self.datas.get(&symbol.key).copied(),
+                    _ => symbol.idx.num,
+                };
+                Some((idx?, symbol.idx.name?.ident(self.db)))
+            })
+            .collect::<Vec<_>>();
+        if entries.is_empty() {
+            return;
+        }
+        entries.sort_by_key(|(idx, _)| *idx);
+        let mut bytes = Vec::new();
+        write_u32(&mut bytes, entries.len() as u32);
+        for (idx, name) in entries {
+            write_u32(&mut bytes, idx);
+            write_bytes(&mut bytes, &ident_bytes(name));
+        }
+        names.push(id);
+        write_bytes(names, &bytes);
+    }
+
+    /// Resolve index in binary format from a reference node.
+    fn resolve_idx(&self, node: AmberNode) -> EncodeResult<u32> {
+        let Some(token) = node
+            .tokens_by_kind([SyntaxKind::IDENT, SyntaxKind::INT, SyntaxKind::UNSIGNED_INT])
+            .next()
+        else {
+            return Err(EncodeError {
+                range: node.text_range(),
+                message: "missing index".into(),
+            });
+        };
+        if token.kind() != SyntaxKind::IDENT {
+            return helpers::parse_u32(token.text()).map_err(|_| EncodeError {
+                range: token.text_range(),
+                message: format!("invalid index `{}`", token.text()),
+            });
+        }
+        self.symbol_table
+            .find_def(node.into())
+            .and_then(|symbol| match symbol.kind {
+                SymbolKind::ElemDef => self.elems.get(&symbol.key).copied(),
+                SymbolKind::DataDef => self.datas.get(&symbol.key).copied(),
+                SymbolKind::Local => self.locals.get(&symbol.key).copied(),
+                _ => symbol.idx.num,
+            })
+            .ok_or_else(|| EncodeError {
+                range: token.text_range(),
+                message: format!("cannot find `{}`", token.text()),
+            })
+    }
+
+    fn expect_child<'a>(
+        &self,
+        node: AmberNode<'a>,
+        matcher: impl wat_syntax::SyntaxKindMatch,
+        name: &str,
+    ) -> EncodeResult<AmberNode<'a>> {
+        node.children_by_kind(matcher).next().ok_or_else(|| EncodeError {
+            range: node.text_range(),
+            message: format!("missing {name}"),
+        })
+    }
+}
+
+enum ElemMode<'a> {
+    Passive,
+    Active { table: u32, offset: Option<AmberNode<'a>> },
+    Declarative,
+}
+
+fn write_section(out: &mut Vec<u8>, id: u8, section: &Section) {
+    if section.count == 0 {
+        return;
+    }
+    out.push(id);
+    let mut bytes = Vec::with_capacity(section.bytes.len() + 5);
+    write_u32(&mut bytes, section.count);
+    bytes.extend_from_slice(&section.bytes);
+    write_bytes(out, &bytes);
+}
+
+/// Length of section ID, section size and item count, which are written before section content.
+fn section_header_len(section: &Section) -> usize {
+    let mut count = Vec::with_capacity(5);
+    write_u32(&mut count, section.count);
+    let mut size = Vec::with_capacity(5);
+    write_u32(&mut size, (count.len() + section.bytes.len()) as u32);
+    1 + size.len() + count.len()
+}
+
+fn is_extern_type(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::EXTERN_TYPE_FUNC
+            | SyntaxKind::EXTERN_TYPE_TABLE
+            | SyntaxKind::EXTERN_TYPE_MEMORY
+            | SyntaxKind::EXTERN_TYPE_GLOBAL
+            | SyntaxKind::EXTERN_TYPE_TAG
+    )
+}
+
+fn is_extern_idx(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::EXTERN_IDX_FUNC
+            | SyntaxKind::EXTERN_IDX_TABLE
+            | SyntaxKind::EXTERN_IDX_MEMORY
+            | SyntaxKind::EXTERN_IDX_GLOBAL
+            | SyntaxKind::EXTERN_IDX_TAG
+    )
+}
+
+fn is_64(node: AmberNode) -> bool {
+    node.children_by_kind(SyntaxKind::ADDR_TYPE)
+        .next()
+        .is_some_and(|addr_type| {
+            addr_type
+                .tokens_by_kind(SyntaxKind::TYPE_KEYWORD)
+                .any(|token| token.text() == "i64")
+        })
+}
+
+/// Concatenate all string literals in the given node.
+fn string_bytes(node: AmberNode) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut offsets = Vec::new();
+    node.tokens_by_kind(SyntaxKind::STRING).for_each(|token| {
+        helpers::decode_string(token.text(), token.text_range().start(), &mut bytes, &mut offsets);
+    });
+    bytes
+}
+
+/// Convert identifier to name in binary format, with leading `$` removed.
+fn ident_bytes(ident: &str) -> Vec<u8> {
+    let name = ident.strip_prefix('$').unwrap_or(ident);
+    if name.starts_with('"') {
+        let mut bytes = Vec::new();
+        helpers::decode_string(name, Default::default(), &mut bytes, &mut Vec::new());
+        bytes
+    } else {
+        name.as_bytes().to_vec()
+    }
+}
@@ -0,0 +1,203 @@
+pub(super) fn write_u32(out: &mut Vec<u8>, value: u32) {
+    write_u64(out, value as u64);
+}
+
+pub(super) fn write_u64(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+pub(super) fn write_i64(out: &mut Vec<u8>, mut value: i64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0) {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Write length-prefixed bytes, which is used by names and sections.
+pub(super) fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_u32(out, bytes.len() as u32);
+    out.extend_from_slice(bytes);
+}
+
+/// Parse integer literal to its bit pattern with the given bit width.
+///
+/// Both signed and unsigned ranges are accepted, as what `iN.const` does.
+pub(crate) fn parse_int(text: &str, bits: u32) -> Option<u64> {
+    let (negative, unsigned) = split_sign(text);
+    let unsigned = unsigned.replace('_', "");
+    let value = if let Some(hex) = unsigned.strip_prefix("0x") {
+        u64::from_str_radix(hex, 16).ok()?
+    } else {
+        unsigned.parse::<u64>().ok()?
+    };
+    let mask = if bits == 64 { u64::MAX } else { (1 << bits) - 1 };
+    if negative {
+        if value > 1 << (bits - 1) {
+            return None;
+        }
+        Some(value.wrapping_neg() & mask)
+    } else if value > mask {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+pub(crate) fn parse_f32(text: &str) -> Option<u32> {
+    parse_float(text, &F32).map(|bits| bits as u32)
+}
+
+pub(crate) fn parse_f64(text: &str) -> Option<u64> {
+    parse_float(text, &F64)
+}
+
+struct FloatFormat {
+    /// Significand bits, including the implicit leading bit.
+    sig_bits: u32,
+    exp_bits: u32,
+}
+const F32: FloatFormat = FloatFormat {
+    sig_bits: 24,
+    exp_bits: 8,
+};
+const F64: FloatFormat = FloatFormat {
+    sig_bits: 53,
+    exp_bits: 11,
+};
+impl FloatFormat {
+    fn sign_bit(&self) -> u64 {
+        1 << (self.sig_bits + self.exp_bits - 1)
+    }
+    fn exp_mask(&self) -> u64 {
+        ((1 << self.exp_bits) - 1) << (self.sig_bits - 1)
+    }
+    fn max_exp(&self) -> i64 {
+        (1 << (self.exp_bits - 1)) - 1
+    }
+}
+
+fn parse_float(text: &str, format: &FloatFormat) -> Option<u64> {
+    let (negative, unsigned) = split_sign(text);
+    let sign = if negative { format.sign_bit() } else { 0 };
+    let unsigned = unsigned.replace('_', "");
+    let bits = if unsigned == "inf" {
+        format.exp_mask()
+    } else if unsigned == "nan" {
+        format.exp_mask() | 1 << (format.sig_bits - 2)
+    } else if let Some(payload) = unsigned.strip_prefix("nan:0x") {
+        let payload = u64::from_str_radix(payload, 16).ok()?;
+        if payload == 0 || payload >= 1 << (format.sig_bits - 1) {
+            return None;
+        }
+        format.exp_mask() | payload
+    } else if let Some(hex) = unsigned.strip_prefix("0x") {
+        parse_hex_float(hex, format)?
+    } else if format.sig_bits == F32.sig_bits {
+        let value = unsigned.parse::<f32>().ok().filter(|value| value.is_finite())?;
+        value.to_bits() as u64
+    } else {
+        unsigned
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())?
+            .to_bits()
+    };
+    Some(sign | bits)
+}
+
+/// Convert hexadecimal float literal to bits with correct rounding (round to nearest, ties to even).
+fn parse_hex_float(hex: &str, format: &FloatFormat) -> Option<u64> {
+    let (mantissa, exp) = match hex.split_once(['p', 'P']) {
+        Some((mantissa, exp)) => (mantissa, exp.parse::<i64>().ok()?),
+        None => (hex, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if int.is_empty() {
+        return None;
+    }
+    let mut sig = 0u128;
+    let mut exp = exp;
+    let mut sticky = false;
+    for (c, is_frac) in int.chars().map(|c| (c, false)).chain(frac.chars().map(|c| (c, true))) {
+        let digit = c.to_digit(16)? as u128;
+        if sig < 1 << 120 {
+            sig = sig * 16 + digit;
+            if is_frac {
+                exp -= 4;
+            }
+        } else {
+            sticky |= digit != 0;
+            if !is_frac {
+                exp += 4;
+            }
+        }
+    }
+    if sig == 0 {
+        return Some(0);
+    }
+
+    let sig_bits = format.sig_bits as i64;
+    let min_exp = 1 - format.max_exp();
+    let width = 128 - sig.leading_zeros() as i64;
+    // exponent of the leading bit
+    let leading_exp = exp + width - 1;
+    if leading_exp > format.max_exp() {
+        return None;
+    }
+    let keep = if leading_exp >= min_exp {
+        sig_bits
+    } else {
+        sig_bits - (min_exp - leading_exp)
+    };
+    let shift = width - keep;
+    let mut rounded = if shift <= 0 {
+        sig << -shift
+    } else if shift > width {
+        0
+    } else {
+        let half = 1u128 << (shift - 1);
+        let rest = sig & half.wrapping_shl(1).wrapping_sub(1);
+        let truncated = if shift == 128 { 0 } else { sig >> shift };
+        if rest > half || rest == half && (sticky || truncated & 1 == 1) {
+            truncated + 1
+        } else {
+            truncated
+        }
+    } as u64;
+
+    if leading_exp < min_exp {
+        // subnormal; carrying into the implicit bit becomes the smallest normal number naturally
+        return Some(rounded);
+    }
+    let mut leading_exp = leading_exp;
+    if rounded == 1 << sig_bits {
+        rounded >>= 1;
+        leading_exp += 1;
+        if leading_exp > format.max_exp() {
+            return None;
+        }
+    }
+    let biased = (leading_exp + format.max_exp()) as u64;
+    Some(biased << (sig_bits - 1) | (rounded & ((1 << (sig_bits - 1)) - 1)))
+}
+
+fn split_sign(text: &str) -> (bool, &str) {
+    if let Some(rest) = text.strip_prefix('-') {
+        (true, rest)
+    } else {
+        (false, text.strip_prefix('+').unwrap_or(text))
+    }
+}
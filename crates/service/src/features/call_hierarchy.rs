@@ -1,6 +1,6 @@
 use crate::{
     LanguageService,
-    binder::{SymbolKind, SymbolTable},
+    binder::{Symbol, SymbolKind, SymbolTable},
     deprecation,
     helpers::{self, LineIndexExt},
     types_analyzer::{self, NamedSig},
@@ -25,7 +25,7 @@ impl LanguageService {
 
         symbol_table.symbols.iter().find_map(|symbol| match symbol.kind {
             SymbolKind::Func if symbol.key.text_range() == parent_range => Some(vec![CallHierarchyItem {
-                name: symbol.idx.render(self).to_string(),
+                name: render_name(self, symbol_table, symbol),
                 kind: LspSymbolKind::Function,
                 tags: if deprecation.contains_key(&symbol.key) {
                     Some(vec![SymbolTag::Deprecated])
@@ -34,7 +34,7 @@ impl LanguageService {
                 },
                 detail: Some(types_analyzer::render_func_header(
                     self,
-                    symbol.idx.name,
+                    symbol_table.get_header_name(self, symbol),
                     NamedSig::from_func(self, document, symbol_table.get_type_node_of(symbol)),
                 )),
                 uri: params.text_document.uri.clone(),
@@ -45,7 +45,7 @@ impl LanguageService {
             SymbolKind::Call if symbol.key.text_range() == parent_range => {
                 symbol_table.find_def(symbol.key).and_then(|symbol| {
                     Some(vec![CallHierarchyItem {
-                        name: symbol.idx.render(self).to_string(),
+                        name: render_name(self, symbol_table, symbol),
                         kind: LspSymbolKind::Function,
                         tags: if deprecation.contains_key(&symbol.key) {
                             Some(vec![SymbolTag::Deprecated])
@@ -54,7 +54,7 @@ impl LanguageService {
                         },
                         detail: Some(types_analyzer::render_func_header(
                             self,
-                            symbol.idx.name,
+                            symbol_table.get_header_name(self, symbol),
                             NamedSig::from_func(self, document, symbol_table.get_type_node_of(symbol)),
                         )),
                         uri: params.text_document.uri.clone(),
@@ -101,7 +101,7 @@ impl LanguageService {
                     .and_then(|symbol| {
                         Some(CallHierarchyIncomingCall {
                             from: CallHierarchyItem {
-                                name: symbol.idx.render(self).to_string(),
+                                name: render_name(self, symbol_table, symbol),
                                 kind: LspSymbolKind::Function,
                                 tags: if deprecation.contains_key(&symbol.key) {
                                     Some(vec![SymbolTag::Deprecated])
@@ -110,7 +110,7 @@ impl LanguageService {
                                 },
                                 detail: Some(types_analyzer::render_func_header(
                                     self,
-                                    symbol.idx.name,
+                                    symbol_table.get_header_name(self, symbol),
                                     NamedSig::from_func(self, document, symbol_table.get_type_node_of(symbol)),
                                 )),
                                 uri: params.item.uri.clone(),
@@ -144,7 +144,7 @@ impl LanguageService {
             .filter_map(|(def_symbol, ref_symbol)| {
                 Some(CallHierarchyOutgoingCall {
                     to: CallHierarchyItem {
-                        name: def_symbol.idx.render(self).to_string(),
+                        name: render_name(self, symbol_table, def_symbol),
                         kind: LspSymbolKind::Function,
                         tags: if deprecation.contains_key(&def_symbol.key) {
                             Some(vec![SymbolTag::Deprecated])
@@ -153,7 +153,7 @@ impl LanguageService {
                         },
                         detail: Some(types_analyzer::render_func_header(
                             self,
-                            def_symbol.idx.name,
+                            symbol_table.get_header_name(self, def_symbol),
                             NamedSig::from_func(self, document, symbol_table.get_type_node_of(def_symbol)),
                         )),
                         uri: params.item.uri.clone(),
@@ -168,3 +168,18 @@ impl LanguageService {
         Some(items)
     }
 }
+
+/// Render function name with identifier, name annotation or numeric index in order.
+fn render_name<'db>(
+    db: &'db dyn salsa::Database,
+    symbol_table: &'db SymbolTable<'db>,
+    symbol: &'db Symbol<'db>,
+) -> String {
+    if symbol.idx.name.is_none()
+        && let Some(annot) = symbol_table.get_name_annot(symbol)
+    {
+        annot.name.clone()
+    } else {
+        symbol.idx.render(db).to_string()
+    }
+}
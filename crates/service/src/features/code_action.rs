@@ -1,10 +1,21 @@
+use super::diagnostics::check_document;
 use crate::{LanguageService, binder::SymbolTable, helpers::LineIndexExt, refactorings::*};
-use lspt::{CodeAction, CodeActionKind, CodeActionParams};
-use wat_syntax::{SyntaxKind, SyntaxNode};
+use lspt::{
+    CodeAction, CodeActionContext, CodeActionKind, CodeActionParams, TextDocumentIdentifier, TextEdit, WorkspaceEdit,
+};
+use rustc_hash::FxBuildHasher;
+use std::{collections::HashMap, sync::Arc};
+use wat_syntax::{SyntaxKind, SyntaxNode, TextRange, TextSize};
+
+/// Maximum rounds of applying quick fixes, in case fixes keep producing new diagnostics.
+const MAX_FIX_ROUNDS: usize = 16;
 
 impl LanguageService {
     /// Handler for `textDocument/codeAction` request.
     pub fn code_action(&self, params: CodeActionParams) -> Option<Vec<CodeAction>> {
+        if self.is_read_only(&params.text_document.uri) {
+            return None;
+        }
         let uri = &params.text_document.uri;
         let document = self.get_document(uri)?;
         self.with_db(|db| {
@@ -17,12 +28,14 @@ impl LanguageService {
             let mut rewrite = params.context.only.is_none();
             let mut inline = params.context.only.is_none();
             let mut extract = params.context.only.is_none();
+            let mut fix_all = false;
             params.context.only.iter().flatten().for_each(|kind| match kind {
                 CodeActionKind::QuickFix => quickfix = true,
                 CodeActionKind::Refactor => refactor = true,
                 CodeActionKind::RefactorRewrite => rewrite = true,
                 CodeActionKind::RefactorInline => inline = true,
                 CodeActionKind::RefactorExtract => extract = true,
+                CodeActionKind::Source | CodeActionKind::SourceFixAll => fix_all = true,
                 _ => {}
             });
 
@@ -52,6 +65,9 @@ impl LanguageService {
                             if let Some(action) = merge_to_return_call::act(db, uri, line_index, symbol_table, &it) {
                                 actions.push(action);
                             }
+                            if let Some(action) = fold_const::act(db, uri, document, line_index, symbol_table, &it) {
+                                actions.push(action);
+                            }
                         }
                         if quickfix
                             && let Some(action) =
@@ -59,6 +75,10 @@ impl LanguageService {
                         {
                             actions.push(action);
                         }
+                        if quickfix && let Some(action) = remove_dead_store::act(uri, line_index, &it, &params.context)
+                        {
+                            actions.push(action);
+                        }
                     }
                     SyntaxKind::PARAM => {
                         if rewrite && let Some(action) = split_types::act(uri, line_index, &it, SyntaxKind::PARAM) {
@@ -103,6 +123,14 @@ impl LanguageService {
                             actions.push(action);
                         }
                     }
+                    SyntaxKind::BLOCK_TRY => {
+                        if rewrite
+                            && let Some(action) =
+                                try_to_try_table::act(db, uri, document, line_index, symbol_table, &it)
+                        {
+                            actions.push(action);
+                        }
+                    }
                     SyntaxKind::BLOCK_IF_THEN => {
                         if quickfix && let Some(action) = add_result_types::act(uri, line_index, &it, &params.context) {
                             actions.push(action);
@@ -199,8 +227,141 @@ impl LanguageService {
                 node = it;
             }
 
-            if actions.is_empty() { None } else { Some(actions) }
+            Some((actions, fix_all))
         })
         .flatten()
+        .map(|(mut actions, fix_all)| {
+            if fix_all && let Some(action) = self.create_fix_all_action(uri) {
+                actions.push(action);
+            }
+            actions
+        })
+        .filter(|actions| !actions.is_empty())
+    }
+
+    /// Apply all unambiguous quick fixes of a document repeatedly until nothing can be fixed.
+    ///
+    /// A diagnostic with more than one quick fix is considered ambiguous and skipped.
+    /// Quick fixes whose edits overlap with other fixes in the same round are postponed to the next round.
+    /// This returns the fixed text, or `None` if there's nothing to fix.
+    pub fn fix_all(&self, uri: &str) -> Option<String> {
+        if self.is_read_only(uri) {
+            return None;
+        }
+        let original = self.get_document(uri)?.text(self);
+        let config = self.configs.read().get(uri)?.unwrap_or_global(self).clone();
+        // Fixed text is committed to a separate service, so documents in the current service won't be touched.
+        let mut service = LanguageService {
+            global_config: Arc::new(config),
+            ..Default::default()
+        };
+        let mut text = original.clone();
+        for _ in 0..MAX_FIX_ROUNDS {
+            service.commit(uri.into(), text.clone());
+            let mut edits = service.collect_quick_fix_edits(uri)?;
+            if edits.is_empty() {
+                break;
+            }
+            edits.sort_by_key(|(range, _)| std::cmp::Reverse((range.start(), range.end())));
+            edits.into_iter().for_each(|(range, new_text)| {
+                text.replace_range(std::ops::Range::<usize>::from(range), &new_text);
+            });
+        }
+        if &text == original { None } else { Some(text) }
+    }
+
+    /// Collect edits of unambiguous quick fixes for current diagnostics without overlapping.
+    fn collect_quick_fix_edits(&self, uri: &str) -> Option<Vec<(TextRange, String)>> {
+        let line_index = self.get_document(uri)?.line_index(self);
+        let mut accepted: Vec<(TextRange, String)> = vec![];
+        check_document(self, uri)?.into_iter().for_each(|diagnostic| {
+            let actions = self.code_action(CodeActionParams {
+                text_document: TextDocumentIdentifier { uri: uri.into() },
+                range: diagnostic.range,
+                context: CodeActionContext {
+                    diagnostics: vec![diagnostic.clone()],
+                    only: Some(vec![CodeActionKind::QuickFix]),
+                    trigger_kind: None,
+                },
+                work_done_token: None,
+                partial_result_token: None,
+            });
+            let mut actions = actions.into_iter().flatten().filter(|action| {
+                action.kind == Some(CodeActionKind::QuickFix)
+                    && action
+                        .diagnostics
+                        .as_ref()
+                        .is_some_and(|diagnostics| diagnostics.contains(&diagnostic))
+            });
+            let (Some(action), None) = (actions.next(), actions.next()) else {
+                return;
+            };
+            let Some(edits) = action
+                .edit
+                .and_then(|edit| edit.changes)
+                .and_then(|mut changes| changes.remove(uri))
+                .and_then(|edits| {
+                    edits
+                        .into_iter()
+                        .map(|edit| line_index.convert(edit.range).map(|range| (range, edit.new_text)))
+                        .collect::<Option<Vec<_>>>()
+                })
+            else {
+                return;
+            };
+            if edits
+                .iter()
+                .all(|(range, _)| accepted.iter().all(|(other, _)| !is_overlapped(*range, *other)))
+            {
+                accepted.extend(edits);
+            }
+        });
+        Some(accepted)
     }
+
+    fn create_fix_all_action(&self, uri: &str) -> Option<CodeAction> {
+        let document = self.get_document(uri)?;
+        let old = document.text(self);
+        let new = self.fix_all(uri)?;
+        // only replace the changed part, which is between common prefix and common suffix
+        let prefix = old
+            .char_indices()
+            .zip(new.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(old.len().min(new.len()), |((i, _), _)| i);
+        let suffix = old[prefix..]
+            .chars()
+            .rev()
+            .zip(new[prefix..].chars().rev())
+            .take_while(|(a, b)| a == b)
+            .map(|(c, _)| c.len_utf8())
+            .sum::<usize>();
+        let range = TextRange::new(
+            TextSize::try_from(prefix).ok()?,
+            TextSize::try_from(old.len() - suffix).ok()?,
+        );
+        let mut changes = HashMap::with_capacity_and_hasher(1, FxBuildHasher);
+        changes.insert(
+            uri.to_owned(),
+            vec![TextEdit {
+                range: document.line_index(self).convert(range)?,
+                new_text: new[prefix..new.len() - suffix].into(),
+            }],
+        );
+        Some(CodeAction {
+            title: "Fix all auto-fixable problems".into(),
+            kind: Some(CodeActionKind::SourceFixAll),
+            edit: Some(WorkspaceEdit {
+                changes: Some(changes),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+/// Ranges starting at the same offset are treated as overlapped even if one of them is empty,
+/// otherwise an insertion may be consumed by a replacement that is applied after it.
+fn is_overlapped(a: TextRange, b: TextRange) -> bool {
+    a.start() < b.end() && b.start() < a.end() || a.start() == b.start()
 }
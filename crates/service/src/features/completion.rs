@@ -1,5 +1,5 @@
 use crate::{
-    LanguageService,
+    Features, LanguageService,
     binder::{SymbolKey, SymbolKind, SymbolTable},
     data_set, deprecation,
     document::Document,
@@ -21,6 +21,13 @@ use wat_syntax::{
 impl LanguageService {
     /// Handler for `textDocument/completion` request.
     pub fn completion(&self, params: CompletionParams) -> Option<Vec<CompletionItem>> {
+        let features = self
+            .configs
+            .read()
+            .get(&params.text_document.uri)
+            .map_or(self.global_config.features, |config| {
+                config.unwrap_or_global(self).features
+            });
         let document = self.get_document(params.text_document.uri)?;
         self.with_db(|db| {
             let line_index = document.line_index(db);
@@ -28,7 +35,7 @@ impl LanguageService {
             let token = helpers::syntax::find_token(&root, line_index.convert(params.position)?)?;
 
             let cmp_ctx = get_cmp_ctx(&token)?;
-            let items = get_cmp_list(db, cmp_ctx, &token, document, line_index);
+            let items = get_cmp_list(db, cmp_ctx, &token, document, line_index, &features);
             if items.is_empty() { None } else { Some(items) }
         })
         .flatten()
@@ -67,6 +74,7 @@ fn get_cmp_ctx(token: &SyntaxToken) -> Option<Vec<CmpCtx>> {
                         | SyntaxKind::BLOCK_BLOCK
                         | SyntaxKind::BLOCK_IF
                         | SyntaxKind::BLOCK_LOOP
+                        | SyntaxKind::BLOCK_TRY
                         | SyntaxKind::BLOCK_TRY_TABLE
                 )
             ) && has_leading_l_paren
@@ -91,6 +99,7 @@ fn get_cmp_ctx(token: &SyntaxToken) -> Option<Vec<CmpCtx>> {
                             | SyntaxKind::BLOCK_BLOCK
                             | SyntaxKind::BLOCK_IF
                             | SyntaxKind::BLOCK_LOOP
+                            | SyntaxKind::BLOCK_TRY
                             | SyntaxKind::BLOCK_TRY_TABLE
                     ) | None
                 )
@@ -121,6 +130,7 @@ fn get_cmp_ctx(token: &SyntaxToken) -> Option<Vec<CmpCtx>> {
                                         | SyntaxKind::BLOCK_BLOCK
                                         | SyntaxKind::BLOCK_IF
                                         | SyntaxKind::BLOCK_LOOP
+                                        | SyntaxKind::BLOCK_TRY
                                         | SyntaxKind::BLOCK_TRY_TABLE
                                 )
                             ) && has_leading_l_paren
@@ -153,6 +163,7 @@ fn get_cmp_ctx(token: &SyntaxToken) -> Option<Vec<CmpCtx>> {
                                         | SyntaxKind::BLOCK_BLOCK
                                         | SyntaxKind::BLOCK_IF
                                         | SyntaxKind::BLOCK_LOOP
+                                        | SyntaxKind::BLOCK_TRY
                                         | SyntaxKind::BLOCK_TRY_TABLE
                                 )
                             ) && has_leading_l_paren
@@ -555,7 +566,7 @@ fn add_cmp_ctx_for_immediates(instr_name: &str, node: &SyntaxNode, has_leading_l
             Some(("cont", "new" | "bind")) => ctx.push(CmpCtx::TypeDef(Some(PreferredType::Cont))),
             None => match instr_name {
                 "call" | "return_call" => ctx.push(CmpCtx::Func),
-                "br" | "br_if" | "br_table" | "br_on_null" | "br_on_non_null" => {
+                "br" | "br_if" | "br_table" | "br_on_null" | "br_on_non_null" | "rethrow" => {
                     ctx.push(CmpCtx::Block);
                 }
                 "call_indirect" | "return_call_indirect" => {
@@ -652,6 +663,7 @@ fn get_cmp_list(
     token: &SyntaxToken,
     document: Document,
     line_index: &LineIndex,
+    features: &Features,
 ) -> Vec<CompletionItem> {
     let symbol_table = SymbolTable::of(db, document);
     ctx.into_iter().fold(Vec::with_capacity(2), |mut items, ctx| {
@@ -661,7 +673,12 @@ fn get_cmp_list(
                     data_set::CONST_INSTRS.iter()
                 } else {
                     data_set::INSTR_NAMES.iter()
-                };
+                }
+                .filter(|name| {
+                    data_set::get_instr_proposals(name)
+                        .iter()
+                        .all(|proposal| features.is_enabled(*proposal))
+                });
                 if let Some((left, _)) = token.text().rsplit_once('.') {
                     items.extend(
                         instrs
@@ -1195,6 +1212,7 @@ fn get_cmp_list(
                 items.extend(
                     [
                         "deprecated",
+                        "allow",
                         "custom",
                         "name",
                         "js",
@@ -1,10 +1,12 @@
 use crate::{
     LanguageService,
     binder::{SymbolKey, SymbolKind, SymbolTable},
+    document::Document,
     helpers::{self, LineIndexExt},
+    imex, script,
 };
 use lspt::{Declaration, DeclarationParams, Definition, DefinitionParams, Location, TypeDefinitionParams};
-use wat_syntax::SyntaxKind;
+use wat_syntax::{AmberNode, SyntaxKind, TextSize};
 
 impl LanguageService {
     /// Handler for `textDocument/definition` request.
@@ -43,6 +45,13 @@ impl LanguageService {
                 }
             })
             .and_then(|ref_key| symbol_table.find_def(ref_key))
+            .or_else(|| {
+                script::get_actions(self, document)
+                    .iter()
+                    .find(|action| action.name.contains_inclusive(position))
+                    .and_then(|action| action.def_key)
+                    .and_then(|def_key| symbol_table.symbols.get(def_key))
+            })
             .and_then(|symbol| line_index.convert(helpers::syntax::infer_def_poi(symbol.amber())))
             .map(|range| {
                 Definition::Location(Location {
@@ -50,6 +59,28 @@ impl LanguageService {
                     range,
                 })
             })
+            .or_else(|| self.goto_linked_export(&params.text_document.uri, document, position))
+    }
+
+    /// Find the exported definition in linked module if the position is on an import name.
+    fn goto_linked_export(&self, uri: &str, document: Document, position: TextSize) -> Option<Definition> {
+        let configs = self.configs.read();
+        let linked_modules = self.get_linked_modules(uri, configs.get(uri)?.unwrap_or_global(self));
+        let root = AmberNode::new_root(document.root(self));
+        let (module_name, name) = imex::get_imports(self, document)
+            .iter()
+            .filter_map(|key| imex::get_import_names(root, *key))
+            .find(|(_, name)| name.text_range().contains_inclusive(position))?;
+        let (linked, def_key) = imex::resolve_linked_import(self, module_name, name, &linked_modules)?;
+        let symbol = SymbolTable::of(self, linked.document).symbols.get(def_key?)?;
+        let range = linked
+            .document
+            .line_index(self)
+            .convert(helpers::syntax::infer_def_poi(symbol.amber()))?;
+        Some(Definition::Location(Location {
+            uri: linked.uri.clone(),
+            range,
+        }))
     }
 
     /// Handler for `textDocument/typeDefinition` request.
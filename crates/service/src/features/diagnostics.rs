@@ -1,5 +1,11 @@
 use crate::{LanguageService, checker};
-use lspt::{Diagnostic, DocumentDiagnosticParams, PublishDiagnosticsParams, RelatedFullDocumentDiagnosticReport};
+use lspt::{
+    Diagnostic, DocumentDiagnosticParams, PublishDiagnosticsParams, RelatedFullDocumentDiagnosticReport,
+    WorkspaceDiagnosticParams, WorkspaceDiagnosticReport, WorkspaceDocumentDiagnosticReport,
+    WorkspaceFullDocumentDiagnosticReport, WorkspaceUnchangedDocumentDiagnosticReport,
+};
+use rustc_hash::{FxHashMap, FxHasher};
+use std::hash::{Hash, Hasher};
 
 impl LanguageService {
     /// Handler for `textDocument/diagnostic` request.
@@ -12,6 +18,50 @@ impl LanguageService {
         }
     }
 
+    /// Handler for `workspace/diagnostic` request.
+    ///
+    /// It reports diagnostics of all documents in the service.
+    /// Result ID is derived from diagnostics, so documents whose diagnostics aren't changed
+    /// since previous request will be reported as unchanged.
+    pub fn pull_workspace_diagnostics(&self, params: WorkspaceDiagnosticParams) -> WorkspaceDiagnosticReport {
+        let previous_result_ids = params
+            .previous_result_ids
+            .iter()
+            .map(|previous| (&*previous.uri, &*previous.value))
+            .collect::<FxHashMap<_, _>>();
+        let mut uris = self.documents.read().keys().cloned().collect::<Vec<_>>();
+        uris.sort_unstable();
+        WorkspaceDiagnosticReport {
+            items: uris
+                .into_iter()
+                .filter_map(|uri| {
+                    let items = check_document(self, &uri)?;
+                    let result_id = compute_result_id(&items);
+                    if previous_result_ids.get(&*uri) == Some(&&*result_id) {
+                        Some(WorkspaceDocumentDiagnosticReport::Unchanged(
+                            WorkspaceUnchangedDocumentDiagnosticReport {
+                                kind: "unchanged".into(),
+                                result_id,
+                                uri,
+                                version: None,
+                            },
+                        ))
+                    } else {
+                        Some(WorkspaceDocumentDiagnosticReport::Full(
+                            WorkspaceFullDocumentDiagnosticReport {
+                                kind: "full".into(),
+                                result_id: Some(result_id),
+                                items,
+                                uri,
+                                version: None,
+                            },
+                        ))
+                    }
+                })
+                .collect(),
+        }
+    }
+
     /// Handler for `textDocument/publishDiagnostics` notification.
     pub fn publish_diagnostics(&self, uri: String) -> PublishDiagnosticsParams {
         let diagnostics = get_diagnostics(self, &uri);
@@ -24,6 +74,12 @@ impl LanguageService {
 }
 
 fn get_diagnostics(service: &LanguageService, uri: &str) -> Vec<Diagnostic> {
+    check_document(service, uri).unwrap_or_default()
+}
+
+/// Run checker on the given document.
+/// It returns `None` if the document or its configuration isn't available, or the computation is cancelled.
+pub(super) fn check_document(service: &LanguageService, uri: &str) -> Option<Vec<Diagnostic>> {
     // Some clients like VS Code support pulling configuration per document.
     // In that case, we won't use global configuration,
     // but document-specific configuration may not be available if client doesn't send it yet.
@@ -33,10 +89,15 @@ fn get_diagnostics(service: &LanguageService, uri: &str) -> Vec<Diagnostic> {
         && let Some(config_state) = configs.get(uri)
     {
         let config = config_state.unwrap_or_global(service);
-        service
-            .with_db(|db| checker::check(db, uri, document, config))
-            .unwrap_or_default()
+        let linked_modules = service.get_linked_modules(uri, config);
+        service.with_db(|db| checker::check(db, uri, document, config, &linked_modules))
     } else {
-        vec![]
+        None
     }
 }
+
+fn compute_result_id(diagnostics: &[Diagnostic]) -> String {
+    let mut hasher = FxHasher::default();
+    serde_json::to_string(diagnostics).unwrap_or_default().hash(&mut hasher);
+    format!("{:x}", hasher.finish())
+}
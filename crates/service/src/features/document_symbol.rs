@@ -1,12 +1,13 @@
 use crate::{
     LanguageService,
     binder::{Symbol, SymbolKind, SymbolTable},
-    deprecation,
+    custom_section, deprecation,
     helpers::{self, LineIndexExt},
     mutability,
     types_analyzer::{self, CompositeType},
 };
 use lspt::{DocumentSymbol, DocumentSymbolParams, SymbolKind as LspSymbolKind, SymbolTag};
+use wat_syntax::{SyntaxKind, SyntaxNode};
 
 impl LanguageService {
     /// Handler for `textDocument/documentSymbol` request.
@@ -36,7 +37,7 @@ impl LanguageService {
                 match symbol.kind {
                     SymbolKind::Module => {
                         if let Some(mut module_lsp_symbol) = module_lsp_symbol.replace(DocumentSymbol {
-                            name: render_symbol_name(symbol, db),
+                            name: render_symbol_name(symbol, symbol_table, db),
                             detail: None,
                             kind: LspSymbolKind::Module,
                             tags,
@@ -53,7 +54,7 @@ impl LanguageService {
                     SymbolKind::Func => {
                         if let Some(module_lsp_symbol) = &mut module_lsp_symbol
                             && let Some(mf_lsp_symbol) = mf_lsp_symbol.replace(DocumentSymbol {
-                                name: render_symbol_name(symbol, db),
+                                name: render_symbol_name(symbol, symbol_table, db),
                                 detail: None,
                                 kind: LspSymbolKind::Function,
                                 tags,
@@ -68,7 +69,7 @@ impl LanguageService {
                     SymbolKind::Local => {
                         if let Some(lsp_symbol) = &mut mf_lsp_symbol {
                             lsp_symbol.children.get_or_insert_default().push(DocumentSymbol {
-                                name: render_symbol_name(symbol, db),
+                                name: render_symbol_name(symbol, symbol_table, db),
                                 detail: types_analyzer::extract_type(db, &symbol.green)
                                     .map(|ty| ty.render(db).to_string()),
                                 kind: LspSymbolKind::Variable,
@@ -82,7 +83,7 @@ impl LanguageService {
                     SymbolKind::Type => {
                         if let Some(module_lsp_symbol) = &mut module_lsp_symbol
                             && let Some(mf_lsp_symbol) = mf_lsp_symbol.replace(DocumentSymbol {
-                                name: render_symbol_name(symbol, db),
+                                name: render_symbol_name(symbol, symbol_table, db),
                                 detail: def_types.get(&symbol.key).map(|def_type| match def_type.comp {
                                     CompositeType::Func(..) => "func".into(),
                                     CompositeType::Struct(..) => "struct".into(),
@@ -106,7 +107,7 @@ impl LanguageService {
                                 children.push(mf_lsp_symbol);
                             }
                             children.push(DocumentSymbol {
-                                name: render_symbol_name(symbol, db),
+                                name: render_symbol_name(symbol, symbol_table, db),
                                 detail: types_analyzer::extract_global_type(
                                     db,
                                     symbol_table.get_type_node_of(symbol).green(),
@@ -141,7 +142,7 @@ impl LanguageService {
                                 children.push(mf_lsp_symbol);
                             }
                             children.push(DocumentSymbol {
-                                name: render_symbol_name(symbol, db),
+                                name: render_symbol_name(symbol, symbol_table, db),
                                 detail: None,
                                 kind: LspSymbolKind::Variable,
                                 tags,
@@ -154,7 +155,7 @@ impl LanguageService {
                     SymbolKind::FieldDef => {
                         if let Some(lsp_symbol) = &mut mf_lsp_symbol {
                             lsp_symbol.children.get_or_insert_default().push(DocumentSymbol {
-                                name: render_symbol_name(symbol, db),
+                                name: render_symbol_name(symbol, symbol_table, db),
                                 detail: types_analyzer::resolve_field_type(db, document, symbol.key, symbol.region)
                                     .map(|ty| ty.render(db).to_string()),
                                 kind: LspSymbolKind::Field,
@@ -186,14 +187,69 @@ impl LanguageService {
                 }
                 lsp_symbols.push(module_lsp_symbol);
             }
+
+            let root = SyntaxNode::new_root(document.root(db));
+            let module_ranges = lsp_symbols.iter().map(|symbol| symbol.range).collect::<Vec<_>>();
+            custom_section::get_custom_sections(db, document)
+                .iter()
+                .filter_map(|section| {
+                    let module = section
+                        .parent
+                        .to_node(&root)
+                        .filter(|parent| parent.kind() == SyntaxKind::MODULE)?;
+                    let (name, name_range) = section.name.as_ref()?;
+                    Some((
+                        line_index.convert(module.text_range())?,
+                        DocumentSymbol {
+                            name: name.clone(),
+                            detail: section.placement.as_ref().map(ToString::to_string),
+                            kind: LspSymbolKind::Object,
+                            tags: None,
+                            range: line_index.convert(section.range)?,
+                            selection_range: line_index.convert(*name_range)?,
+                            children: None,
+                        },
+                    ))
+                })
+                .for_each(|(module_range, lsp_symbol)| {
+                    if let Some(module_lsp_symbol) = module_ranges
+                        .iter()
+                        .position(|range| *range == module_range)
+                        .and_then(|i| lsp_symbols.get_mut(i))
+                    {
+                        // annotations at top level of implicit module are outside the module node
+                        let start = &mut module_lsp_symbol.range.start;
+                        if (lsp_symbol.range.start.line, lsp_symbol.range.start.character)
+                            < (start.line, start.character)
+                        {
+                            *start = lsp_symbol.range.start;
+                        }
+                        let end = &mut module_lsp_symbol.range.end;
+                        if (lsp_symbol.range.end.line, lsp_symbol.range.end.character) > (end.line, end.character) {
+                            *end = lsp_symbol.range.end;
+                        }
+                        let children = module_lsp_symbol.children.get_or_insert_default();
+                        let index = children.partition_point(|child| {
+                            (child.range.start.line, child.range.start.character)
+                                < (lsp_symbol.range.start.line, lsp_symbol.range.start.character)
+                        });
+                        children.insert(index, lsp_symbol);
+                    }
+                });
             lsp_symbols
         })
     }
 }
 
-fn render_symbol_name(symbol: &Symbol, db: &dyn salsa::Database) -> String {
+pub(super) fn render_symbol_name<'db>(
+    symbol: &'db Symbol<'db>,
+    symbol_table: &'db SymbolTable<'db>,
+    db: &'db dyn salsa::Database,
+) -> String {
     if let Some(name) = symbol.idx.name {
         name.ident(db).to_string()
+    } else if let Some(annot) = symbol_table.get_name_annot(symbol) {
+        annot.name.clone()
     } else if let Some(num) = symbol.idx.num {
         format!("{} {num}", symbol.kind)
     } else {
@@ -1,5 +1,5 @@
-use crate::{LanguageService, cfa, helpers::LineIndexExt};
-use lspt::{ExecuteCommandParams, Position};
+use crate::{LanguageService, cfa, encoder, helpers::LineIndexExt};
+use lspt::{ExecuteCommandParams, Position, Range};
 use wat_syntax::{AmberNode, SyntaxKind, TextRange};
 
 impl LanguageService {
@@ -21,6 +21,63 @@ impl LanguageService {
                 let cfg = cfa::analyze(self, func.green().clone().into(), func.text_range());
                 Some(serde_json::Value::String(cfg.generate_dot()))
             }
+            // args: [uri: String, moduleId?: u32]
+            // returns `{ binary: number[] }` on success, or `{ error: { range, message } }` on failure
+            "wasmLanguageTools.buildWasm" => {
+                let mut args = params.arguments?.into_iter();
+                let document = self.get_document(args.next()?.as_str()?)?;
+                let module_id = args.next().and_then(|arg| arg.as_u64()).unwrap_or_default() as u32;
+                match encoder::encode(self, document, module_id) {
+                    Ok(encoded) => Some(serde_json::json!({ "binary": encoded.bytes })),
+                    Err(error) => {
+                        let range = document.line_index(self).convert(error.range).unwrap_or_default();
+                        Some(serde_json::json!({
+                            "error": {
+                                "range": serde_json::to_value::<Range>(range).ok()?,
+                                "message": error.message,
+                            }
+                        }))
+                    }
+                }
+            }
+            // args: [uri: String, offset: u32]
+            // returns `Range` of module field or instruction in the disassembled document
+            "wasmLanguageTools.resolveBinaryOffset" => {
+                let mut args = params.arguments?.into_iter();
+                let uri = args.next()?;
+                let uri = uri.as_str()?;
+                let offset = u32::try_from(args.next()?.as_u64()?).ok()?;
+                let range = self.resolve_binary_offset(uri, offset)?;
+                let range = self.get_document(uri)?.line_index(self).convert(range)?;
+                serde_json::to_value::<Range>(range).ok()
+            }
+            // args: [uri: String, funcIdx: u32 | null, offset: u32, moduleId?: u32]
+            // `offset` is relative to the start of function body if `funcIdx` is given,
+            // otherwise it's relative to the start of binary
+            // returns `Range` of instruction, or function if offset doesn't point to any instruction
+            "wasmLanguageTools.gotoCodeOffset" => {
+                let mut args = params.arguments?.into_iter();
+                let document = self.get_document(args.next()?.as_str()?)?;
+                let func_idx = args.next()?.as_u64().map(u32::try_from).transpose().ok()?;
+                let offset = u32::try_from(args.next()?.as_u64()?).ok()?;
+                let module_id = args.next().and_then(|arg| arg.as_u64()).unwrap_or_default() as usize;
+                let layouts = encoder::get_code_layouts(self, document);
+                let funcs = layouts.get(module_id)?.as_ref()?;
+                let (func, offset) = if let Some(func_idx) = func_idx {
+                    (funcs.iter().find(|func| func.idx == func_idx)?, offset)
+                } else {
+                    let func = funcs
+                        .iter()
+                        .find(|func| func.offset <= offset && offset < func.offset + func.size)?;
+                    (func, offset - func.offset)
+                };
+                if offset >= func.size {
+                    return None;
+                }
+                let range = func.instr_at(offset).map_or(func.range, |instr| instr.range);
+                let range = document.line_index(self).convert(range)?;
+                serde_json::to_value::<Range>(range).ok()
+            }
             _ => None,
         }
     }
@@ -1,10 +1,11 @@
 use crate::{
     LanguageService,
     binder::{Symbol, SymbolKey, SymbolKind, SymbolTable},
-    data_set,
+    code_metadata::{self, Payload},
+    const_eval, data_set,
     document::Document,
     helpers::{self, LineIndexExt},
-    mutability,
+    mutability, script,
     types_analyzer::{self, CompositeType, DefType, HeapType, InstrSigResolverCtx, NamedSig, RefType},
 };
 use bumpalo::Bump;
@@ -81,6 +82,16 @@ impl LanguageService {
                         range: line_index.convert(token.text_range()),
                     })
             }
+            SyntaxKind::STRING => script::get_actions(self, document)
+                .iter()
+                .find(|action| action.name.contains_range(token.text_range()))
+                .and_then(|action| action.def_key)
+                .and_then(|def_key| def_key.to_node(&root))
+                .and_then(|node| create_symbol_hover(self, uri, document, symbol_table, &node))
+                .map(|contents| Hover {
+                    contents: HoverContents::MarkupContent(contents),
+                    range: line_index.convert(token.text_range()),
+                }),
             SyntaxKind::TYPE_KEYWORD => {
                 let ty = token.text();
                 data_set::get_value_type_description(token.text()).map(|doc| Hover {
@@ -98,6 +109,15 @@ impl LanguageService {
                 } else {
                     node
                 };
+                if node.kind() == SyntaxKind::OFFSET {
+                    return const_eval::eval(self, document, node.amber()).map(|value| Hover {
+                        contents: HoverContents::MarkupContent(MarkupContent {
+                            kind: MarkupKind::Markdown,
+                            value: format!("```wat\n(offset {value})\n```"),
+                        }),
+                        range: line_index.convert(token.text_range()),
+                    });
+                }
                 create_symbol_hover(self, uri, document, symbol_table, &node).map(|contents| Hover {
                     contents: HoverContents::MarkupContent(contents),
                     range: line_index.convert(if matches!(token.text(), "mut" | "ref") {
@@ -189,6 +209,20 @@ impl LanguageService {
                         );
                     }
 
+                    // instruction at top level of constant expression
+                    if let Some(value) = parent
+                        .parent()
+                        .filter(|node| {
+                            matches!(
+                                node.kind(),
+                                SyntaxKind::MODULE_FIELD_GLOBAL | SyntaxKind::OFFSET | SyntaxKind::ELEM_EXPR
+                            )
+                        })
+                        .and_then(|node| const_eval::eval(self, document, node.amber()))
+                    {
+                        let _ = write!(contents, "\n\n---\n\n**Value**: `{value}`");
+                    }
+
                     Hover {
                         contents: HoverContents::MarkupContent(MarkupContent {
                             kind: MarkupKind::Markdown,
@@ -198,6 +232,17 @@ impl LanguageService {
                     }
                 })
             }
+            SyntaxKind::ANNOT_START | SyntaxKind::ANNOT_ELEM | SyntaxKind::ANNOT_END => {
+                code_metadata::get_code_metadata(self, document)
+                    .iter()
+                    .find(|metadata| metadata.range.contains_range(token.text_range()))
+                    .and_then(|metadata| {
+                        metadata.payload.as_ref().ok().map(|payload| Hover {
+                            contents: HoverContents::MarkupContent(create_code_metadata_hover(payload)),
+                            range: line_index.convert(metadata.range),
+                        })
+                    })
+            }
             _ => None,
         }
     }
@@ -214,10 +259,10 @@ fn create_symbol_hover(
         .symbols
         .get(SymbolKey::from(node))
         .and_then(|symbol| match symbol.kind {
-            SymbolKind::Param | SymbolKind::Local => Some(create_param_or_local_hover(db, symbol)),
+            SymbolKind::Param | SymbolKind::Local => Some(create_param_or_local_hover(db, symbol_table, symbol)),
             SymbolKind::LocalRef => symbol_table
                 .find_def(symbol.key)
-                .map(|symbol| create_param_or_local_hover(db, symbol)),
+                .map(|symbol| create_param_or_local_hover(db, symbol_table, symbol)),
             SymbolKind::Func => Some(create_func_hover(db, document, symbol_table, symbol)),
             SymbolKind::Call => symbol_table
                 .find_def(symbol.key)
@@ -250,14 +295,14 @@ fn create_symbol_hover(
             SymbolKind::TagRef => symbol_table
                 .find_def(symbol.key)
                 .map(|symbol| create_tag_def_hover(db, document, symbol_table, symbol)),
-            SymbolKind::DataDef => Some(create_data_def_hover(db, symbol)),
+            SymbolKind::DataDef => Some(create_data_def_hover(db, symbol_table, symbol)),
             SymbolKind::DataRef => symbol_table
                 .find_def(symbol.key)
-                .map(|symbol| create_data_def_hover(db, symbol)),
-            SymbolKind::ElemDef => Some(create_elem_def_hover(db, symbol)),
+                .map(|symbol| create_data_def_hover(db, symbol_table, symbol)),
+            SymbolKind::ElemDef => Some(create_elem_def_hover(db, symbol_table, symbol)),
             SymbolKind::ElemRef => symbol_table
                 .find_def(symbol.key)
-                .map(|symbol| create_elem_def_hover(db, symbol)),
+                .map(|symbol| create_elem_def_hover(db, symbol_table, symbol)),
             SymbolKind::Module => None,
         })
 }
@@ -273,7 +318,7 @@ fn create_func_hover(
         "```wat\n{}\n```",
         types_analyzer::render_func_header(
             db,
-            symbol.idx.name,
+            symbol_table.get_header_name(db, symbol),
             NamedSig::from_func(db, document, symbol_table.get_type_node_of(symbol)),
         )
     );
@@ -287,7 +332,7 @@ fn create_func_hover(
     }
 }
 
-fn create_param_or_local_hover(db: &dyn salsa::Database, symbol: &Symbol) -> MarkupContent {
+fn create_param_or_local_hover(db: &dyn salsa::Database, symbol_table: &SymbolTable, symbol: &Symbol) -> MarkupContent {
     let mut content = '('.to_string();
     match symbol.kind {
         SymbolKind::Param => {
@@ -298,7 +343,7 @@ fn create_param_or_local_hover(db: &dyn salsa::Database, symbol: &Symbol) -> Mar
         }
         _ => {}
     }
-    if let Some(name) = symbol.idx.name {
+    if let Some(name) = symbol_table.get_header_name(db, symbol) {
         content.push(' ');
         content.push_str(name.ident(db));
     }
@@ -320,7 +365,7 @@ fn create_global_def_hover(
     symbol: &Symbol,
 ) -> MarkupContent {
     let mut content = "(global".to_string();
-    if let Some(name) = symbol.idx.name {
+    if let Some(name) = symbol_table.get_header_name(db, symbol) {
         content.push(' ');
         content.push_str(name.ident(db));
     }
@@ -339,15 +384,23 @@ fn create_global_def_hover(
         content.push(')');
     }
     content.push(')');
+    let mut value = format!("```wat\n{content}\n```");
+    if let Some(init) = const_eval::get_global_values(db, document).get(&symbol.key) {
+        let _ = write!(
+            value,
+            "\n---\n**{}**: `{init}`",
+            if mutable { "Initial value" } else { "Value" }
+        );
+    }
     MarkupContent {
         kind: MarkupKind::Markdown,
-        value: format!("```wat\n{content}\n```"),
+        value,
     }
 }
 
 fn create_memory_def_hover(db: &dyn salsa::Database, symbol_table: &SymbolTable, symbol: &Symbol) -> MarkupContent {
     let mut content = "(memory".to_string();
-    if let Some(name) = symbol.idx.name {
+    if let Some(name) = symbol_table.get_header_name(db, symbol) {
         content.push(' ');
         content.push_str(name.ident(db));
     }
@@ -370,7 +423,7 @@ fn create_memory_def_hover(db: &dyn salsa::Database, symbol_table: &SymbolTable,
 
 fn create_table_def_hover(db: &dyn salsa::Database, symbol_table: &SymbolTable, symbol: &Symbol) -> MarkupContent {
     let mut content = "(table".to_string();
-    if let Some(name) = symbol.idx.name {
+    if let Some(name) = symbol_table.get_header_name(db, symbol) {
         content.push(' ');
         content.push_str(name.ident(db));
     }
@@ -403,7 +456,7 @@ fn create_type_def_hover(
     let def_types = types_analyzer::get_def_types(db, document);
     let mut content = "(type".to_string();
     let mut appendix = None;
-    if let Some(name) = symbol.idx.name {
+    if let Some(name) = symbol_table.get_header_name(db, symbol) {
         content.push(' ');
         content.push_str(name.ident(db));
     }
@@ -521,9 +574,9 @@ fn create_tag_def_hover(
     }
 }
 
-fn create_data_def_hover(db: &dyn salsa::Database, symbol: &Symbol) -> MarkupContent {
+fn create_data_def_hover(db: &dyn salsa::Database, symbol_table: &SymbolTable, symbol: &Symbol) -> MarkupContent {
     let mut content = "(data".to_string();
-    if let Some(name) = symbol.idx.name {
+    if let Some(name) = symbol_table.get_header_name(db, symbol) {
         let _ = write!(&mut content, " {}", name.ident(db));
     }
     content.push(')');
@@ -533,9 +586,9 @@ fn create_data_def_hover(db: &dyn salsa::Database, symbol: &Symbol) -> MarkupCon
     }
 }
 
-fn create_elem_def_hover(db: &dyn salsa::Database, symbol: &Symbol) -> MarkupContent {
+fn create_elem_def_hover(db: &dyn salsa::Database, symbol_table: &SymbolTable, symbol: &Symbol) -> MarkupContent {
     let mut content = "(elem".to_string();
-    if let Some(name) = symbol.idx.name {
+    if let Some(name) = symbol_table.get_header_name(db, symbol) {
         let _ = write!(&mut content, " {}", name.ident(db));
     }
     content.push(')');
@@ -545,6 +598,48 @@ fn create_elem_def_hover(db: &dyn salsa::Database, symbol: &Symbol) -> MarkupCon
     }
 }
 
+fn create_code_metadata_hover(payload: &Payload) -> MarkupContent {
+    let value = match payload {
+        Payload::BranchHint(true) => {
+            "**Branch hint**: likely taken\n\nThe branch is expected to be taken in most executions.".into()
+        }
+        Payload::BranchHint(false) => {
+            "**Branch hint**: unlikely taken\n\nThe branch is expected to be rarely taken.".into()
+        }
+        Payload::InstrFreq(freq) => {
+            format!("**Instruction frequency**: `{freq}`\n\nHow often the following instruction is expected to run.")
+        }
+        Payload::CallTargets(targets) => {
+            let mut content = "**Call targets**\n\nExpected callees of the following indirect call:\n".to_string();
+            targets.iter().for_each(|target| {
+                let _ = write!(content, "\n- `{}` with frequency `{}`", target.idx, target.freq);
+            });
+            content
+        }
+        Payload::CompilationPriority {
+            compilation,
+            optimization,
+            run_once,
+        } => {
+            let mut content = "**Compilation priority**\n".to_string();
+            if let Some(compilation) = compilation {
+                let _ = write!(content, "\n- Compilation: `{compilation}`");
+            }
+            if let Some(optimization) = optimization {
+                let _ = write!(content, "\n- Optimization: `{optimization}`");
+            }
+            if *run_once {
+                content.push_str("\n- Run once");
+            }
+            content
+        }
+    };
+    MarkupContent {
+        kind: MarkupKind::Markdown,
+        value,
+    }
+}
+
 fn format_op_code(code: u32) -> String {
     if code >> 16 > 0 {
         format!(
@@ -1,11 +1,13 @@
 use crate::{
     LanguageService,
     binder::{SymbolKind, SymbolTable},
+    encoder,
     helpers::{self, LineIndexExt},
     idx::Idx,
     types_analyzer,
 };
 use lspt::{InlayHint, InlayHintKind, InlayHintLabel, InlayHintParams};
+use rustc_hash::FxHashSet;
 use wat_syntax::SyntaxKind;
 
 impl LanguageService {
@@ -126,6 +128,7 @@ impl LanguageService {
                                     match symbol.key.kind() {
                                         SyntaxKind::BLOCK_IF => "if",
                                         SyntaxKind::BLOCK_LOOP => "loop",
+                                        SyntaxKind::BLOCK_TRY => "try",
                                         SyntaxKind::BLOCK_TRY_TABLE => "try_table",
                                         _ => "block",
                                     },
@@ -221,6 +224,33 @@ impl LanguageService {
                     _ => {}
                 }
             }
+            if options.code_offset {
+                let mut visited = FxHashSet::default();
+                encoder::get_code_layouts(db, document)
+                    .iter()
+                    .flatten()
+                    .flatten()
+                    .for_each(|func| {
+                        // blocks have multiple entries, only the first one is the block header
+                        func.instrs
+                            .iter()
+                            .filter(|instr| range.contains_range(instr.range) && visited.insert(instr.range))
+                            .for_each(|instr| {
+                                if let Some(position) = line_index.convert(instr.range.start()) {
+                                    inlay_hints.push(InlayHint {
+                                        position,
+                                        label: InlayHintLabel::String(format!("@0x{:x}", func.offset + instr.offset)),
+                                        kind: None,
+                                        text_edits: None,
+                                        tooltip: None,
+                                        padding_left: None,
+                                        padding_right: Some(true),
+                                        data: None,
+                                    });
+                                }
+                            });
+                    });
+            }
             Some(inlay_hints)
         })
         .flatten()
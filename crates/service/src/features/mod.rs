@@ -17,6 +17,7 @@ mod selection_range;
 mod semantic_tokens;
 mod signature_help;
 mod type_hierarchy;
+mod workspace_symbol;
 
 pub(crate) use self::semantic_tokens::{SemanticTokenType, SemanticTokenTypes};
 use crate::{document::Document, helpers::LineIndexExt};
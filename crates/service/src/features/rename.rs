@@ -1,7 +1,7 @@
 use crate::{
     LanguageService,
-    binder::{IdxKind, SymbolKey, SymbolKind, SymbolTable},
-    helpers::LineIndexExt,
+    binder::{IdxKind, Symbol, SymbolKey, SymbolKind, SymbolTable},
+    helpers::{self, LineIndexExt},
     idx::InternIdent,
 };
 use lspt::{PrepareRenameParams, PrepareRenameResult, RenameParams, TextEdit, WorkspaceEdit};
@@ -9,23 +9,35 @@ use rustc_hash::FxBuildHasher;
 use std::collections::HashMap;
 use wat_parser::is_id_char;
 use wat_syntax::{
-    SyntaxKind, SyntaxNode,
+    SyntaxKind, SyntaxNode, SyntaxToken, TextRange,
     ast::{AstNode, ExternType},
 };
 
 impl LanguageService {
     /// Handler for `textDocument/prepareRename` request.
     pub fn prepare_rename(&self, params: PrepareRenameParams) -> Option<PrepareRenameResult> {
+        if self.is_read_only(&params.text_document.uri) {
+            return None;
+        }
         let document = self.get_document(params.text_document.uri)?;
         let line_index = document.line_index(self);
         let root = SyntaxNode::new_root(document.root(self));
-        let token = super::find_meaningful_token(self, document, &root, params.position)
-            .filter(|token| token.kind() == SyntaxKind::IDENT)?;
+        let token = super::find_meaningful_token(self, document, &root, params.position)?;
+        match token.kind() {
+            SyntaxKind::IDENT => {}
+            SyntaxKind::ANNOT_ELEM => {
+                find_annotated_symbol(SymbolTable::of(self, document), token.text_range())?;
+            }
+            _ => return None,
+        }
         line_index.convert(token.text_range()).map(PrepareRenameResult::Range)
     }
 
     /// Handler for `textDocument/rename` request.
     pub fn rename(&self, params: RenameParams) -> Option<WorkspaceEdit> {
+        if self.is_read_only(&params.text_document.uri) {
+            return None;
+        }
         let mut new_name = params.new_name;
         if !new_name.chars().all(is_id_char) || new_name.strip_prefix('$').is_some_and(str::is_empty) {
             match new_name.as_bytes() {
@@ -48,28 +60,43 @@ impl LanguageService {
 
         let document = self.get_document(&params.text_document.uri)?;
         let root = SyntaxNode::new_root(document.root(self));
-        let token = super::find_meaningful_token(self, document, &root, params.position)
-            .filter(|token| token.kind() == SyntaxKind::IDENT)?;
+        let token = super::find_meaningful_token(self, document, &root, params.position)?;
         let line_index = document.line_index(self);
         let symbol_table = SymbolTable::of(self, document);
-
-        let old_name = InternIdent::new(self, token.text());
-        let parent = token.parent();
-        let symbol = if ExternType::can_cast(parent.kind()) {
-            let range = parent.text_range();
-            symbol_table.symbols.iter().find(|symbol| {
-                matches!(
-                    symbol.kind,
-                    SymbolKind::Func
-                        | SymbolKind::GlobalDef
-                        | SymbolKind::MemoryDef
-                        | SymbolKind::TableDef
-                        | SymbolKind::TagDef
-                ) && symbol_table.get_type_node_of(symbol).text_range() == range
-            })?
+        // string literal of `(@name "...")` annotation will be quoted without `$`
+        let new_annot = if let Some(quoted) = new_name.strip_prefix("$\"") {
+            format!("\"{quoted}")
         } else {
-            symbol_table.symbols.get(SymbolKey::from(&parent))?
+            format!("\"{}\"", new_name.trim_start_matches('$').replace('\\', "\\\\"))
+        };
+
+        let (symbol, old_name) = match token.kind() {
+            SyntaxKind::IDENT => (
+                find_symbol_of_ident(symbol_table, &token)?,
+                Some(InternIdent::new(self, token.text())),
+            ),
+            SyntaxKind::ANNOT_ELEM => {
+                let symbol = find_annotated_symbol(symbol_table, token.text_range())?;
+                (symbol, symbol.idx.name)
+            }
+            _ => return None,
+        };
+        let mut changes = HashMap::with_capacity_and_hasher(1, FxBuildHasher);
+        let Some(old_name) = old_name else {
+            // only name annotation exists, so there's nothing to refer to it
+            changes.insert(
+                params.text_document.uri,
+                vec![TextEdit {
+                    range: line_index.convert(token.text_range())?,
+                    new_text: new_annot,
+                }],
+            );
+            return Some(WorkspaceEdit {
+                changes: Some(changes),
+                ..Default::default()
+            });
         };
+        let old_plain_name = helpers::ident_to_name(old_name.ident(self));
         let idx_kind = IdxKind::from(symbol.kind);
         let text_edits = symbol_table
             .symbols
@@ -121,22 +148,31 @@ impl LanguageService {
                     }
                 }
             })
-            .filter_map(|sym| {
-                match sym.key.kind() {
+            .flat_map(|sym| {
+                let ident = match sym.key.kind() {
                     SyntaxKind::MODULE_FIELD_IMPORT | SyntaxKind::IMPORT_ITEM => symbol_table.get_type_node_of(sym),
                     _ => sym.amber(),
                 }
                 .tokens_by_kind(SyntaxKind::IDENT)
                 .next()
                 .and_then(|token| line_index.convert(token.text_range()))
-            })
-            .map(|range| TextEdit {
-                range,
-                new_text: new_name.clone(),
+                .map(|range| TextEdit {
+                    range,
+                    new_text: new_name.clone(),
+                });
+                // keep name annotation in sync with identifier,
+                // unless it was intentionally different
+                let annot = symbol_table
+                    .get_name_annot(sym)
+                    .filter(|annot| annot.range == token.text_range() || annot.name == old_plain_name)
+                    .and_then(|annot| line_index.convert(annot.range))
+                    .map(|range| TextEdit {
+                        range,
+                        new_text: new_annot.clone(),
+                    });
+                ident.into_iter().chain(annot)
             })
             .collect();
-
-        let mut changes = HashMap::with_capacity_and_hasher(1, FxBuildHasher);
         changes.insert(params.text_document.uri, text_edits);
         Some(WorkspaceEdit {
             changes: Some(changes),
@@ -144,3 +180,32 @@ impl LanguageService {
         })
     }
 }
+
+fn find_symbol_of_ident<'db>(symbol_table: &'db SymbolTable<'db>, token: &SyntaxToken) -> Option<&'db Symbol<'db>> {
+    let parent = token.parent();
+    if ExternType::can_cast(parent.kind()) {
+        let range = parent.text_range();
+        symbol_table.symbols.iter().find(|symbol| {
+            matches!(
+                symbol.kind,
+                SymbolKind::Func
+                    | SymbolKind::GlobalDef
+                    | SymbolKind::MemoryDef
+                    | SymbolKind::TableDef
+                    | SymbolKind::TagDef
+            ) && symbol_table.get_type_node_of(symbol).text_range() == range
+        })
+    } else {
+        symbol_table.symbols.get(SymbolKey::from(&parent))
+    }
+}
+
+/// Find definition symbol whose `(@name "...")` annotation is at the given range.
+fn find_annotated_symbol<'db>(symbol_table: &'db SymbolTable<'db>, range: TextRange) -> Option<&'db Symbol<'db>> {
+    symbol_table.symbols.iter().find(|symbol| {
+        symbol.key.text_range().contains_range(range)
+            && symbol_table
+                .get_name_annot(symbol)
+                .is_some_and(|annot| annot.range == range)
+    })
+}
@@ -0,0 +1,105 @@
+use super::document_symbol::render_symbol_name;
+use crate::{
+    LanguageService,
+    binder::{SymbolKind, SymbolTable},
+    deprecation,
+    helpers::LineIndexExt,
+    imex,
+};
+use lspt::{Location, SymbolKind as LspSymbolKind, SymbolTag, WorkspaceSymbol, WorkspaceSymbolParams};
+use rustc_hash::FxHashMap;
+
+impl LanguageService {
+    /// Handler for `workspace/symbol` request.
+    ///
+    /// Module fields in all committed documents are matched fuzzily against
+    /// their identifiers, name annotations and export names.
+    pub fn workspace_symbol(&self, params: WorkspaceSymbolParams) -> Option<Vec<WorkspaceSymbol>> {
+        let mut documents = self
+            .documents
+            .read()
+            .iter()
+            .map(|(uri, document)| (uri.clone(), *document))
+            .collect::<Vec<_>>();
+        documents.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        self.with_db(|db| {
+            documents
+                .into_iter()
+                .flat_map(|(uri, document)| {
+                    let line_index = document.line_index(db);
+                    let symbol_table = SymbolTable::of(db, document);
+                    let deprecation = deprecation::get_deprecation(db, document);
+                    let mut export_names = FxHashMap::<_, Vec<_>>::default();
+                    imex::get_exports(db, document)
+                        .values()
+                        .flatten()
+                        .for_each(|export| export_names.entry(export.def_key).or_default().push(&*export.name));
+                    symbol_table
+                        .symbols
+                        .iter()
+                        .filter_map(|symbol| {
+                            let kind = match symbol.kind {
+                                SymbolKind::Module => LspSymbolKind::Module,
+                                SymbolKind::Func => LspSymbolKind::Function,
+                                SymbolKind::Type => LspSymbolKind::Class,
+                                SymbolKind::GlobalDef
+                                | SymbolKind::MemoryDef
+                                | SymbolKind::TableDef
+                                | SymbolKind::TagDef
+                                | SymbolKind::DataDef
+                                | SymbolKind::ElemDef => LspSymbolKind::Variable,
+                                _ => return None,
+                            };
+                            let exports = export_names.get(&symbol.key).map(Vec::as_slice).unwrap_or_default();
+                            let ident = symbol.idx.name.map(|name| name.ident(db));
+                            let annot_name = symbol_table.get_name_annot(symbol).map(|annot| &*annot.name);
+                            if !params.query.trim().is_empty()
+                                && !ident
+                                    .iter()
+                                    .map(|ident| ident.as_str())
+                                    .chain(annot_name)
+                                    .chain(exports.iter().map(|name| name.trim_matches('"')))
+                                    .any(|candidate| fuzzy_match(&params.query, candidate))
+                            {
+                                return None;
+                            }
+                            // prefer export name over numeric idx if both identifier and name annotation are absent
+                            let name = match (ident, annot_name, exports.first()) {
+                                (None, None, Some(export)) => export.to_string(),
+                                _ => render_symbol_name(symbol, symbol_table, db),
+                            };
+                            Some(WorkspaceSymbol {
+                                name,
+                                kind,
+                                tags: deprecation
+                                    .contains_key(&symbol.key)
+                                    .then(|| vec![SymbolTag::Deprecated]),
+                                container_name: symbol_table
+                                    .symbols
+                                    .get(symbol.region)
+                                    .filter(|module| module.kind == SymbolKind::Module)
+                                    .map(|module| render_symbol_name(module, symbol_table, db)),
+                                location: Location {
+                                    uri: uri.clone(),
+                                    range: line_index.convert(symbol.key.text_range())?,
+                                }
+                                .into(),
+                                data: None,
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect()
+        })
+    }
+}
+
+/// Check if all characters of query appear in candidate in order, ignoring case.
+fn fuzzy_match(query: &str, candidate: &str) -> bool {
+    let mut candidate = candidate.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .all(|q| candidate.any(|c| c == q))
+}
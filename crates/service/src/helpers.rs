@@ -2,7 +2,7 @@ use crate::binder::{Symbol, SymbolTable};
 use line_index::{LineIndex, WideEncoding, WideLineCol};
 use lspt::{Position, Range};
 use std::{borrow::Cow, num::ParseIntError};
-use wat_syntax::{NodeOrToken, SyntaxKind, TextRange, TextSize};
+use wat_syntax::{AmberNode, NodeOrToken, SyntaxKind, TextRange, TextSize};
 
 pub use self::arena::{BumpCollectionsExt, BumpHashMap, BumpHashSet};
 
@@ -68,6 +68,7 @@ pub fn is_stack_polymorphic(instr_name: &str) -> bool {
             | "return_call_ref"
             | "throw"
             | "throw_ref"
+            | "rethrow"
     )
 }
 
@@ -95,6 +96,169 @@ fn clean_underscores(s: &str) -> Cow<'_, str> {
     }
 }
 
+/// Decode string literal into bytes, and record source offset of each byte.
+///
+/// There's an extra offset at the end which points to the closing quote.
+pub(crate) fn decode_string(text: &str, start: TextSize, bytes: &mut Vec<u8>, offsets: &mut Vec<TextSize>) {
+    let content = text.strip_prefix('"').unwrap_or(text);
+    let content = content.strip_suffix('"').unwrap_or(content);
+    let start = start + TextSize::new(1);
+    let mut chars = content.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let offset = start + TextSize::new(i as u32);
+        if c != '\\' {
+            let mut buf = [0; 4];
+            let encoded = c.encode_utf8(&mut buf);
+            bytes.extend_from_slice(encoded.as_bytes());
+            offsets.extend((0..encoded.len()).map(|j| offset + TextSize::new(j as u32)));
+            continue;
+        }
+        let byte = match chars.next() {
+            Some((_, 't')) => b'\t',
+            Some((_, 'n')) => b'\n',
+            Some((_, 'r')) => b'\r',
+            Some((_, '"')) => b'"',
+            Some((_, '\'')) => b'\'',
+            Some((_, '\\')) => b'\\',
+            Some((j, 'u')) => {
+                let rest = &content[j + 1..];
+                if let Some(c) = rest
+                    .strip_prefix('{')
+                    .and_then(|rest| rest.split_once('}'))
+                    .and_then(|(hex, _)| u32::from_str_radix(&hex.replace('_', ""), 16).ok())
+                    .and_then(char::from_u32)
+                {
+                    let mut buf = [0; 4];
+                    let encoded = c.encode_utf8(&mut buf);
+                    bytes.extend_from_slice(encoded.as_bytes());
+                    offsets.extend((0..encoded.len()).map(|_| offset));
+                    while chars.next_if(|(_, c)| *c != '}').is_some() {}
+                    chars.next();
+                }
+                continue;
+            }
+            Some((_, high)) => {
+                let Some((high, low)) = high.to_digit(16).zip(
+                    chars
+                        .next_if(|(_, c)| c.is_ascii_hexdigit())
+                        .and_then(|(_, c)| c.to_digit(16)),
+                ) else {
+                    continue;
+                };
+                (high * 16 + low) as u8
+            }
+            None => break,
+        };
+        bytes.push(byte);
+        offsets.push(offset);
+    }
+    offsets.push(start + TextSize::new(content.len() as u32));
+}
+
+/// Get the name that identifier stands for, without `$` prefix and quotes.
+pub(crate) fn ident_to_name(ident: &str) -> String {
+    let ident = ident.strip_prefix('$').unwrap_or(ident);
+    if ident.starts_with('"') {
+        let mut bytes = Vec::with_capacity(ident.len());
+        decode_string(ident, TextSize::new(0), &mut bytes, &mut vec![]);
+        String::from_utf8_lossy(&bytes).into_owned()
+    } else {
+        ident.to_string()
+    }
+}
+
+/// Resolve path relative to the given document URI.
+///
+/// If the path is already an absolute URI, it will be returned as is.
+pub(crate) fn resolve_uri(base: &str, path: &str) -> String {
+    if path.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.len() > 1
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    }) {
+        return path.to_string();
+    }
+    // keep scheme and authority, such as `file://` or `untitled:`
+    let prefix_len = if let Some(i) = base.find("://") {
+        base[i + 3..].find('/').map_or(base.len(), |j| i + 3 + j)
+    } else {
+        base.find(':').map_or(0, |i| i + 1)
+    };
+    let (prefix, base_path) = base.split_at(prefix_len);
+    let mut segments = Vec::new();
+    if !path.starts_with('/') {
+        segments.extend(base_path.split('/'));
+        segments.pop();
+    }
+    path.split('/').for_each(|segment| match segment {
+        "." => {}
+        ".." => {
+            if segments.len() > 1 {
+                segments.pop();
+            }
+        }
+        _ => segments.push(segment),
+    });
+    format!("{prefix}{}", segments.join("/"))
+}
+
+/// Get log2 of natural alignment if it's a memory instruction.
+pub(crate) fn natural_align(name: &str) -> Option<u32> {
+    match name {
+        "memory.atomic.notify" | "memory.atomic.wait32" => return Some(2),
+        "memory.atomic.wait64" => return Some(3),
+        _ => {}
+    }
+    let (ty, action) = name.split_once('.')?;
+    let action = action.strip_prefix("atomic.").unwrap_or(action);
+    let rest = action
+        .strip_prefix("load")
+        .or_else(|| action.strip_prefix("store"))
+        .or_else(|| action.strip_prefix("rmw"))?;
+    match rest.split(['_', '.']).next().unwrap_or_default() {
+        "8" => Some(0),
+        "16" => Some(1),
+        "32" => Some(2),
+        "64" | "8x8" | "16x4" | "32x2" => Some(3),
+        "" => match ty {
+            "i32" | "f32" => Some(2),
+            "i64" | "f64" => Some(3),
+            "v128" => Some(4),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Check if memory page size is allowed by the custom page sizes proposal.
+pub(crate) fn is_valid_mem_page_size(page_size: u32) -> bool {
+    page_size == 1 || page_size == 65536
+}
+
+/// Get page size of memory type in bytes, which is 65536 if omitted.
+///
+/// It returns `None` if page size is invalid, which is reported by `mem-type`.
+pub(crate) fn mem_page_size(mem_type: AmberNode) -> Option<u64> {
+    if let Some(page_size) = mem_type.children_by_kind(SyntaxKind::MEM_PAGE_SIZE).next() {
+        let page_size = parse_u32(page_size.tokens_by_kind(SyntaxKind::UNSIGNED_INT).next()?.text()).ok()?;
+        is_valid_mem_page_size(page_size).then_some(page_size as u64)
+    } else {
+        Some(65536)
+    }
+}
+
+/// Parse minimum and optional maximum of memory or table limits.
+pub(crate) fn parse_limits(limits: AmberNode) -> Option<(u64, Option<u64>)> {
+    let mut uints = limits.tokens_by_kind(SyntaxKind::UNSIGNED_INT);
+    let min = parse_u64(uints.next()?.text()).ok()?;
+    let max = match uints.next() {
+        Some(max) => Some(parse_u64(max.text()).ok()?),
+        None => None,
+    };
+    Some((min, max))
+}
+
 pub fn get_doc_comment(def_symbol: &Symbol, symbol_table: &SymbolTable) -> Option<String> {
     let node = def_symbol.amber();
     symbol_table.symbols.get(def_symbol.region).map(|module| {
@@ -182,6 +346,9 @@ pub(crate) mod syntax {
                     | SyntaxKind::BLOCK_IF_THEN
                     | SyntaxKind::BLOCK_IF_ELSE
                     | SyntaxKind::BLOCK_TRY_TABLE
+                    | SyntaxKind::BLOCK_TRY_DO
+                    | SyntaxKind::BLOCK_TRY_CATCH
+                    | SyntaxKind::BLOCK_TRY_CATCH_ALL
             )
         })
     }
@@ -1,5 +1,7 @@
 use crate::{
+    LanguageService,
     binder::{SymbolKey, SymbolTable},
+    config::ServiceConfig,
     document::Document,
     helpers,
 };
@@ -30,7 +32,7 @@ pub(crate) type ExportMap = FxHashMap<SyntaxNodePtr, Box<[Export]>>;
 pub(crate) fn get_exports(db: &dyn salsa::Database, document: Document) -> ExportMap {
     let symbol_table = SymbolTable::of(db, document);
     AmberNode::new_root(document.root(db))
-        .children()
+        .children_by_kind(SyntaxKind::MODULE)
         .map(|module| {
             let mut exports = Vec::new();
             module.children().for_each(|module_field| {
@@ -70,3 +72,76 @@ pub(crate) struct Export {
     pub name: String,
     pub range: TextRange,
 }
+
+/// Documents that provide modules for imports, keyed by module name without double quotes.
+pub(crate) type LinkedModules = FxHashMap<String, LinkedModule>;
+
+#[derive(Clone, Debug)]
+pub(crate) struct LinkedModule {
+    pub uri: String,
+    pub document: Document,
+}
+
+impl LanguageService {
+    /// Find documents of modules that imports of the given document refer to,
+    /// according to the `imports` configuration.
+    ///
+    /// Documents that aren't committed to the service are skipped.
+    pub(crate) fn get_linked_modules(&self, uri: &str, config: &ServiceConfig) -> LinkedModules {
+        config
+            .imports
+            .iter()
+            .filter_map(|(module_name, path)| {
+                let target = helpers::resolve_uri(uri, path);
+                if target == uri {
+                    return None;
+                }
+                let document = self.get_document(&target)?;
+                Some((module_name.clone(), LinkedModule { uri: target, document }))
+            })
+            .collect()
+    }
+}
+
+/// Get module name and item name of an import.
+///
+/// The given key can be a standalone import, an item of compact import, or a module field with inline import.
+pub(crate) fn get_import_names<'a>(root: AmberNode<'a>, key: SymbolKey) -> Option<(AmberNode<'a>, AmberNode<'a>)> {
+    let range = key.text_range();
+    let module_field = root.child_at_range(range)?.child_at_range(range)?;
+    let (import, item) = match key.kind() {
+        SyntaxKind::MODULE_FIELD_IMPORT => (module_field, module_field),
+        SyntaxKind::IMPORT_ITEM => (module_field, module_field.child_at_range(range)?),
+        _ => {
+            let import = module_field.children_by_kind(SyntaxKind::IMPORT).next()?;
+            (import, import)
+        }
+    };
+    Some((
+        import.children_by_kind(SyntaxKind::MODULE_NAME).next()?,
+        item.children_by_kind(SyntaxKind::NAME).next()?,
+    ))
+}
+
+/// Find the exported definition in linked module that the import refers to.
+///
+/// Only the first module in the linked document is considered.
+/// It returns the linked module with `None` if the module exists but the export doesn't.
+pub(crate) fn resolve_linked_import<'a>(
+    db: &dyn salsa::Database,
+    module_name: AmberNode,
+    name: AmberNode,
+    linked_modules: &'a LinkedModules,
+) -> Option<(&'a LinkedModule, Option<SymbolKey>)> {
+    let module_name = module_name.green().to_string();
+    let linked = linked_modules.get(module_name.trim_matches('"'))?;
+    let module = AmberNode::new_root(linked.document.root(db))
+        .children_by_kind(SyntaxKind::MODULE)
+        .next()?;
+    let name = name.green().to_string();
+    let def_key = get_exports(db, linked.document)
+        .get(&module.to_ptr())
+        .and_then(|exports| exports.iter().find(|export| export.name == name))
+        .map(|export| export.def_key);
+    Some((linked, def_key))
+}
@@ -1,21 +1,35 @@
 #![doc = include_str!("../README.md")]
 
+mod annotation;
 mod binder;
 mod cfa;
 mod checker;
+mod code_metadata;
 mod config;
+mod const_eval;
+mod const_fold;
+mod custom_section;
 mod data_set;
+mod decoder;
 mod deprecation;
 mod document;
+mod encoder;
 mod features;
 mod helpers;
 mod idx;
 mod imex;
+mod liveness;
 mod mutability;
 mod refactorings;
+mod report;
+mod script;
 mod types_analyzer;
 
-pub use crate::config::*;
+pub use crate::{
+    config::*,
+    decoder::DecodeError,
+    report::{FileDiagnostics, ReportFormat, serialize_diagnostics},
+};
 use crate::{
     document::Document,
     features::{SemanticTokenType, SemanticTokenTypes},
@@ -30,6 +44,7 @@ use lspt::{
     SemanticTokensFull, SemanticTokensLegend, SemanticTokensOptions, SemanticTokensProvider, SemanticTokensRange,
     ServerCapabilities, ServerInfo, SignatureHelpOptions, TextDocumentClientCapabilities, TextDocumentSync,
     TextDocumentSyncKind, TextDocumentSyncOptions, TextDocumentSyncSave, TypeDefinitionProvider, TypeHierarchyProvider,
+    WorkspaceSymbolProvider,
 };
 use parking_lot::RwLock;
 use rustc_hash::{FxBuildHasher, FxHashMap};
@@ -39,6 +54,7 @@ use std::{
     panic::{AssertUnwindSafe, UnwindSafe},
     sync::Arc,
 };
+use wat_syntax::TextRange;
 
 #[salsa::db]
 #[derive(Clone, Default)]
@@ -58,8 +74,13 @@ pub struct LanguageService {
     documents: Arc<RwLock<FxHashMap<String, Document>>>,
     global_config: Arc<ServiceConfig>,
     configs: Arc<RwLock<FxHashMap<String, ConfigState>>>,
+    /// Byte offsets in binary and their corresponding ranges in read-only disassembled documents.
+    binary_offsets: Arc<RwLock<FxHashMap<String, BinaryOffsets>>>,
     support_pull_config: bool,
 }
+
+type BinaryOffsets = Arc<[(u32, TextRange)]>;
+
 #[salsa::db]
 impl Database for LanguageService {}
 
@@ -116,6 +137,7 @@ impl LanguageService {
                         CodeActionKind::QuickFix,
                         CodeActionKind::RefactorRewrite,
                         CodeActionKind::RefactorInline,
+                        CodeActionKind::SourceFixAll,
                     ]),
                     resolve_provider: Some(false),
                     ..Default::default()
@@ -141,8 +163,8 @@ impl LanguageService {
                 definition_provider: Some(DefinitionProvider::Bool(true)),
                 diagnostic_provider: Some(DiagnosticProvider::Options(DiagnosticOptions {
                     identifier: Some("wat".into()),
-                    inter_file_dependencies: false,
-                    workspace_diagnostics: false,
+                    inter_file_dependencies: true,
+                    workspace_diagnostics: true,
                     ..Default::default()
                 })),
                 type_definition_provider: Some(TypeDefinitionProvider::Bool(true)),
@@ -157,7 +179,12 @@ impl LanguageService {
                 )),
                 document_symbol_provider: Some(DocumentSymbolProvider::Bool(true)),
                 execute_command_provider: Some(ExecuteCommandOptions {
-                    commands: vec!["wasmLanguageTools.__generateControlFlowGraphDot".into()],
+                    commands: vec![
+                        "wasmLanguageTools.__generateControlFlowGraphDot".into(),
+                        "wasmLanguageTools.buildWasm".into(),
+                        "wasmLanguageTools.gotoCodeOffset".into(),
+                        "wasmLanguageTools.resolveBinaryOffset".into(),
+                    ],
                     ..Default::default()
                 }),
                 folding_range_provider: Some(FoldingRangeProvider::Bool(true)),
@@ -190,6 +217,7 @@ impl LanguageService {
                     will_save_wait_until: Some(false),
                     save: Some(TextDocumentSyncSave::Bool(false)),
                 })),
+                workspace_symbol_provider: Some(WorkspaceSymbolProvider::Bool(true)),
                 ..Default::default()
             },
             server_info: Some(ServerInfo {
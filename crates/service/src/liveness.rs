@@ -0,0 +1,160 @@
+use crate::{
+    binder::{SymbolKey, SymbolKind, SymbolTable},
+    cfa::{self, FlowNodeId, FlowNodeKind},
+    document::Document,
+};
+use rustc_hash::FxHashMap;
+use smallvec::SmallVec;
+use wat_syntax::{AmberNode, SyntaxKind};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Liveness of params and locals in a function,
+/// computed on basic blocks of control flow graph.
+pub(crate) struct Liveness {
+    /// Params and locals that are accessed in function body.
+    /// Their positions are used as indices in live sets.
+    locals: FxHashMap<SymbolKey, usize>,
+    /// Live sets of each flow node, indexed by flow node id.
+    /// Unreachable flow nodes are always empty.
+    sets: Box<[LiveSets]>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct LiveSets {
+    live_in: LocalSet,
+    live_out: LocalSet,
+}
+
+impl Liveness {
+    pub fn index_of(&self, local: SymbolKey) -> Option<usize> {
+        self.locals.get(&local).copied()
+    }
+    /// Locals that may be read later when leaving this flow node.
+    pub fn live_out(&self, id: FlowNodeId) -> Option<&LocalSet> {
+        self.sets.get(id.index()).map(|sets| &sets.live_out)
+    }
+}
+
+#[salsa::tracked(returns(ref))]
+pub(crate) fn analyze(db: &dyn salsa::Database, document: Document, func: SymbolKey) -> Liveness {
+    let symbol_table = SymbolTable::of(db, document);
+    let Some(func) = symbol_table.symbols.get(func) else {
+        return Liveness {
+            locals: FxHashMap::default(),
+            sets: Box::default(),
+        };
+    };
+    let func = func.amber();
+    let cfg = cfa::analyze(db, func.green().clone().into(), func.text_range());
+
+    let mut locals = FxHashMap::default();
+    // `gen` is the set of locals read before written in this flow node,
+    // and `kill` is the set of locals written in this flow node.
+    let (gens, kills) = cfg
+        .nodes()
+        .iter()
+        .map(|flow_node| {
+            let mut gen_set = LocalSet::default();
+            let mut kill_set = LocalSet::default();
+            if let FlowNodeKind::BasicBlock(bb) = &flow_node.kind
+                && !flow_node.unreachable
+            {
+                bb.instrs().for_each(|instr| match access(symbol_table, instr) {
+                    Some((Access::Read, local)) => {
+                        let len = locals.len();
+                        let index = *locals.entry(local).or_insert(len);
+                        if !kill_set.contains(index) {
+                            gen_set.insert(index);
+                        }
+                    }
+                    Some((Access::Write, local)) => {
+                        let len = locals.len();
+                        kill_set.insert(*locals.entry(local).or_insert(len));
+                    }
+                    None => {}
+                });
+            }
+            (gen_set, kill_set)
+        })
+        .collect::<(Vec<_>, Vec<_>)>();
+
+    let mut sets = vec![LiveSets::default(); cfg.nodes().len()].into_boxed_slice();
+    // Information flows backward from successors to predecessors,
+    // so start from all flow nodes and revisit predecessors whose live-out set changes.
+    let mut worklist = cfg.nodes_with_ids().map(|(_, id)| id).collect::<Vec<_>>();
+    while let Some(id) = worklist.pop() {
+        let Some(flow_node) = cfg.get_node(id) else {
+            continue;
+        };
+        if flow_node.unreachable {
+            continue;
+        }
+        let mut live_out = LocalSet::default();
+        flow_node.outgoings.iter().for_each(|outgoing| {
+            if let Some(sets) = sets.get(outgoing.index()) {
+                live_out.union_with(&sets.live_in);
+            }
+        });
+        let mut live_in = live_out.clone();
+        live_in.subtract(&kills[id.index()]);
+        live_in.union_with(&gens[id.index()]);
+        let sets = &mut sets[id.index()];
+        sets.live_out = live_out;
+        if sets.live_in != live_in {
+            sets.live_in = live_in;
+            worklist.extend_from_slice(&flow_node.incomings);
+        }
+    }
+    Liveness { locals, sets }
+}
+
+pub(crate) enum Access {
+    Read,
+    Write,
+}
+
+/// Find out whether the instruction reads or writes a param or local.
+pub(crate) fn access(symbol_table: &SymbolTable, instr: AmberNode) -> Option<(Access, SymbolKey)> {
+    let access = match instr.tokens_by_kind(SyntaxKind::INSTR_NAME).next()?.text() {
+        "local.get" => Access::Read,
+        "local.set" | "local.tee" => Access::Write,
+        _ => return None,
+    };
+    let def = symbol_table.find_def(instr.children_by_kind(SyntaxKind::IMMEDIATE).next()?.into())?;
+    if matches!(def.kind, SymbolKind::Param | SymbolKind::Local) {
+        Some((access, def.key))
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// Bit set of local indices.
+pub(crate) struct LocalSet(SmallVec<[u64; 1]>);
+impl LocalSet {
+    pub fn contains(&self, index: usize) -> bool {
+        self.0
+            .get(index / 64)
+            .is_some_and(|word| word & (1 << (index % 64)) != 0)
+    }
+    pub fn insert(&mut self, index: usize) {
+        if self.0.len() <= index / 64 {
+            self.0.resize(index / 64 + 1, 0);
+        }
+        self.0[index / 64] |= 1 << (index % 64);
+    }
+    pub fn remove(&mut self, index: usize) {
+        if let Some(word) = self.0.get_mut(index / 64) {
+            *word &= !(1 << (index % 64));
+        }
+    }
+    fn union_with(&mut self, other: &LocalSet) {
+        if self.0.len() < other.0.len() {
+            self.0.resize(other.0.len(), 0);
+        }
+        self.0.iter_mut().zip(&other.0).for_each(|(a, b)| *a |= b);
+    }
+    fn subtract(&mut self, other: &LocalSet) {
+        self.0.iter_mut().zip(&other.0).for_each(|(a, b)| *a &= !b);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    binder::SymbolTable,
+    const_fold::{self, FoldKind},
+    document::Document,
+    helpers::{self, LineIndexExt},
+    types_analyzer::{self, InstrSigResolverCtx},
+};
+use bumpalo::Bump;
+use line_index::LineIndex;
+use lspt::{CodeAction, CodeActionKind, TextEdit, WorkspaceEdit};
+use rustc_hash::FxBuildHasher;
+use std::collections::HashMap;
+use wat_syntax::{SyntaxKind, SyntaxNode};
+
+pub fn act(
+    db: &dyn salsa::Database,
+    uri: &str,
+    document: Document,
+    line_index: &LineIndex,
+    symbol_table: &SymbolTable,
+    node: &SyntaxNode,
+) -> Option<CodeAction> {
+    let outer_block = helpers::syntax::find_outer_block_for_types(node)?;
+    let module = node
+        .ancestors()
+        .find(|ancestor| ancestor.kind() == SyntaxKind::MODULE)?;
+    let module_id = module
+        .parent()?
+        .children_by_kind(SyntaxKind::MODULE)
+        .position(|it| it == module)?;
+    let bump = Bump::new();
+    let foldable = const_fold::find_foldables(
+        &outer_block,
+        &InstrSigResolverCtx {
+            db,
+            document,
+            symbol_table,
+            def_types: types_analyzer::get_def_types(db, document),
+            module: &module,
+            module_id: module_id as u32,
+            bump: &bump,
+        },
+    )
+    .into_iter()
+    .find(|foldable| {
+        // only offer the action at the outermost instruction inside the foldable,
+        // otherwise there will be duplicated actions for nested instructions
+        foldable.range.contains_range(node.text_range())
+            && node.parent().is_none_or(|parent| {
+                parent.kind() != SyntaxKind::PLAIN_INSTR || !foldable.range.contains_range(parent.text_range())
+            })
+    })?;
+
+    let mut changes = HashMap::with_capacity_and_hasher(1, FxBuildHasher);
+    changes.insert(
+        uri.to_owned(),
+        vec![TextEdit {
+            range: line_index.convert(foldable.range)?,
+            new_text: foldable.replacement,
+        }],
+    );
+    Some(CodeAction {
+        title: match foldable.kind {
+            FoldKind::Const(value) => format!("Fold to `{value}`"),
+            FoldKind::Identity { instr_name, .. } => format!("Remove `{instr_name}` that has no effect"),
+        },
+        kind: Some(CodeActionKind::RefactorRewrite),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
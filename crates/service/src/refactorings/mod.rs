@@ -9,12 +9,15 @@ pub mod export_as;
 pub mod extract_export;
 pub mod fix_invalid_mem_arg;
 pub mod fix_packing;
+pub mod fold_const;
 pub mod if_br_to_br_if;
 pub mod inline_export;
 pub mod inline_extern_type;
 pub mod inline_func_type;
 pub mod join_types;
 pub mod merge_to_return_call;
+pub mod remove_dead_store;
 pub mod remove_mut;
 pub mod simplify_ref_type;
 pub mod split_types;
+pub mod try_to_try_table;
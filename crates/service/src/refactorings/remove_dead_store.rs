@@ -0,0 +1,109 @@
+use crate::helpers::LineIndexExt;
+use line_index::LineIndex;
+use lspt::{CodeAction, CodeActionContext, CodeActionKind, NumberOrString, TextEdit, WorkspaceEdit};
+use rustc_hash::FxBuildHasher;
+use std::collections::HashMap;
+use wat_syntax::{
+    NodeOrToken, SyntaxKind, SyntaxNode, TextRange,
+    ast::{AstNode, Instr, support},
+};
+
+/// Remove `local.set` or `local.tee` whose value is never read.
+/// Operand of `local.set` will be dropped instead if it may have side effects.
+pub fn act(uri: &str, line_index: &LineIndex, node: &SyntaxNode, context: &CodeActionContext) -> Option<CodeAction> {
+    let instr_name = support::token(node, SyntaxKind::INSTR_NAME)?;
+    let immediate = node.children_by_kind(SyntaxKind::IMMEDIATE).next()?;
+    let immediate_lsp_range = line_index.convert(immediate.text_range())?;
+    let diagnostic = context.diagnostics.iter().find(|diagnostic| match &diagnostic.code {
+        Some(NumberOrString::String(code)) => code == "unread" && diagnostic.range == immediate_lsp_range,
+        _ => false,
+    })?;
+    let operands = node
+        .children()
+        .filter(|child| Instr::can_cast(child.kind()))
+        .collect::<Vec<_>>();
+
+    let (title, range, new_text) = match (instr_name.text(), &*operands) {
+        // `local.tee` keeps its operand on the stack, so it can be removed directly
+        ("local.tee", [operand]) => ("Remove dead store", node.text_range(), operand.to_string()),
+        ("local.tee", []) => ("Remove dead store", with_leading_whitespace(node), String::new()),
+        ("local.set", [operand]) if is_pure(operand) => {
+            ("Remove dead store", with_leading_whitespace(node), String::new())
+        }
+        ("local.set", []) => {
+            if let Some(operand) = node
+                .prev_siblings()
+                .next()
+                .filter(|prev| prev.kind() == SyntaxKind::PLAIN_INSTR && is_pure(prev))
+            {
+                (
+                    "Remove dead store",
+                    TextRange::new(with_leading_whitespace(&operand).start(), node.text_range().end()),
+                    String::new(),
+                )
+            } else {
+                (
+                    "Replace dead store with `drop`",
+                    TextRange::new(instr_name.text_range().start(), immediate.text_range().end()),
+                    "drop".into(),
+                )
+            }
+        }
+        ("local.set", [_]) => (
+            "Replace dead store with `drop`",
+            TextRange::new(instr_name.text_range().start(), immediate.text_range().end()),
+            "drop".into(),
+        ),
+        _ => return None,
+    };
+
+    let mut changes = HashMap::with_capacity_and_hasher(1, FxBuildHasher);
+    changes.insert(
+        uri.to_owned(),
+        vec![TextEdit {
+            range: line_index.convert(range)?,
+            new_text,
+        }],
+    );
+    Some(CodeAction {
+        title: title.into(),
+        kind: Some(CodeActionKind::QuickFix),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }),
+        is_preferred: Some(true),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        ..Default::default()
+    })
+}
+
+/// Check if instruction produces a value without consuming operands or having side effects.
+fn is_pure(node: &SyntaxNode) -> bool {
+    node.kind() == SyntaxKind::PLAIN_INSTR
+        && !node.children().any(|child| Instr::can_cast(child.kind()))
+        && support::token(node, SyntaxKind::INSTR_NAME).is_some_and(|name| {
+            matches!(
+                name.text(),
+                "i32.const"
+                    | "i64.const"
+                    | "f32.const"
+                    | "f64.const"
+                    | "v128.const"
+                    | "local.get"
+                    | "global.get"
+                    | "ref.null"
+                    | "ref.func"
+            )
+        })
+}
+
+/// Extend range of node to cover whitespace before it, so no empty line is left after removal.
+fn with_leading_whitespace(node: &SyntaxNode) -> TextRange {
+    node.prev_sibling_or_token()
+        .and_then(NodeOrToken::into_token)
+        .filter(|token| token.kind() == SyntaxKind::WHITESPACE)
+        .map_or(node.text_range(), |whitespace| {
+            TextRange::new(whitespace.text_range().start(), node.text_range().end())
+        })
+}
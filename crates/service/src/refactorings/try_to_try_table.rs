@@ -0,0 +1,315 @@
+use crate::{
+    binder::{SymbolKey, SymbolKind, SymbolTable},
+    document::Document,
+    helpers::{self, LineIndexExt},
+    types_analyzer::Sig,
+};
+use line_index::LineIndex;
+use lspt::{CodeAction, CodeActionKind, TextEdit, WorkspaceEdit};
+use rustc_hash::FxBuildHasher;
+use std::{collections::HashMap, fmt::Write};
+use wat_syntax::{
+    NodeOrToken, SyntaxKind, SyntaxNode, TextRange, TextSize,
+    ast::{AstNode, BlockTry, Instr, support},
+};
+
+/// Rewrite legacy `try` block into nested blocks with `try_table`.
+/// Each handler gets its own block which `try_table` branches to,
+/// and `rethrow` becomes `throw_ref` with exception reference saved in a new local.
+pub fn act(
+    db: &dyn salsa::Database,
+    uri: &str,
+    document: Document,
+    line_index: &LineIndex,
+    symbol_table: &SymbolTable,
+    node: &SyntaxNode,
+) -> Option<CodeAction> {
+    let block_try = BlockTry::cast(node.clone())?;
+    if block_try.delegate().is_some() {
+        return None;
+    }
+    // block type with params can't be shared by the outer block and `try_table`
+    if block_try.type_use().is_some_and(|type_use| {
+        type_use
+            .syntax()
+            .children()
+            .any(|child| matches!(child.kind(), SyntaxKind::PARAM | SyntaxKind::INDEX))
+    }) {
+        return None;
+    }
+    let do_block = block_try.do_block()?;
+    let handlers = node
+        .children()
+        .filter(|child| {
+            matches!(
+                child.kind(),
+                SyntaxKind::BLOCK_TRY_CATCH | SyntaxKind::BLOCK_TRY_CATCH_ALL
+            )
+        })
+        .collect::<Vec<_>>();
+    if handlers.is_empty() {
+        return None;
+    }
+    let count = handlers.len();
+
+    let key = SymbolKey::from(node);
+    let range = node.text_range();
+    let func = node
+        .ancestors()
+        .find(|ancestor| ancestor.kind() == SyntaxKind::MODULE_FIELD_FUNC)?;
+    let exn = (0..)
+        .map(|i| if i == 0 { "$exn".to_string() } else { format!("$exn{i}") })
+        .find(|name| {
+            !func
+                .descendants()
+                .filter(|node| matches!(node.kind(), SyntaxKind::PARAM | SyntaxKind::LOCAL))
+                .filter_map(|node| support::token(&node, SyntaxKind::IDENT))
+                .any(|ident| ident.text() == name)
+        })?;
+
+    // numeric labels that refer to this block or outer blocks must be shifted,
+    // since there will be more blocks in between
+    let mut edits = Vec::new();
+    let mut rethrow = false;
+    for label in node
+        .descendants()
+        .filter(|node| matches!(node.kind(), SyntaxKind::IMMEDIATE | SyntaxKind::INDEX))
+    {
+        let Some(def) = symbol_table
+            .find_def(SymbolKey::from(&label))
+            .filter(|def| def.kind == SymbolKind::BlockDef)
+        else {
+            continue;
+        };
+        if def.key == key {
+            match label.parent() {
+                Some(parent) if parent.kind() == SyntaxKind::BLOCK_TRY_DELEGATE => return None,
+                Some(parent)
+                    if support::token(&parent, SyntaxKind::INSTR_NAME).is_some_and(|name| name.text() == "rethrow") =>
+                {
+                    edits.push((parent.text_range(), format!("(throw_ref (local.get {exn}))")));
+                    rethrow = true;
+                    continue;
+                }
+                _ => {}
+            }
+        } else if range.contains_range(def.key.text_range()) {
+            continue;
+        }
+        let Some(num) = label_num(&label) else {
+            continue;
+        };
+        let shift = if do_block.syntax().text_range().contains_range(label.text_range()) {
+            count + 1
+        } else if let Some(i) = handlers
+            .iter()
+            .position(|handler| handler.text_range().contains_range(label.text_range()))
+        {
+            count - 1 - i
+        } else {
+            continue;
+        };
+        edits.push((label.text_range(), (num as usize + shift).to_string()));
+    }
+
+    let text = document.text(db);
+    let line_start = text[..usize::from(range.start())].rfind('\n').map_or(0, |i| i + 1);
+    let indent = text[line_start..]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect::<String>();
+
+    let label = block_try
+        .ident_token()
+        .map(|ident| format!(" {ident}"))
+        .unwrap_or_default();
+    let block_type = block_try
+        .type_use()
+        .map(|type_use| format!(" {}", type_use.syntax()))
+        .unwrap_or_default();
+    let ref_suffix = if rethrow { "_ref" } else { "" };
+
+    // comments directly in `try` block are kept in the body of the preceding clause,
+    // and comments before `do` clause are kept at the beginning of `try_table` body
+    let mut bodies = vec![vec![]; count + 1];
+    let mut current = 0;
+    node.children_with_tokens().for_each(|child| match child {
+        NodeOrToken::Node(child) if child.kind() == SyntaxKind::BLOCK_TRY_DO => {
+            current = 0;
+            bodies[current].extend(render_body(&child, &edits));
+        }
+        NodeOrToken::Node(child) => {
+            if let Some(i) = handlers.iter().position(|handler| *handler == child) {
+                current = i + 1;
+                bodies[current].extend(render_body(&child, &edits));
+            }
+        }
+        NodeOrToken::Token(token) => {
+            if matches!(token.kind(), SyntaxKind::LINE_COMMENT | SyntaxKind::BLOCK_COMMENT) {
+                bodies[current].push(token.to_string());
+            }
+        }
+    });
+    let mut bodies = bodies.into_iter();
+
+    let mut lines = Vec::with_capacity(count * 4 + 4);
+    let open = if block_try.l_paren_token().is_some() { "(" } else { "" };
+    lines.push((0, format!("{open}block{label}{block_type}")));
+    for (i, handler) in handlers.iter().enumerate().rev() {
+        let mut types = if handler.kind() == SyntaxKind::BLOCK_TRY_CATCH {
+            handler
+                .children_by_kind(SyntaxKind::INDEX)
+                .next()
+                .and_then(|index| symbol_table.find_def(SymbolKey::from(&index)))
+                .map(|tag| {
+                    Sig::from_func(db, document, symbol_table.get_type_node_of(tag))
+                        .params
+                        .iter()
+                        .map(|ty| ty.render(db).to_string())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        } else {
+            vec![]
+        };
+        if rethrow {
+            types.push("exnref".into());
+        }
+        if types.is_empty() {
+            lines.push((count - i, "block".into()));
+        } else {
+            lines.push((count - i, format!("block (result {})", types.join(" "))));
+        }
+    }
+    let mut try_table = format!("try_table{block_type}");
+    handlers.iter().enumerate().for_each(|(i, handler)| {
+        if handler.kind() == SyntaxKind::BLOCK_TRY_CATCH {
+            if let Some(tag) = handler.children_by_kind(SyntaxKind::INDEX).next() {
+                let _ = write!(try_table, " (catch{ref_suffix} {tag} {i})");
+            }
+        } else {
+            let _ = write!(try_table, " (catch_all{ref_suffix} {i})");
+        }
+    });
+    lines.push((count + 1, try_table));
+    lines.extend(bodies.next()?.into_iter().map(|line| (count + 2, line)));
+    lines.push((count + 1, "end".into()));
+    lines.push((count + 1, format!("br {count}")));
+    (0..count).for_each(|i| {
+        let level = count - i;
+        lines.push((level, "end".into()));
+        if rethrow {
+            lines.push((level, format!("local.set {exn}")));
+        }
+        lines.extend(bodies.next().into_iter().flatten().map(|line| (level, line)));
+        if i + 1 < count {
+            lines.push((level, format!("br {}", count - 1 - i)));
+        }
+    });
+    if block_try.l_paren_token().is_some() {
+        match lines.last_mut() {
+            // closing parenthesis can't be put after line comment
+            Some((_, last)) if last.starts_with(";;") => lines.push((0, ")".into())),
+            Some((_, last)) => last.push(')'),
+            None => {}
+        }
+    } else {
+        lines.push((0, "end".into()));
+    }
+    let new_text = lines
+        .into_iter()
+        .enumerate()
+        .fold(String::new(), |mut new_text, (i, (level, line))| {
+            if i > 0 {
+                new_text.push('\n');
+                new_text.push_str(&indent);
+                (0..level).for_each(|_| new_text.push_str("  "));
+            }
+            new_text.push_str(&line);
+            new_text
+        });
+
+    let mut text_edits = Vec::with_capacity(2);
+    text_edits.push(TextEdit {
+        range: line_index.convert(range)?,
+        new_text,
+    });
+    if rethrow {
+        let position = func
+            .children()
+            .filter(|child| {
+                matches!(
+                    child.kind(),
+                    SyntaxKind::LOCAL | SyntaxKind::TYPE_USE | SyntaxKind::EXPORT | SyntaxKind::IMPORT
+                )
+            })
+            .last()
+            .map(|child| child.text_range().end())
+            .or_else(|| {
+                support::token(&func, SyntaxKind::IDENT)
+                    .or_else(|| support::token(&func, SyntaxKind::KEYWORD))
+                    .map(|token| token.text_range().end())
+            })?;
+        text_edits.push(TextEdit {
+            range: line_index.convert(TextRange::empty(position))?,
+            new_text: format!(" (local {exn} exnref)"),
+        });
+    }
+
+    let mut changes = HashMap::with_capacity_and_hasher(1, FxBuildHasher);
+    changes.insert(uri.to_owned(), text_edits);
+    Some(CodeAction {
+        title: "Convert `try` to `try_table`".into(),
+        kind: Some(CodeActionKind::RefactorRewrite),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
+
+/// Render instructions and comments in the given clause, one per line.
+fn render_body(clause: &SyntaxNode, edits: &[(TextRange, String)]) -> Vec<String> {
+    clause
+        .children_with_tokens()
+        .filter_map(|child| match child {
+            NodeOrToken::Node(node) if Instr::can_cast(node.kind()) => Some(render_with_edits(&node, edits)),
+            NodeOrToken::Token(token)
+                if matches!(token.kind(), SyntaxKind::LINE_COMMENT | SyntaxKind::BLOCK_COMMENT) =>
+            {
+                Some(token.to_string())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn label_num(label: &SyntaxNode) -> Option<u32> {
+    support::token(label, SyntaxKind::INT)
+        .or_else(|| support::token(label, SyntaxKind::UNSIGNED_INT))
+        .and_then(|token| helpers::parse_u32(token.text()).ok())
+}
+
+/// Get text of node with replacements applied to ranges inside it.
+fn render_with_edits(node: &SyntaxNode, edits: &[(TextRange, String)]) -> String {
+    let range = node.text_range();
+    let text = node.to_string();
+    let mut edits = edits
+        .iter()
+        .filter(|(edit_range, _)| range.contains_range(*edit_range))
+        .collect::<Vec<_>>();
+    edits.sort_by_key(|(edit_range, _)| edit_range.start());
+    let mut result = String::with_capacity(text.len());
+    let mut last = TextSize::new(0);
+    edits.into_iter().for_each(|(edit_range, new_text)| {
+        let start = edit_range.start() - range.start();
+        if start >= last {
+            result.push_str(&text[usize::from(last)..usize::from(start)]);
+            result.push_str(new_text);
+            last = edit_range.end() - range.start();
+        }
+    });
+    result.push_str(&text[usize::from(last)..]);
+    result
+}
@@ -0,0 +1,252 @@
+use crate::checker::RULES;
+use lspt::{Diagnostic, DiagnosticSeverity, NumberOrString, Range, StringOrMarkupContent};
+use serde_json::{Value, json};
+use std::fmt::Write;
+
+const DOCS_URL: &str = "https://wasm-language-tools.netlify.app/diagnostics";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Machine-readable format for serializing diagnostics.
+pub enum ReportFormat {
+    /// JSON array with one flat object per diagnostic.
+    Json,
+    /// [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) log.
+    Sarif,
+    /// [GitHub Actions workflow commands](https://docs.github.com/en/actions/reference/workflow-commands-for-github-actions)
+    /// that create annotations.
+    GitHub,
+    /// Checkstyle XML report.
+    Checkstyle,
+}
+
+/// Diagnostics of a single file to be serialized.
+pub struct FileDiagnostics<'a> {
+    /// File path that will appear in report.
+    ///
+    /// It's better to be relative to the repository root, which is expected by GitHub annotations and SARIF viewers.
+    pub path: &'a str,
+    /// Diagnostics reported by language service, such as from [`publish_diagnostics`](crate::LanguageService::publish_diagnostics).
+    pub diagnostics: &'a [Diagnostic],
+}
+
+/// Serialize diagnostics of files into the given format.
+///
+/// Diagnostic codes are used as rule IDs,
+/// and help URIs point to documentation of diagnostics if available.
+pub fn serialize_diagnostics(format: ReportFormat, files: &[FileDiagnostics]) -> String {
+    match format {
+        ReportFormat::Json => serialize_json(files),
+        ReportFormat::Sarif => serialize_sarif(files),
+        ReportFormat::GitHub => serialize_github(files),
+        ReportFormat::Checkstyle => serialize_checkstyle(files),
+    }
+}
+
+fn serialize_json(files: &[FileDiagnostics]) -> String {
+    let items = files
+        .iter()
+        .flat_map(|file| {
+            file.diagnostics.iter().map(|diagnostic| {
+                let code = get_code(diagnostic);
+                json!({
+                    "path": file.path,
+                    "line": diagnostic.range.start.line + 1,
+                    "column": diagnostic.range.start.character + 1,
+                    "endLine": diagnostic.range.end.line + 1,
+                    "endColumn": diagnostic.range.end.character + 1,
+                    "severity": match get_severity(diagnostic) {
+                        DiagnosticSeverity::Error => "error",
+                        DiagnosticSeverity::Warning => "warning",
+                        DiagnosticSeverity::Information => "info",
+                        DiagnosticSeverity::Hint => "hint",
+                    },
+                    "code": code,
+                    "message": get_message(diagnostic),
+                    "helpUri": code.and_then(get_help_uri),
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+    serde_json::to_string_pretty(&items).unwrap_or_default()
+}
+
+fn serialize_sarif(files: &[FileDiagnostics]) -> String {
+    let rules = RULES
+        .iter()
+        .map(|(code, _)| {
+            let mut rule = json!({ "id": code });
+            if let Some(help_uri) = get_help_uri(code) {
+                rule["helpUri"] = help_uri.into();
+            }
+            rule
+        })
+        .collect::<Vec<_>>();
+    let results = files
+        .iter()
+        .flat_map(|file| {
+            file.diagnostics.iter().map(|diagnostic| {
+                let mut result = json!({
+                    "level": match get_severity(diagnostic) {
+                        DiagnosticSeverity::Error => "error",
+                        DiagnosticSeverity::Warning => "warning",
+                        DiagnosticSeverity::Information | DiagnosticSeverity::Hint => "note",
+                    },
+                    "message": { "text": get_message(diagnostic) },
+                    "locations": [create_sarif_location(file.path, &diagnostic.range)],
+                });
+                if let Some(code) = get_code(diagnostic) {
+                    result["ruleId"] = code.into();
+                    if let Some(index) = RULES.iter().position(|(rule, _)| *rule == code) {
+                        result["ruleIndex"] = index.into();
+                    }
+                }
+                if let Some(related_information) = &diagnostic.related_information {
+                    result["relatedLocations"] = related_information
+                        .iter()
+                        .map(|info| {
+                            let mut location = create_sarif_location(file.path, &info.location.range);
+                            location["message"] = json!({ "text": info.message });
+                            location
+                        })
+                        .collect();
+                }
+                result
+            })
+        })
+        .collect::<Vec<_>>();
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "WebAssembly Language Tools",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://wasm-language-tools.netlify.app/",
+                    "rules": rules,
+                },
+            },
+            // SARIF counts columns in UTF-16 code units by default, which is the same as LSP.
+            "columnKind": "utf16CodeUnits",
+            "results": results,
+        }],
+    });
+    serde_json::to_string_pretty(&log).unwrap_or_default()
+}
+
+fn create_sarif_location(path: &str, range: &Range) -> Value {
+    json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": path },
+            "region": {
+                "startLine": range.start.line + 1,
+                "startColumn": range.start.character + 1,
+                "endLine": range.end.line + 1,
+                "endColumn": range.end.character + 1,
+            },
+        },
+    })
+}
+
+fn serialize_github(files: &[FileDiagnostics]) -> String {
+    files.iter().fold(String::new(), |mut output, file| {
+        file.diagnostics.iter().for_each(|diagnostic| {
+            let command = match get_severity(diagnostic) {
+                DiagnosticSeverity::Error => "error",
+                DiagnosticSeverity::Warning => "warning",
+                DiagnosticSeverity::Information | DiagnosticSeverity::Hint => "notice",
+            };
+            let range = &diagnostic.range;
+            let _ = write!(
+                output,
+                "::{command} file={},line={},col={},endLine={},endColumn={}",
+                escape_github_property(file.path),
+                range.start.line + 1,
+                range.start.character + 1,
+                range.end.line + 1,
+                range.end.character + 1,
+            );
+            if let Some(code) = get_code(diagnostic) {
+                let _ = write!(output, ",title={}", escape_github_property(code));
+            }
+            let _ = writeln!(output, "::{}", escape_github_data(get_message(diagnostic)));
+        });
+        output
+    })
+}
+
+fn escape_github_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_github_property(s: &str) -> String {
+    escape_github_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn serialize_checkstyle(files: &[FileDiagnostics]) -> String {
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n");
+    files.iter().for_each(|file| {
+        let _ = writeln!(output, "  <file name=\"{}\">", escape_xml(file.path));
+        file.diagnostics.iter().for_each(|diagnostic| {
+            let _ = write!(
+                output,
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\"",
+                diagnostic.range.start.line + 1,
+                diagnostic.range.start.character + 1,
+                match get_severity(diagnostic) {
+                    DiagnosticSeverity::Error => "error",
+                    DiagnosticSeverity::Warning => "warning",
+                    DiagnosticSeverity::Information | DiagnosticSeverity::Hint => "info",
+                },
+                escape_xml(get_message(diagnostic)),
+            );
+            if let Some(code) = get_code(diagnostic) {
+                let _ = write!(output, " source=\"{}\"", escape_xml(code));
+            }
+            output.push_str(" />\n");
+        });
+        output.push_str("  </file>\n");
+    });
+    output.push_str("</checkstyle>\n");
+    output
+}
+
+fn escape_xml(s: &str) -> String {
+    s.chars().fold(String::with_capacity(s.len()), |mut escaped, c| {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            c => escaped.push(c),
+        }
+        escaped
+    })
+}
+
+fn get_code(diagnostic: &Diagnostic) -> Option<&str> {
+    match &diagnostic.code {
+        Some(NumberOrString::String(code)) => Some(code),
+        _ => None,
+    }
+}
+
+fn get_severity(diagnostic: &Diagnostic) -> DiagnosticSeverity {
+    diagnostic.severity.unwrap_or(DiagnosticSeverity::Error)
+}
+
+fn get_message(diagnostic: &Diagnostic) -> &str {
+    match &diagnostic.message {
+        StringOrMarkupContent::String(message) => message,
+        StringOrMarkupContent::MarkupContent(content) => &content.value,
+    }
+}
+
+fn get_help_uri(code: &str) -> Option<String> {
+    RULES
+        .iter()
+        .any(|(rule, documented)| *rule == code && *documented)
+        .then(|| format!("{DOCS_URL}/{code}.html"))
+}
@@ -0,0 +1,79 @@
+use crate::{binder::SymbolKey, document::Document, imex};
+use wat_syntax::{AmberNode, SyntaxKind, TextRange};
+
+/// Resolve `invoke` and `get` actions in `.wast` scripts to the exports of modules they refer to.
+///
+/// An action with module identifier refers to the latest module defined with that identifier,
+/// otherwise it refers to the latest module defined before it.
+/// Binary and quoted modules can be referenced but their exports are unknown.
+#[salsa::tracked]
+pub(crate) fn get_actions(db: &dyn salsa::Database, document: Document) -> Box<[Action]> {
+    let exports = imex::get_exports(db, document);
+    let mut modules = Vec::with_capacity(1);
+    let mut actions = Vec::new();
+    AmberNode::new_root(document.root(db))
+        .children()
+        .for_each(|command| match command.kind() {
+            SyntaxKind::MODULE | SyntaxKind::MODULE_BINARY | SyntaxKind::MODULE_QUOTE => modules.push(command),
+            SyntaxKind::ACTION_GET | SyntaxKind::ACTION_INVOKE => {
+                actions.extend(resolve_action(command, &modules, exports));
+            }
+            SyntaxKind::ASSERT_RETURN | SyntaxKind::ASSERT_TRAP | SyntaxKind::ASSERT_EXHAUSTION => {
+                actions.extend(
+                    command
+                        .children_by_kind([SyntaxKind::ACTION_GET, SyntaxKind::ACTION_INVOKE])
+                        .next()
+                        .and_then(|action| resolve_action(action, &modules, exports)),
+                );
+            }
+            _ => {}
+        });
+    actions.into_boxed_slice()
+}
+
+fn resolve_action(action: AmberNode, modules: &[AmberNode], exports: &imex::ExportMap) -> Option<Action> {
+    let name = action.children_by_kind(SyntaxKind::NAME).next()?;
+    let index = if let Some(ident) = action.tokens_by_kind(SyntaxKind::IDENT).next() {
+        modules.iter().rposition(|module| {
+            module
+                .tokens_by_kind(SyntaxKind::IDENT)
+                .next()
+                .is_some_and(|token| token.text() == ident.text())
+        })
+    } else {
+        modules.len().checked_sub(1)
+    };
+    // module ID only counts text modules, since binary and quoted modules aren't bound
+    let module_id = index
+        .filter(|index| modules[*index].kind() == SyntaxKind::MODULE)
+        .map(|index| {
+            modules[..index]
+                .iter()
+                .filter(|module| module.kind() == SyntaxKind::MODULE)
+                .count()
+        });
+    let def_key = index
+        .and_then(|index| exports.get(&modules[index].to_ptr()))
+        .and_then(|exports| {
+            let name = name.green().to_string();
+            exports.iter().find(|export| export.name == name)
+        })
+        .map(|export| export.def_key);
+    Some(Action {
+        key: action.into(),
+        name: name.text_range(),
+        module_id: module_id.map(|module_id| module_id as u32),
+        def_key,
+    })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Action {
+    pub key: SymbolKey,
+    /// Range of the export name, including double quotes.
+    pub name: TextRange,
+    /// ID of referenced module. It's `None` if the module can't be found or it isn't a text module.
+    pub module_id: Option<u32>,
+    /// Key of exported definition. It's `None` if there's no such export.
+    pub def_key: Option<SymbolKey>,
+}
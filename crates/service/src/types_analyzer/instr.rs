@@ -1396,6 +1396,17 @@ pub(crate) fn resolve_instr_sig<'db, 'bump>(
                 ),
             }
         }
+        // host references can only be constructed in arguments of script actions
+        "ref.extern" | "ref.host" => ResolvedSig {
+            params: BumpVec::new_in(bump),
+            results: BumpVec::from_iter_in(
+                [OperandType::Val(ValType::Ref(RefType {
+                    heap_ty: HeapType::Extern,
+                    nullable: false,
+                }))],
+                bump,
+            ),
+        },
         "extern.convert_any" => {
             let nullable = if let Some(OperandType::Val(ValType::Ref(RefType { nullable, .. }))) = stack.last() {
                 *nullable
@@ -1450,7 +1461,7 @@ impl<'db, 'bump> ResolvedSig<'db, 'bump> {
             results: BumpVec::new_in(bump),
         }
     }
-    fn from_sig_in(sig: Sig<'db>, bump: &'bump Bump) -> Self {
+    pub(super) fn from_sig_in(sig: Sig<'db>, bump: &'bump Bump) -> Self {
         Self {
             params: BumpVec::from_iter_in(sig.params.into_iter().map(OperandType::Val), bump),
             results: BumpVec::from_iter_in(sig.results.into_iter().map(OperandType::Val), bump),
@@ -1,11 +1,11 @@
 use super::{
     instr::{InstrSigResolverCtx, ResolvedSig, resolve_instr_sig},
     signature::Sig,
-    types::OperandType,
+    types::{OperandType, ValType},
 };
 use crate::helpers;
 use bumpalo::collections::Vec as BumpVec;
-use std::ops::ControlFlow;
+use std::{mem, ops::ControlFlow};
 use wat_syntax::{
     AmberNode, SyntaxKind, SyntaxNode,
     ast::{AstNode, Instr},
@@ -18,54 +18,76 @@ pub(crate) fn perform_types_till<'db, 'bump>(
     outer_block: &'db SyntaxNode,
     ctx: &InstrSigResolverCtx<'db, 'bump>,
 ) -> Option<(TypeStack<'db, 'bump>, ResolvedSig<'db, 'bump>)> {
-    fn unfold<'db, 'bump>(
+    simulate(outer_block, ctx, |node, sig, stack| {
+        if node == target {
+            ControlFlow::Break((mem::replace(stack, BumpVec::new_in(ctx.bump)), sig))
+        } else {
+            ControlFlow::Continue(sig)
+        }
+    })
+}
+
+/// Simulate operand stack through instructions in the block.
+///
+/// Before each instruction or nested block takes effect, `visit` is called with it, its signature and current stack.
+/// The signature returned by `visit` is then applied to the stack, unless `visit` breaks the simulation.
+pub(crate) fn simulate<'db: 'bump, 'bump, B>(
+    outer_block: &'db SyntaxNode,
+    ctx: &InstrSigResolverCtx<'db, 'bump>,
+    mut visit: impl FnMut(
+        AmberNode<'db>,
+        ResolvedSig<'db, 'bump>,
+        &mut TypeStack<'db, 'bump>,
+    ) -> ControlFlow<B, ResolvedSig<'db, 'bump>>,
+) -> Option<B> {
+    fn unfold<'db, 'bump, B>(
         node: AmberNode<'db>,
         ctx: &InstrSigResolverCtx<'db, 'bump>,
         stack: &mut TypeStack<'db, 'bump>,
-        target: AmberNode<'db>,
-    ) -> ControlFlow<ResolvedSig<'db, 'bump>> {
+        visit: &mut impl FnMut(
+            AmberNode<'db>,
+            ResolvedSig<'db, 'bump>,
+            &mut TypeStack<'db, 'bump>,
+        ) -> ControlFlow<B, ResolvedSig<'db, 'bump>>,
+    ) -> ControlFlow<B> {
         let kind = node.kind();
         if matches!(kind, SyntaxKind::PLAIN_INSTR | SyntaxKind::BLOCK_IF) {
             node.children_by_kind(Instr::can_cast)
-                .try_for_each(|child| unfold(child, ctx, stack, target))?;
+                .try_for_each(|child| unfold(child, ctx, stack, visit))?;
         }
-        match kind {
+        let sig = match kind {
             SyntaxKind::PLAIN_INSTR => {
-                if let Some(instr_name) = node.tokens_by_kind(SyntaxKind::INSTR_NAME).next() {
-                    let instr_name = instr_name.text();
-                    let mut sig = resolve_instr_sig(ctx, instr_name, node, stack);
-                    if node == target {
-                        ControlFlow::Break(sig)
-                    } else {
-                        stack.truncate(stack.len().saturating_sub(sig.params.len()));
-                        if helpers::is_stack_polymorphic(instr_name) {
-                            stack.clear();
-                        }
-                        stack.append(&mut sig.results);
-                        ControlFlow::Continue(())
-                    }
-                } else {
-                    ControlFlow::Continue(())
+                let Some(instr_name) = node.tokens_by_kind(SyntaxKind::INSTR_NAME).next() else {
+                    return ControlFlow::Continue(());
+                };
+                let instr_name = instr_name.text();
+                let mut sig = visit(node, resolve_instr_sig(ctx, instr_name, node, stack), stack)?;
+                stack.truncate(stack.len().saturating_sub(sig.params.len()));
+                if helpers::is_stack_polymorphic(instr_name) {
+                    stack.clear();
                 }
+                stack.append(&mut sig.results);
+                return ControlFlow::Continue(());
             }
-            SyntaxKind::BLOCK_BLOCK | SyntaxKind::BLOCK_LOOP | SyntaxKind::BLOCK_TRY_TABLE => {
-                let sig = Sig::from_func(ctx.db, ctx.document, node);
-                stack.truncate(stack.len().saturating_sub(sig.params.len()));
-                stack.extend(sig.results.iter().map(|ty| OperandType::Val(ty.clone())));
-                ControlFlow::Continue(())
+            SyntaxKind::BLOCK_BLOCK | SyntaxKind::BLOCK_LOOP | SyntaxKind::BLOCK_TRY_TABLE | SyntaxKind::BLOCK_TRY => {
+                ResolvedSig::from_sig_in(Sig::from_func(ctx.db, ctx.document, node), ctx.bump)
             }
             SyntaxKind::BLOCK_IF => {
-                let sig = Sig::from_func(ctx.db, ctx.document, node);
-                stack.truncate(stack.len().saturating_sub(sig.params.len() + 1));
-                stack.extend(sig.results.iter().map(|ty| OperandType::Val(ty.clone())));
-                ControlFlow::Continue(())
+                let mut sig = ResolvedSig::from_sig_in(Sig::from_func(ctx.db, ctx.document, node), ctx.bump);
+                // condition
+                sig.params.push(OperandType::Val(ValType::I32));
+                sig
             }
-            _ => ControlFlow::Continue(()),
-        }
+            _ => return ControlFlow::Continue(()),
+        };
+        let mut sig = visit(node, sig, stack)?;
+        stack.truncate(stack.len().saturating_sub(sig.params.len()));
+        stack.append(&mut sig.results);
+        ControlFlow::Continue(())
     }
 
     let mut stack = match outer_block.kind() {
-        SyntaxKind::BLOCK_IF_THEN | SyntaxKind::BLOCK_IF_ELSE => BumpVec::from_iter_in(
+        SyntaxKind::BLOCK_IF_THEN | SyntaxKind::BLOCK_IF_ELSE | SyntaxKind::BLOCK_TRY_DO => BumpVec::from_iter_in(
             Sig::from_func(ctx.db, ctx.document, outer_block.parent()?.amber())
                 .params
                 .into_iter()
@@ -79,12 +101,27 @@ pub(crate) fn perform_types_till<'db, 'bump>(
                 .map(OperandType::Val),
             ctx.bump,
         ),
+        // values carried by the exception are pushed onto the stack in `catch` block
+        SyntaxKind::BLOCK_TRY_CATCH => outer_block
+            .amber()
+            .children_by_kind(SyntaxKind::INDEX)
+            .next()
+            .and_then(|index| ctx.symbol_table.find_def(index.into()))
+            .map(|tag| {
+                BumpVec::from_iter_in(
+                    Sig::from_func(ctx.db, ctx.document, ctx.symbol_table.get_type_node_of(tag))
+                        .params
+                        .into_iter()
+                        .map(OperandType::Val),
+                    ctx.bump,
+                )
+            })
+            .unwrap_or_else(|| BumpVec::new_in(ctx.bump)),
         _ => BumpVec::new_in(ctx.bump),
     };
     outer_block
         .amber()
         .children_by_kind(Instr::can_cast)
-        .try_for_each(|child| unfold(child, ctx, &mut stack, target))
+        .try_for_each(|child| unfold(child, ctx, &mut stack, &mut visit))
         .break_value()
-        .map(|sig| (stack, sig))
 }
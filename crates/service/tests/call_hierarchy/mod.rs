@@ -127,3 +127,28 @@ fn f3_outgoing() {
     });
     assert_json_snapshot!(outgoing_calls);
 }
+
+#[test]
+fn name_annotation() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (func (@name "caller")
+    call 1)
+  (func (@name "callee") (param i32)))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let prepare = service.prepare_call_hierarchy(CallHierarchyPrepareParams {
+        text_document: TextDocumentIdentifier { uri },
+        position: Position { line: 2, character: 3 },
+        work_done_token: Default::default(),
+    });
+    assert_json_snapshot!(prepare);
+    let outgoing_calls = service.call_hierarchy_outgoing_calls(CallHierarchyOutgoingCallsParams {
+        item: prepare.unwrap().first().unwrap().clone(),
+        work_done_token: Default::default(),
+        partial_result_token: Default::default(),
+    });
+    assert_json_snapshot!(outgoing_calls);
+}
@@ -0,0 +1,117 @@
+use super::*;
+use insta::{assert_json_snapshot, assert_snapshot};
+use lspt::CodeActionKind;
+use wat_service::LanguageService;
+
+fn create_fix_all_params(uri: String, only: Option<Vec<CodeActionKind>>) -> CodeActionParams {
+    let mut params = create_params(uri, 0, 0, 0, 0);
+    params.context.only = only;
+    params
+}
+
+#[test]
+fn multiple_fixes() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (global $a (mut i32)
+    i32.const 0)
+  (global $b (mut i64)
+    i64.const 0)
+  (memory 1)
+  (func (export \"load\") (param i32) (result i32)
+    local.get 0
+    i32.load offset= 4)
+  (func (export \"a\") (result i32)
+    global.get $a)
+  (func (export \"b\") (result i64)
+    global.get $b))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    assert_snapshot!(service.fix_all(&uri).unwrap());
+}
+
+#[test]
+fn until_fixpoint() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (export \"f\")
+    (block
+      (block
+        i32.const 0))
+    drop))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    assert_snapshot!(service.fix_all(&uri).unwrap());
+}
+
+#[test]
+fn ambiguous() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (type $arr (array i8))
+  (func (export \"f\") (param (ref $arr)) (result i32)
+    local.get 0
+    i32.const 0
+    array.get $arr))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    assert!(service.fix_all(&uri).is_none());
+}
+
+#[test]
+fn nothing_to_fix() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (export \"f\") (result i32)
+    i32.const 0))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    assert!(service.fix_all(&uri).is_none());
+    let response = service.code_action(create_fix_all_params(uri, Some(vec![CodeActionKind::SourceFixAll])));
+    assert!(response.is_none());
+}
+
+#[test]
+fn code_action() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (global $a (mut i32)
+    i32.const 0)
+  (func (export \"a\") (result i32)
+    global.get $a))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_fix_all_params(uri, Some(vec![CodeActionKind::SourceFixAll])));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn not_requested() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (global $a (mut i32)
+    i32.const 0)
+  (func (export \"a\") (result i32)
+    global.get $a))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_fix_all_params(uri, None));
+    assert!(
+        response
+            .into_iter()
+            .flatten()
+            .all(|action| action.kind != Some(CodeActionKind::SourceFixAll))
+    );
+}
@@ -0,0 +1,136 @@
+use super::*;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+#[test]
+fn not_foldable() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (param i32) (result i32)
+    local.get 0
+    i32.const 1
+    i32.add))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 5, 6, 5, 6));
+    assert!(response.is_none());
+}
+
+#[test]
+fn sequence() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (result i32)
+    i32.const 4
+    i32.const 8
+    i32.add))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 5, 6, 5, 6));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn sequence_at_operand() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (result i32)
+    i32.const 4
+    i32.const 8
+    i32.add))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 3, 6, 3, 6));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn folded() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (result i32)
+    (i32.mul
+      (i32.add
+        (i32.const 1)
+        (i32.const 2))
+      (i32.const 3))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 5, 10, 5, 10));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn identity_sequence() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (param i32) (result i32)
+    local.get 0
+    i32.const 0
+    i32.add))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 5, 6, 5, 6));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn identity_folded() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (param $x i32) (result i32)
+    (i32.mul
+      (i32.const 1)
+      (local.get $x))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 3, 7, 3, 7));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn comments_sequence() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (result f64)
+    f64.const 1e300 ;; keep me
+    f64.const 1e10 (; me too ;)
+    f64.mul))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 5, 6, 5, 6));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn comments_folded() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (param i32) (result i32)
+    (i32.add
+      ;; keep me
+      (local.get 0)
+      (i32.mul (; me too ;)
+        (i32.const 0)
+        (i32.const 2)))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 3, 6, 3, 6));
+    assert_json_snapshot!(response);
+}
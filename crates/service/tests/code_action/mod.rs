@@ -9,17 +9,21 @@ mod expand_compact_import;
 mod expand_ref_type;
 mod export_as;
 mod extract_export;
+mod fix_all;
 mod fix_invalid_mem_arg;
 mod fix_packing;
+mod fold_const;
 mod if_br_to_br_if;
 mod inline_export;
 mod inline_extern_type;
 mod inline_func_type;
 mod join_types;
 mod merge_to_return_call;
+mod remove_dead_store;
 mod remove_mut;
 mod simplify_ref_type;
 mod split_types;
+mod try_to_try_table;
 
 fn create_params(
     uri: String,
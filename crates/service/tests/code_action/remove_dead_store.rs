@@ -0,0 +1,139 @@
+use super::*;
+use insta::assert_json_snapshot;
+use lspt::{CodeActionKind, Diagnostic, NumberOrString, StringOrMarkupContent};
+use wat_service::LanguageService;
+
+fn create_params(uri: String, line: u32, start: u32, end: u32) -> CodeActionParams {
+    let range = Range {
+        start: Position { line, character: start },
+        end: Position { line, character: end },
+    };
+    CodeActionParams {
+        text_document: TextDocumentIdentifier { uri },
+        range,
+        context: CodeActionContext {
+            diagnostics: vec![Diagnostic {
+                range,
+                severity: None,
+                code: Some(NumberOrString::String("unread".into())),
+                code_description: None,
+                source: None,
+                message: StringOrMarkupContent::String("".into()),
+                tags: None,
+                related_information: None,
+                data: None,
+            }],
+            only: Some(vec![CodeActionKind::QuickFix]),
+            trigger_kind: None,
+        },
+        work_done_token: Default::default(),
+        partial_result_token: Default::default(),
+    }
+}
+
+#[test]
+fn no_diagnostics() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (local $a i32)
+    (local.set $a
+      (i32.const 0))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let mut params = create_params(uri, 3, 16, 18);
+    params.context.diagnostics.clear();
+    let response = service.code_action(params);
+    assert!(response.is_none());
+}
+
+#[test]
+fn tee_folded() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (result i32) (local $a i32)
+    (local.tee $a
+      (call 0))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 3, 15, 17));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn tee_sequence() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (result i32) (local $a i32)
+    call 0
+    local.tee $a))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 4, 14, 16));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn set_pure_folded() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (param i32) (local $a i32)
+    (local.set $a (local.get 0))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 3, 15, 17));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn set_pure_sequence() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (local $a i32)
+    i32.const 0
+    local.set $a))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 4, 14, 16));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn set_impure_folded() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (result i32) (local $a i32)
+    (local.set $a (call 0))
+    (i32.const 0)))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 3, 15, 17));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn set_impure_sequence() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (result i32) (local $a i32)
+    call 0
+    local.set $a
+    i32.const 0))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 4, 14, 16));
+    assert_json_snapshot!(response);
+}
@@ -0,0 +1,143 @@
+use super::*;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+#[test]
+fn delegate() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func
+    try
+      nop
+    delegate 0))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 3, 5, 3, 5));
+    assert!(response.is_none());
+}
+
+#[test]
+fn no_handlers() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func
+    try
+      nop
+    end))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 3, 5, 3, 5));
+    assert!(response.is_none());
+}
+
+#[test]
+fn sequence() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (tag $e (param i32))
+  (func (result i32)
+    try $l (result i32)
+      i32.const 0
+      br_if 0
+      i32.const 1
+    catch $e
+    catch_all
+      i32.const 2
+      br 0
+    end))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 4, 5, 4, 5));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn folded() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (tag $e)
+  (func
+    (try
+      (do
+        (call 0))
+      (catch $e))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 4, 6, 4, 6));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn rethrow() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (tag $e (param i32))
+  (func (local $exn i32)
+    try $l
+      nop
+    catch $e
+      drop
+      rethrow $l
+    catch_all
+      try
+        nop
+      catch_all
+        rethrow 1
+      end
+    end))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 4, 5, 4, 5));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn comments() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (tag $e)
+  (func (result i32)
+    try (result i32) ;; important comment
+      i32.const 1 ;; another
+    catch $e ;; handler comment
+      i32.const 2
+    end))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 4, 5, 4, 5));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn comments_folded() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (tag $e)
+  (func (result i32)
+    (try (result i32) ;; before do
+      (do ;; in do
+        (i32.const 1))
+      ;; between clauses
+      (catch $e ;; in catch
+        (i32.const 2)
+        ;; at the end
+      ))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.code_action(create_params(uri, 4, 6, 4, 6));
+    assert_json_snapshot!(response);
+}
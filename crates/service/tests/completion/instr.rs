@@ -351,3 +351,27 @@ fn shape_descriptor_incomplete() {
     let response = service.completion(create_params(uri, 2, 22));
     assert_json_snapshot!(response);
 }
+
+#[test]
+fn disabled_proposals() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+    (func (i32.))
+)
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    service.set_config(
+        uri.clone(),
+        Some(wat_service::ServiceConfig {
+            features: wat_service::Features {
+                preset: wat_service::FeaturePreset::Mvp,
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    );
+    let response = service.completion(create_params(uri, 2, 15));
+    assert_json_snapshot!(response);
+}
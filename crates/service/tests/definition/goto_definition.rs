@@ -1,6 +1,6 @@
 use insta::assert_json_snapshot;
 use lspt::{DefinitionParams, Position, TextDocumentIdentifier};
-use wat_service::LanguageService;
+use wat_service::{LanguageService, ServiceConfig};
 
 fn create_params(uri: String, line: u32, character: u32) -> DefinitionParams {
     DefinitionParams {
@@ -660,3 +660,75 @@ fn nesting_ref_node() {
     let response = service.goto_definition(create_params(uri, 6, 19));
     assert_json_snapshot!(response);
 }
+
+#[test]
+fn invoke_export() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module $M
+  (func $f (export "f")))
+(module
+  (func (export "f")))
+(invoke $M "f")
+(assert_return (invoke "f"))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.goto_definition(create_params(uri.clone(), 5, 12));
+    assert_json_snapshot!("invoke_export_named_module", response);
+    let response = service.goto_definition(create_params(uri, 6, 24));
+    assert_json_snapshot!("invoke_export_latest_module", response);
+}
+
+#[test]
+fn get_export() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (global $g i32
+    (i32.const 0))
+  (export "g" (global $g)))
+(get "g")
+(get "h")
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.goto_definition(create_params(uri.clone(), 5, 7));
+    assert_json_snapshot!(response);
+    assert!(service.goto_definition(create_params(uri, 6, 7)).is_none());
+}
+
+#[test]
+fn linked_import() {
+    let uri = "untitled:main.wat".to_string();
+    let source = r#"
+(module
+  (import "env" "f" (func))
+  (import "env" (item "g" (global i32)))
+  (func (import "env" "h"))
+  (import "other" "f" (func)))
+"#;
+    let env = r#"
+(module
+  (func $f (export "f"))
+  (global $g (export "g") i32
+    i32.const 0))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    service.commit("untitled:env.wat".into(), env.into());
+    service.set_config(
+        uri.clone(),
+        Some(ServiceConfig {
+            imports: [("env".into(), "./env.wat".into())].into(),
+            ..Default::default()
+        }),
+    );
+    let response = service.goto_definition(create_params(uri.clone(), 2, 18));
+    assert_json_snapshot!("linked_import_func", response);
+    let response = service.goto_definition(create_params(uri.clone(), 3, 23));
+    assert_json_snapshot!("linked_import_item", response);
+    assert!(service.goto_definition(create_params(uri.clone(), 2, 11)).is_none());
+    assert!(service.goto_definition(create_params(uri.clone(), 4, 24)).is_none());
+    assert!(service.goto_definition(create_params(uri, 5, 20)).is_none());
+}
@@ -0,0 +1,127 @@
+use super::*;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+#[test]
+fn valid() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (type $t (func))
+  (table 1 funcref)
+  (func $f (@metadata.code.compilation_priority (compilation 1) (optimization 2) (run_once)) (param i32)
+    (@metadata.code.branch_hint "\01")
+    (if (local.get 0)
+      (then))
+    (block
+      local.get 0
+      (@metadata.code.branch_hint "\00")
+      (@metadata.code.instr_freq (freq 3))
+      br_if 0)
+    (@metadata.code.call_targets (target $f 60) (target 1 40))
+    (call_indirect (type $t) (i32.const 0)))
+  (func))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
+
+#[test]
+fn misplaced() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (@metadata.code.instr_freq (freq 1))
+  (global i32 (@metadata.code.instr_freq (freq 1)) (i32.const 0))
+  (func $f (param i32)
+    (@metadata.code.branch_hint "\01")
+    (br 0)
+    (@metadata.code.call_targets (target $f 1))
+    (call $f (i32.const 0))
+    (@metadata.code.compilation_priority (compilation 1))
+    (nop)
+    (@metadata.code.branch_hint "\01")))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn malformed() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (type $t (func))
+  (table 1 funcref)
+  (func $f (@metadata.code.compilation_priority (tier 1)) (param i32)
+    (@metadata.code.branch_hint "\02")
+    (if (local.get 0)
+      (then))
+    (@metadata.code.branch_hint)
+    (br_if 0 (local.get 0))
+    (@metadata.code.instr_freq (freq -1))
+    (nop)
+    (@metadata.code.instr_freq 1)
+    (nop)
+    (@metadata.code.call_targets)
+    (call_indirect (type $t) (i32.const 0))
+    (@metadata.code.call_targets (target $f))
+    (call_indirect (type $t) (i32.const 0))))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn undefined_call_targets() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (type $t (func))
+  (table 1 funcref)
+  (global $g i32 (i32.const 0))
+  (func $f
+    (@metadata.code.call_targets (target $g 50) (target 1 20) (target $f 30))
+    (call_indirect (type $t) (i32.const 0))))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn deny() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (func
+    (@metadata.code.branch_hint "\01")
+    nop))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    service.set_config(
+        uri.clone(),
+        Some(ServiceConfig {
+            lint: Lints {
+                unused: LintLevel::Allow,
+                code_metadata: LintLevel::Deny,
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    );
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
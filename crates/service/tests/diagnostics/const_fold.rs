@@ -0,0 +1,172 @@
+use super::*;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+fn disable_other_lints(service: &mut LanguageService, uri: String) {
+    service.set_config(
+        uri,
+        Some(ServiceConfig {
+            lint: Lints {
+                unused: LintLevel::Allow,
+                const_fold: LintLevel::Warn,
+                trap: LintLevel::Allow,
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    );
+}
+
+#[test]
+fn sequence() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (result i32)
+    i32.const 4
+    i32.const 8
+    i32.add
+    i32.const 3
+    i32.mul)
+  (func (result i64)
+    i32.const -1
+    i64.extend_i32_u)
+  (func (result i32)
+    f64.const 1.5
+    f64.const 2.25
+    f64.lt))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn folded() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (result i32)
+    (i32.shl
+      (i32.sub
+        (i32.const 10)
+        (i32.const 3))
+      (i32.const 2)))
+  (func (result f32)
+    (f32.div
+      (f32.const 1)
+      (f32.const 3)))
+  (func
+    (if
+      (i32.eqz
+        (i32.const 0))
+      (then))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn identity() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (param $x i32) (result i32)
+    (i32.mul
+      (i32.const 1)
+      (local.get $x)))
+  (func (param $x i64) (result i64)
+    local.get $x
+    i64.const 0
+    i64.add)
+  (func (param $x i32) (result i32)
+    (i32.add
+      (i32.and
+        (local.get $x)
+        (i32.const -1))
+      (i32.const 0)))
+  (func (param $x i32) (result i32)
+    (i32.sub
+      (i32.const 0)
+      (local.get $x))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn not_foldable() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (result i32)
+    i32.const 1
+    i32.const 0
+    i32.div_s)
+  (func (result i32)
+    i32.const 0x80000000
+    i32.const -1
+    i32.div_s)
+  (func (result i32)
+    f32.const 3e9
+    i32.trunc_f32_s)
+  (func (result f32)
+    f32.const 0
+    f32.const 0
+    f32.div)
+  (func (result i32)
+    i32.const 1
+    call 0
+    drop
+    i32.const 2
+    i32.add)
+  (func (param i32) (result i32)
+    local.get 0
+    i32.const 2
+    i32.add)
+  (func (param f32) (result f32)
+    local.get 0
+    f32.const 0
+    f32.add)
+  (func (result i32)
+    i32.const 1
+    block (param i32) (result i32)
+      i32.const 2
+      i32.add
+    end))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
+
+#[test]
+fn nested_blocks() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (result i32)
+    block (result i32)
+      i32.const 1
+      i32.const 2
+      i32.add
+    end
+    i32.const 3
+    i32.add))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
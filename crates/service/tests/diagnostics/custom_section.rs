@@ -0,0 +1,95 @@
+use super::*;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+#[test]
+fn valid() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (@custom "producers" (after func) "\01\0cprocessed-by\01\05clang\0218")
+  (@custom "name" "")
+  (@custom "a" (before first))
+  (@custom "a" (after last) "x" "y")
+  (@custom "b" (before datacount))
+  (func))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
+
+#[test]
+fn implicit_module() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(@custom "x" "abc")
+(func)
+(@custom "name" "")
+(func)
+(@custom "name" "")
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn malformed() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (@custom)
+  (@custom name)
+  (@custom "a" (around func))
+  (@custom "a" (after funcs))
+  (@custom "a" (after first))
+  (@custom "a" (before func extra))
+  (@custom "a" "x" (after func))
+  (@custom "a" (after func) (before func))
+  (@custom "a" 1))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn misplaced() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (func (@custom "a" "x")))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn duplicated() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (@custom "name" "")
+  (@custom "producers" "")
+  (@custom "name" "")
+  (@custom "other" "")
+  (@custom "other" ""))
+(module
+  (@custom "name" ""))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
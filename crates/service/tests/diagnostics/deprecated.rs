@@ -176,3 +176,27 @@ fn compact_import() {
     let response = service.pull_diagnostics(create_params(uri));
     assert_json_snapshot!(response);
 }
+
+#[test]
+fn legacy_exception_handling() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (tag $e)
+  (func
+    try
+      nop
+    catch $e
+      rethrow 0
+    end
+    (try
+      (do)
+      (catch_all
+        (rethrow 0)))))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
@@ -0,0 +1,205 @@
+use super::*;
+use insta::assert_json_snapshot;
+use wat_service::{FeaturePreset, Features, LanguageService};
+
+fn set_features(service: &mut LanguageService, uri: String, features: Features) {
+    service.set_config(
+        uri,
+        Some(ServiceConfig {
+            lint: Lints {
+                unused: LintLevel::Allow,
+                const_fold: LintLevel::Allow,
+                ..Default::default()
+            },
+            features,
+            ..Default::default()
+        }),
+    );
+}
+
+#[test]
+fn latest_by_default() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (type (struct (field i32)))
+  (tag)
+  (memory i64 1)
+  (memory 1)
+  (func (param (ref 0))
+    local.get 0
+    return_call 0))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
+
+#[test]
+fn mvp() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (table 1 externref)
+  (memory 1)
+  (func (param v128) (result i32)
+    i32.const 0
+    i32.extend8_s
+    drop
+    f32.const 0
+    i32.trunc_sat_f32_s
+    drop
+    i32.const 0
+    i32.const 0
+    i32.const 0
+    memory.copy
+    ref.null func
+    drop
+    i32.const 0
+    v128.const i32x4 0 0 0 0
+    drop))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    set_features(
+        &mut service,
+        uri.clone(),
+        Features {
+            preset: FeaturePreset::Mvp,
+            ..Default::default()
+        },
+    );
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn v2() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (rec
+    (type (sub (struct (field i32))))
+    (type (array i8)))
+  (type (func))
+  (type (cont 2))
+  (tag)
+  (memory i64 1)
+  (memory 1)
+  (func (param (ref 2) (ref null func) anyref exnref)
+    block
+      try_table
+      end
+    end
+    i64.const 0
+    i64.const 0
+    i64.const 0
+    i64.const 0
+    i64.add128
+    drop
+    drop
+    return_call 0))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    set_features(
+        &mut service,
+        uri.clone(),
+        Features {
+            preset: FeaturePreset::V2,
+            ..Default::default()
+        },
+    );
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn v3() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (type (struct (field i32)))
+  (memory 1 1 shared)
+  (memory i64 1)
+  (func
+    i32.const 0
+    i32.const 0
+    i32.atomic.rmw.add
+    drop
+    return_call 0))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    set_features(
+        &mut service,
+        uri.clone(),
+        Features {
+            preset: FeaturePreset::V3,
+            ..Default::default()
+        },
+    );
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn toggles_override_preset() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (memory 1)
+  (memory 1)
+  (func
+    return_call 0
+    i32.const 0
+    i32.extend8_s
+    drop))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    set_features(
+        &mut service,
+        uri.clone(),
+        Features {
+            preset: FeaturePreset::Mvp,
+            sign_extension: Some(true),
+            multi_memory: Some(true),
+            tail_call: Some(false),
+            ..Default::default()
+        },
+    );
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn memories_in_different_modules() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module (memory 1))
+(module (memory 1))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    service.set_config(
+        uri.clone(),
+        Some(ServiceConfig {
+            lint: Lints {
+                unused: LintLevel::Allow,
+                multi_modules: LintLevel::Allow,
+                const_fold: LintLevel::Allow,
+                ..Default::default()
+            },
+            features: Features {
+                preset: FeaturePreset::Mvp,
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    );
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
@@ -6,32 +6,42 @@ use wat_service::{LanguageService, LintLevel, Lints, ServiceConfig};
 mod block_type;
 mod br_table_branches;
 mod catch_type;
+mod code_metadata;
 mod const_expr;
+mod const_fold;
 mod cont_type;
+mod custom_section;
 mod deprecated;
 mod dup_names;
 mod elem_type;
+mod feature;
 mod implicit_module;
 mod import_occur;
 mod import_with_def;
 mod lane;
 mod mem_arg;
 mod mem_type;
+mod module_binary;
+mod module_quote;
 mod multi_modules;
 mod multi_starts;
 mod mutated_immutable;
+mod name_annot;
 mod needless_mut;
 mod needless_try_table;
 mod new_non_defaultable;
 mod omitted_idx_in_instr;
 mod packing;
 mod plain_instr;
+mod segment_bounds;
 mod shadow;
 mod start;
 mod subtyping;
+mod suppression;
 mod syntax;
 mod table_type;
 mod tag_type;
+mod trap;
 mod type_misuse;
 mod typeck;
 mod undef;
@@ -40,6 +50,7 @@ mod unreachable;
 mod unread;
 mod unused;
 mod useless_catch;
+mod workspace;
 
 fn create_params(uri: String) -> DocumentDiagnosticParams {
     DocumentDiagnosticParams {
@@ -62,6 +73,9 @@ fn calm(service: &mut LanguageService, uri: String) {
                 needless_mut: LintLevel::Allow,
                 needless_try_table: LintLevel::Allow,
                 useless_catch: LintLevel::Allow,
+                deprecated: LintLevel::Allow,
+                const_fold: LintLevel::Allow,
+                segment_bounds: LintLevel::Allow,
                 ..Default::default()
             },
             ..Default::default()
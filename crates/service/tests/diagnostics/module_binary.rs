@@ -0,0 +1,92 @@
+use super::*;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+#[test]
+fn valid() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module binary "\00asm\01\00\00\00")
+(module $M binary
+  "\00asm" "\01\00\00\00"
+  "\01\04\01\60\00\00"
+  "\03\02\01\00"
+  "\0a\04\01\02\00\0b")
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
+
+#[test]
+fn bad_magic() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module binary "\00wasm\01\00\00\00")
+(module binary "\00as")
+(module binary)
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn bad_version() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module binary "\00asm" "\0d\00\00\00")
+(module binary "\00asm\01\00")
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn truncated_section() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module binary "\00asm\01\00\00\00" "\01\05\01\60\00")
+(module binary "\00asm\01\00\00\00" "\01\80")
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn malformed_section_size() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module binary "\00asm\01\00\00\00" "\01\80\80\80\80\10")
+(module binary "\00asm\01\00\00\00" "\01\ff\ff\ff\ff\7f")
+(module binary "\00asm\01\00\00\00" "\01\84\80\80\80\00\01\60\00\00")
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn assert_malformed() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(assert_malformed (module binary "\00asm") "unexpected end")
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
@@ -0,0 +1,45 @@
+use super::*;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+#[test]
+fn valid() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module quote "(func (export \"f\") (result i32)" "i32.const 0)")
+(module $M quote "(memory 1)\n(data (i32.const 0) \"\\u{1F600}\")")
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
+
+#[test]
+fn syntax_errors() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module quote "(fnuc)")
+(module quote "(func" "(param i32) (local.get 0)")
+(module quote "(func \u{1F600} (export \"f\")" "(result)) (glo)")
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn assert_malformed() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(assert_malformed (module quote "(func (fnuc))") "unexpected token")
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
@@ -51,3 +51,17 @@ fn allowed_by_config() {
     let response = service.pull_diagnostics(create_params(uri));
     assert!(response.items.is_empty());
 }
+
+#[test]
+fn script() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module)
+(module (func (export "f")))
+(invoke "f")
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
@@ -0,0 +1,60 @@
+use super::*;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+#[test]
+fn matched() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (func $f (@name "f"))
+  (func $"my func" (@name "my func"))
+  (func (@name "anonymous"))
+  (global $g (@name "\67") i32 (i32.const 0)))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
+
+#[test]
+fn conflicted() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (import "env" "h" (func $h (@name "i")))
+  (func $f (@name "g"))
+  (func (param $x (@name "y") i32)))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn allowed() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (func $f (@name "g")))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    service.set_config(
+        uri.clone(),
+        Some(ServiceConfig {
+            lint: Lints {
+                unused: LintLevel::Allow,
+                name_annotation: LintLevel::Allow,
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    );
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
@@ -0,0 +1,161 @@
+use super::*;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+fn disable_other_lints(service: &mut LanguageService, uri: String) {
+    service.set_config(
+        uri,
+        Some(ServiceConfig {
+            lint: Lints {
+                unused: LintLevel::Allow,
+                segment_bounds: LintLevel::Warn,
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    );
+}
+
+#[test]
+fn data_in_bounds() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (memory 1)
+  (global $offset i32 (i32.const 65532))
+  (data (i32.const 65534) "ab")
+  (data (global.get $offset) "\01\02\03\04")
+  (data (i32.const 65536))
+  (data "passive segment"))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn data_out_of_bounds() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (memory $a 1)
+  (memory $b 0)
+  (global $offset i32 (i32.const 65533))
+  (data (i32.const 65535) "ab")
+  (data (global.get $offset) "\01\02\03\04")
+  (data (memory $b) (i32.const 0) "a")
+  (data (i32.const -1) "a")
+  (data (i32.const 65537)))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn data_custom_page_size() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (memory 4 (pagesize 1))
+  (data (i32.const 2) "ab")
+  (data (i32.const 3) "ab"))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn data_memory64() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (memory i64 1)
+  (data (i64.const 65535) "a")
+  (data (i64.const 4294967296) "a"))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn data_imported_memory() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (import "env" "memory" (memory 0))
+  (memory (import "env" "memory2") 0)
+  (data (i32.const 0) "a")
+  (data (memory 1) (i32.const 0) "a"))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn elem_in_bounds() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (table 2 funcref)
+  (func $f)
+  (elem (i32.const 0) $f $f)
+  (elem (table 0) (i32.const 1) funcref (ref.func $f))
+  (elem (i32.const 2))
+  (elem declare func $f)
+  (elem func $f $f $f))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn elem_out_of_bounds() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (table $a 2 funcref)
+  (table $b i64 1 funcref)
+  (func $f)
+  (elem (i32.const 1) $f $f)
+  (elem (table $b) (i64.const 0) funcref (ref.func $f) (ref.null func))
+  (elem (i32.const 3)))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn elem_imported_table() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (import "env" "table" (table 0 funcref))
+  (func $f)
+  (elem (i32.const 0) $f))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
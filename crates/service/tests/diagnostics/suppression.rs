@@ -0,0 +1,130 @@
+use super::*;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+#[test]
+fn next_line() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  ;; wat-lint-disable-next-line unused
+  (func $a)
+  (func $b)
+  (; wat-lint-disable-next-line ;)
+  (func $c (local $x i32)))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn disable_enable() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (; wat-lint-disable shadow, unused ;)
+  (func $a
+    (block $b
+      (block $b)))
+  (; wat-lint-enable ;)
+  (func $c
+    (block $b
+      (block $b))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn disable_until_end() {
+    let uri = "untitled:test".to_string();
+    let source = "
+;; wat-lint-disable unused -- kept for testing
+(module
+  (func $a)
+  (func $b))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn allow_annotation() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (import "env" "f" (@allow unused) (func $f))
+  (@allow unused)
+  (func $a)
+  (func $b (@allow "shadow")
+    (block $l
+      (block $l)))
+  (func $c))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn unused_suppression() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  ;; wat-lint-disable-next-line unused, shadow
+  (func $a)
+  (@allow)
+  (func (export \"f\"))
+  (; wat-lint-disable-next-line undef ;)
+  (func (export \"g\")))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn unused_suppression_allowed() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  ;; wat-lint-disable-next-line unused
+  (func (export \"f\")))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    service.set_config(
+        uri.clone(),
+        Some(ServiceConfig {
+            lint: Lints {
+                unused_suppression: LintLevel::Allow,
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    );
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn not_directive() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  ;; wat-lint-disable-next-lines unused
+  (func $a))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
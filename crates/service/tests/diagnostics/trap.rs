@@ -0,0 +1,211 @@
+use super::*;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+fn disable_other_lints(service: &mut LanguageService, uri: String) {
+    service.set_config(
+        uri,
+        Some(ServiceConfig {
+            lint: Lints {
+                unused: LintLevel::Allow,
+                unreachable: LintLevel::Allow,
+                needless_mut: LintLevel::Allow,
+                const_fold: LintLevel::Allow,
+                trap: LintLevel::Warn,
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    );
+}
+
+#[test]
+fn div_by_zero() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (param i32) (result i32)
+    local.get 0
+    i32.const 0
+    i32.div_u)
+  (func (param i64) (result i64)
+    (i64.rem_s
+      (local.get 0)
+      (i64.sub
+        (i64.const 1)
+        (i64.const 1)))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn div_overflow() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (result i32)
+    i32.const -2147483648
+    i32.const -1
+    i32.div_s)
+  (func (result i64)
+    (i64.div_s
+      (i64.const -9223372036854775808)
+      (i64.const -1)))
+  (func (result i32)
+    i32.const -2147483648
+    i32.const -1
+    i32.rem_s))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn trunc() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (result i32)
+    f32.const nan
+    i32.trunc_f32_s)
+  (func (result i32)
+    f64.const 4294967296
+    i32.trunc_f64_u)
+  (func (result i64)
+    (i64.trunc_f32_u
+      (f32.const -1)))
+  (func (result i32)
+    f64.const nan
+    i32.trunc_sat_f64_s))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn null_ref() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (type $s (struct (field i32)))
+  (type $f (func))
+  (func
+    ref.null func
+    ref.as_non_null
+    drop)
+  (func (result i32)
+    (struct.get $s 0
+      (ref.null $s)))
+  (func
+    (call_ref $f
+      (ref.null $f))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn memory_out_of_bounds() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (memory 1 1)
+  (memory $m 0 2 (pagesize 1))
+  (memory $n 1)
+  (func (result i32)
+    i32.const 65536
+    i32.load)
+  (func (result i64)
+    (i64.load offset=65530
+      (i32.const 0)))
+  (func
+    (i32.store8 $m
+      (i32.const 2)
+      (i32.const 0)))
+  (func (result i32)
+    i32.const -1
+    i32.load $n))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn immutable_global() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (global $zero i32 (i32.const 0))
+  (global $mut (mut i32) (i32.const 0))
+  (func (result i32)
+    i32.const 1
+    global.get $zero
+    i32.div_s)
+  (func (result i32)
+    i32.const 1
+    global.get $mut
+    i32.div_s))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn valid() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (memory 1 1)
+  (memory $m i64 1)
+  (func (param i32) (result i32)
+    i32.const 1
+    local.get 0
+    i32.div_s)
+  (func (result i32)
+    i32.const -2147483648
+    i32.const -1
+    i32.mul)
+  (func (result i32)
+    f32.const 1.5
+    i32.trunc_f32_s)
+  (func (result i32)
+    i32.const 65532
+    i32.load)
+  (func (result i32)
+    i64.const 4294967296
+    i32.load $m)
+  (func (param (ref null func))
+    local.get 0
+    ref.as_non_null
+    drop)
+  (func (param i32) (result i32)
+    (block (result i32)
+      (i32.const 0))
+    local.get 0
+    i32.div_u))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    disable_other_lints(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
@@ -248,3 +248,51 @@ fn try_table() {
     let response = service.pull_diagnostics(create_params(uri));
     assert!(response.items.is_empty());
 }
+
+#[test]
+fn legacy_try() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (tag $e0)
+  (tag $e-i32 (param i32))
+
+  (func (result i32)
+    try (result i32)
+      i32.const 0
+    catch $e-i32
+    catch $e0
+      i32.const 1
+    catch_all
+      i32.const 2
+    end)
+  (func (result i32)
+    (try (result i32)
+      (do
+        (i64.const 0))
+      (catch $e-i32
+        (i64.extend_i32_u))
+      (catch_all)))
+  (func (param i32)
+    (try
+      (do
+        (local.get 0)
+        (throw $e-i32))
+      (delegate 0)))
+  (func
+    try $outer
+      try
+        nop
+      catch $e0
+        rethrow $outer
+      end
+    catch_all
+      rethrow 0
+    end))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
@@ -0,0 +1,50 @@
+use super::*;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+#[test]
+fn mismatched_args() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module $M
+  (func (export "add") (param i32 i32) (result i32)
+    (i32.add
+      (local.get 0)
+      (local.get 1))))
+(module
+  (func (export "f") (param f32)))
+(assert_return (invoke "f" (i32.const 1)))
+(invoke $M "add" (i32.const 1) (i64.const 2))
+(invoke $M "add" (i32.const 1))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn valid() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module $M
+  (func (export "add") (param i32 i32) (result i32)
+    (i32.add
+      (local.get 0)
+      (local.get 1))))
+(module
+  (func (export "f") (param f32 externref funcref))
+  (global (export "g") i32
+    (i32.const 0)))
+(assert_return (invoke "f" (f32.const nan) (ref.extern 1) (ref.null func)))
+(assert_return (invoke $M "add" (i32.const 1) (i32.const 2)) (i32.const 3))
+(assert_return (get "g") (i32.const 0))
+(invoke "unknown" (i32.const 1))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
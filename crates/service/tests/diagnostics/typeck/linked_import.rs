@@ -0,0 +1,156 @@
+use super::*;
+use std::collections::BTreeMap;
+use wat_service::{LintLevel, Lints, ServiceConfig};
+
+fn link(service: &mut LanguageService, uri: &str, env: &str) {
+    service.commit("untitled:env.wat".into(), env.into());
+    service.set_config(
+        uri.into(),
+        Some(ServiceConfig {
+            imports: BTreeMap::from([("env".into(), "./env.wat".into())]),
+            lint: Lints {
+                unused: LintLevel::Allow,
+                needless_mut: LintLevel::Allow,
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    );
+}
+
+#[test]
+fn func_matches() {
+    let uri = "untitled:main.wat".to_string();
+    let source = r#"
+(module
+  (import "env" "f" (func (param i32) (result anyref)))
+  (import "env" "g" (func (param (ref any)))))
+"#;
+    let env = r#"
+(module
+  (func (export "f") (param i32) (result i31ref)
+    unreachable)
+  (func (export "g") (param anyref)))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    link(&mut service, &uri, env);
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn func_mismatched() {
+    let uri = "untitled:main.wat".to_string();
+    let source = r#"
+(module
+  (import "env" "f" (func (param i32) (result i31ref)))
+  (import "env" "g" (func (param anyref))))
+"#;
+    let env = r#"
+(module
+  (func (export "f") (param i32) (result anyref)
+    unreachable)
+  (func (export "g") (param (ref any))))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    link(&mut service, &uri, env);
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn kind_mismatched() {
+    let uri = "untitled:main.wat".to_string();
+    let source = r#"
+(module
+  (import "env" "f" (func))
+  (import "env" (item "g" (memory 1))))
+"#;
+    let env = r#"
+(module
+  (global (export "f") i32
+    i32.const 0)
+  (func (export "g")))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    link(&mut service, &uri, env);
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn globals() {
+    let uri = "untitled:main.wat".to_string();
+    let source = r#"
+(module
+  (import "env" "a" (global anyref))
+  (import "env" "b" (global (mut anyref)))
+  (import "env" "c" (global i32))
+  (global (import "env" "d") (mut i32)))
+"#;
+    let env = r#"
+(module
+  (global (export "a") i31ref
+    ref.null i31)
+  (global (export "b") (mut i31ref)
+    ref.null i31)
+  (global (export "c") (mut i32)
+    i32.const 0)
+  (global (export "d") (mut i32)
+    i32.const 0))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    link(&mut service, &uri, env);
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn memories_and_tables() {
+    let uri = "untitled:main.wat".to_string();
+    let source = r#"
+(module
+  (import "env" "m1" (memory 1))
+  (import "env" "m2" (memory 1 2))
+  (import "env" "m3" (memory i64 1))
+  (import "env" "t1" (table 1 funcref))
+  (import "env" "t2" (table 1 anyref)))
+"#;
+    let env = r#"
+(module
+  (memory (export "m1") 2 3)
+  (memory (export "m2") 1)
+  (memory (export "m3") 1)
+  (table (export "t1") 1 2 funcref)
+  (table (export "t2") 1 eqref))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    link(&mut service, &uri, env);
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn not_linked() {
+    let uri = "untitled:main.wat".to_string();
+    let source = r#"
+(module
+  (import "env" "f" (func (param i32)))
+  (import "other" "f" (func (param i32))))
+"#;
+    let env = r#"
+(module
+  (func (export "f")))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    service.commit("untitled:env.wat".into(), env.into());
+    calm(&mut service, uri.clone());
+    let response = service.pull_diagnostics(create_params(uri));
+    assert!(response.items.is_empty());
+}
@@ -16,6 +16,8 @@ mod elem;
 mod exception;
 mod func;
 mod global;
+mod invoke;
+mod linked_import;
 mod local;
 mod offset;
 mod rec;
@@ -422,3 +422,29 @@ fn export() {
     let response = service.pull_diagnostics(create_params(uri));
     assert!(response.items.is_empty());
 }
+
+#[test]
+fn linked_import() {
+    let uri = "untitled:main.wat".to_string();
+    let source = r#"
+(module
+  (import "env" "f" (func))
+  (import "env" "g" (func)))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    service.commit("untitled:env.wat".into(), r#"(module (func (export "f")))"#.into());
+    service.set_config(
+        uri.clone(),
+        Some(ServiceConfig {
+            imports: [("env".into(), "./env.wat".into())].into(),
+            lint: Lints {
+                unused: LintLevel::Allow,
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    );
+    let response = service.pull_diagnostics(create_params(uri));
+    assert_json_snapshot!(response);
+}
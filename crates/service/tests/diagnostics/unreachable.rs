@@ -10,6 +10,7 @@ fn disable_other_lints(service: &mut LanguageService, uri: String) {
                 unused: LintLevel::Allow,
                 unreachable: LintLevel::Warn,
                 needless_try_table: LintLevel::Allow,
+                const_fold: LintLevel::Allow,
                 ..Default::default()
             },
             ..Default::default()
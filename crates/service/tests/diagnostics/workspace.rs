@@ -0,0 +1,98 @@
+use super::*;
+use insta::assert_json_snapshot;
+use lspt::{PreviousResultId, WorkspaceDiagnosticParams, WorkspaceDocumentDiagnosticReport};
+use wat_service::LanguageService;
+
+fn create_workspace_params(previous_result_ids: Vec<PreviousResultId>) -> WorkspaceDiagnosticParams {
+    WorkspaceDiagnosticParams {
+        identifier: Some("wat".into()),
+        previous_result_ids,
+        work_done_token: Default::default(),
+        partial_result_token: Default::default(),
+    }
+}
+
+#[test]
+fn all_documents() {
+    let mut service = LanguageService::default();
+    service.commit("untitled:a.wat".into(), "(module (func (result i32)))".into());
+    service.commit("untitled:b.wat".into(), "(module (func $f))".into());
+    calm(&mut service, "untitled:b.wat".into());
+    let response = service.pull_workspace_diagnostics(create_workspace_params(vec![]));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn unchanged() {
+    let mut service = LanguageService::default();
+    service.commit("untitled:a.wat".into(), "(module (func (result i32)))".into());
+    service.commit("untitled:b.wat".into(), "(module (func (result i32)))".into());
+    let previous_result_ids = service
+        .pull_workspace_diagnostics(create_workspace_params(vec![]))
+        .items
+        .into_iter()
+        .map(|report| match report {
+            WorkspaceDocumentDiagnosticReport::Full(report) => PreviousResultId {
+                uri: report.uri,
+                value: report.result_id.unwrap(),
+            },
+            WorkspaceDocumentDiagnosticReport::Unchanged(..) => panic!("expected full report"),
+        })
+        .collect();
+    service.commit(
+        "untitled:b.wat".into(),
+        "(module (func (result i32) i32.const 0))".into(),
+    );
+    let response = service.pull_workspace_diagnostics(create_workspace_params(previous_result_ids));
+    assert!(matches!(
+        &response.items[0],
+        WorkspaceDocumentDiagnosticReport::Unchanged(report) if report.uri == "untitled:a.wat"
+    ));
+    assert!(matches!(
+        &response.items[1],
+        WorkspaceDocumentDiagnosticReport::Full(report) if report.uri == "untitled:b.wat"
+    ));
+}
+
+#[test]
+fn document_config() {
+    let mut service = LanguageService::default();
+    service.commit("untitled:a.wat".into(), "(module (func $f))".into());
+    service.commit("untitled:b.wat".into(), "(module (func $f))".into());
+    service.set_config(
+        "untitled:b.wat".into(),
+        Some(ServiceConfig {
+            lint: Lints {
+                unused: LintLevel::Deny,
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    );
+    let response = service.pull_workspace_diagnostics(create_workspace_params(vec![]));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn uninit_config() {
+    let mut service = LanguageService::default();
+    service.initialize(InitializeParams {
+        capabilities: ClientCapabilities {
+            workspace: Some(WorkspaceClientCapabilities {
+                configuration: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    service.commit("untitled:a.wat".into(), "(module (func $f))".into());
+    service.commit("untitled:b.wat".into(), "(module (func $f))".into());
+    service.set_config("untitled:b.wat".into(), None);
+    let response = service.pull_workspace_diagnostics(create_workspace_params(vec![]));
+    assert_eq!(response.items.len(), 1);
+    assert!(matches!(
+        &response.items[0],
+        WorkspaceDocumentDiagnosticReport::Full(report) if report.uri == "untitled:b.wat"
+    ));
+}
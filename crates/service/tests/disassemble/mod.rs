@@ -0,0 +1,119 @@
+use insta::assert_snapshot;
+use lspt::{ExecuteCommandParams, Position, RenameParams, TextDocumentIdentifier};
+use wat_service::LanguageService;
+
+fn build(source: &str) -> Vec<u8> {
+    let uri = "untitled:test.wat".to_string();
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service
+        .execute_command(ExecuteCommandParams {
+            command: "wasmLanguageTools.buildWasm".into(),
+            arguments: Some(vec![serde_json::Value::String(uri)]),
+            work_done_token: Default::default(),
+        })
+        .unwrap();
+    serde_json::from_value(response["binary"].clone()).unwrap()
+}
+
+#[test]
+fn names() {
+    let source = r#"
+(module $m
+  (type $t (func (param i32)))
+  (import "env" "log" (func $log (type $t)))
+  (memory $mem 1)
+  (global $g (mut i32) (i32.const 0))
+  (func $main (export "main") (param $p i32) (local $x i32)
+    (block $b
+      (br_if $b
+        (local.get $x)))
+    (call $log
+      (i32.add
+        (local.get $p)
+        (global.get $g))))
+  (data (i32.const 8) "hi\00"))
+"#;
+    let mut service = LanguageService::default();
+    let text = service
+        .disassemble("untitled:test.wasm".into(), &build(source))
+        .unwrap();
+    assert_snapshot!(text);
+}
+
+#[test]
+fn without_names() {
+    #[rustfmt::skip]
+    let binary = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+        0x01, 0x07, 0x01, 0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f,
+        0x03, 0x02, 0x01, 0x00,
+        0x07, 0x07, 0x01, 0x03, b'a', b'd', b'd', 0x00, 0x00,
+        0x0a, 0x09, 0x01, 0x07, 0x00, 0x20, 0x00, 0x20, 0x01, 0x6a, 0x0b,
+    ];
+    let mut service = LanguageService::default();
+    let text = service.disassemble("untitled:test.wasm".into(), &binary).unwrap();
+    assert_snapshot!(text);
+}
+
+#[test]
+fn bad_magic() {
+    let mut service = LanguageService::default();
+    let error = service
+        .disassemble("untitled:test.wasm".into(), b"\0wasm\x01\0\0\0")
+        .unwrap_err();
+    assert_eq!(error.offset, 0);
+    assert_snapshot!(error.to_string());
+}
+
+#[test]
+fn unexpected_end() {
+    let mut service = LanguageService::default();
+    let error = service
+        .disassemble("untitled:test.wasm".into(), b"\0asm\x01\0\0\0\x01\x07\x01\x60")
+        .unwrap_err();
+    assert_snapshot!(error.to_string());
+}
+
+#[test]
+fn diagnostics() {
+    #[rustfmt::skip]
+    let binary = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+        0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f,
+        0x03, 0x02, 0x01, 0x00,
+        // function body returns nothing while `i32` is expected
+        0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+    ];
+    let uri = "untitled:test.wasm".to_string();
+    let mut service = LanguageService::default();
+    service.disassemble(uri.clone(), &binary).unwrap();
+    let diagnostics = service.publish_diagnostics(uri).diagnostics;
+    assert!(!diagnostics.is_empty());
+}
+
+#[test]
+fn read_only() {
+    let uri = "untitled:test.wasm".to_string();
+    let mut service = LanguageService::default();
+    service
+        .disassemble(uri.clone(), &build("(module (func $f) (func (call $f)))"))
+        .unwrap();
+    let response = service.rename(RenameParams {
+        text_document: TextDocumentIdentifier { uri: uri.clone() },
+        position: Position { line: 1, character: 10 },
+        new_name: "$g".into(),
+        work_done_token: Default::default(),
+    });
+    assert!(response.is_none());
+
+    // editing the disassembled text makes it an ordinary document
+    service.commit(uri.clone(), "(module (func $f) (func (call $f)))".into());
+    let response = service.rename(RenameParams {
+        text_document: TextDocumentIdentifier { uri },
+        position: Position { line: 0, character: 15 },
+        new_name: "$g".into(),
+        work_done_token: Default::default(),
+    });
+    assert!(response.is_some());
+}
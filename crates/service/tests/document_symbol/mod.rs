@@ -70,3 +70,64 @@ fn deprecated() {
     });
     assert_json_snapshot!(response);
 }
+
+#[test]
+fn name_annotations() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module (@name "mod")
+  (func (@name "my func") (param (@name "x") i32)
+    (local (@name "tmp") i64))
+  (func $f (@name "f"))
+  (import "env" "g" (func (@name "imported")))
+  (global (@name "counter") (mut i32) (i32.const 0))
+)
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.document_symbol(DocumentSymbolParams {
+        text_document: TextDocumentIdentifier { uri },
+        work_done_token: Default::default(),
+        partial_result_token: Default::default(),
+    });
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn custom_sections() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (@custom "a" (before first) "")
+  (func $f)
+  (@custom "producers" (after func) "")
+  (global $g i32 (i32.const 0))
+  (func (@custom "misplaced" "")))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.document_symbol(DocumentSymbolParams {
+        text_document: TextDocumentIdentifier { uri },
+        work_done_token: Default::default(),
+        partial_result_token: Default::default(),
+    });
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn custom_sections_in_implicit_module() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(@custom "a" "")
+(func $f)
+(@custom "b" (after func) "")
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.document_symbol(DocumentSymbolParams {
+        text_document: TextDocumentIdentifier { uri },
+        work_done_token: Default::default(),
+        partial_result_token: Default::default(),
+    });
+    assert_json_snapshot!(response);
+}
@@ -0,0 +1,155 @@
+use insta::assert_json_snapshot;
+use lspt::ExecuteCommandParams;
+use wat_service::LanguageService;
+
+const COMMAND: &str = "wasmLanguageTools.buildWasm";
+
+fn build(source: &str, module_id: Option<u32>) -> serde_json::Value {
+    let uri = "untitled:test".to_string();
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let mut arguments = vec![serde_json::Value::String(uri)];
+    if let Some(module_id) = module_id {
+        arguments.push(module_id.into());
+    }
+    service
+        .execute_command(ExecuteCommandParams {
+            command: COMMAND.into(),
+            arguments: Some(arguments),
+            work_done_token: Default::default(),
+        })
+        .unwrap()
+}
+
+fn binary(response: serde_json::Value) -> Vec<u8> {
+    serde_json::from_value(response["binary"].clone()).unwrap()
+}
+
+#[test]
+fn empty_module() {
+    let response = build("(module)", None);
+    assert_eq!(binary(response), b"\0asm\x01\0\0\0");
+}
+
+#[test]
+fn func() {
+    let source = r#"
+(module
+  (func (export "add") (param i32 i32) (result i32)
+    (i32.add
+      (local.get 0)
+      (local.get 1))))
+"#;
+    #[rustfmt::skip]
+    let expected = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+        // type section
+        0x01, 0x07, 0x01, 0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f,
+        // func section
+        0x03, 0x02, 0x01, 0x00,
+        // export section
+        0x07, 0x07, 0x01, 0x03, b'a', b'd', b'd', 0x00, 0x00,
+        // code section
+        0x0a, 0x09, 0x01, 0x07, 0x00, 0x20, 0x00, 0x20, 0x01, 0x6a, 0x0b,
+    ];
+    assert_eq!(binary(build(source, None)), expected);
+}
+
+#[test]
+fn elem_without_list() {
+    let source = "(module (table 1 funcref) (elem (i32.const 0)))";
+    #[rustfmt::skip]
+    let expected = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+        // table section
+        0x04, 0x04, 0x01, 0x70, 0x00, 0x01,
+        // elem section
+        0x09, 0x06, 0x01, 0x00, 0x41, 0x00, 0x0b, 0x00,
+    ];
+    assert_eq!(binary(build(source, None)), expected);
+}
+
+#[test]
+fn idents_and_names() {
+    let source = r#"
+(module $m
+  (type $t (func (param i32)))
+  (import "env" "log" (func $log (type $t)))
+  (memory $mem 1)
+  (func $main (local $x i32)
+    (block $b
+      (br_if $b
+        (local.get $x)))
+    (call $log
+      (i32.const -1)))
+  (data (i32.const 8) "hi"))
+"#;
+    assert_json_snapshot!(build(source, None));
+}
+
+#[test]
+fn block_labels() {
+    let source = r#"
+(module
+  (func (result i32)
+    (block $outer (result i32)
+      (loop $inner
+        (br_if $inner
+          (i32.const 0))
+        (br $outer
+          (i32.const 1))))))
+"#;
+    #[rustfmt::skip]
+    let expected = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+        0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f,
+        0x03, 0x02, 0x01, 0x00,
+        0x0a, 0x12, 0x01, 0x10, 0x00,
+        0x02, 0x7f, // block $outer
+        0x03, 0x40, // loop $inner
+        0x41, 0x00, 0x0d, 0x00, // br_if $inner
+        0x41, 0x01, 0x0c, 0x01, // br $outer
+        0x0b, 0x0b, 0x0b,
+    ];
+    assert_eq!(binary(build(source, None)), expected);
+}
+
+#[test]
+fn multiple_modules() {
+    let source = r#"
+(module)
+(module
+  (memory 1))
+"#;
+    assert_eq!(
+        binary(build(source, Some(1))),
+        [
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01
+        ]
+    );
+}
+
+#[test]
+fn module_not_found() {
+    assert_json_snapshot!(build("(module)", Some(1)));
+}
+
+#[test]
+fn syntax_error() {
+    let source = r#"
+(module
+  (func
+    (i32.add (i32.const 0) (i32.const 1)
+"#;
+    assert_json_snapshot!(build(source, None));
+}
+
+#[test]
+fn undefined_ident() {
+    let source = r#"
+(module
+  (func
+    (call $missing)))
+"#;
+    assert_json_snapshot!(build(source, None));
+}
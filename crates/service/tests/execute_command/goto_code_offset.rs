@@ -0,0 +1,87 @@
+use insta::assert_json_snapshot;
+use lspt::ExecuteCommandParams;
+use wat_service::LanguageService;
+
+const COMMAND: &str = "wasmLanguageTools.gotoCodeOffset";
+
+fn goto(source: &str, func_idx: Option<u32>, offset: u32) -> serde_json::Value {
+    let uri = "untitled:test".to_string();
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    service
+        .execute_command(ExecuteCommandParams {
+            command: COMMAND.into(),
+            arguments: Some(vec![uri.into(), func_idx.into(), offset.into()]),
+            work_done_token: Default::default(),
+        })
+        .unwrap_or_default()
+}
+
+const SOURCE: &str = r#"
+(module
+  (import "env" "f" (func))
+  (func (param i32) (result i32)
+    (local i64)
+    (i32.add
+      (local.get 0)
+      (i32.const 1)))
+  (func
+    (block $b
+      (br $b))))
+"#;
+
+#[test]
+fn func_relative() {
+    // locals: 0x01 0x01 0x7e, `local.get 0`: 0x20 0x00, `i32.const 1`: 0x41 0x01, `i32.add`: 0x6a
+    assert_json_snapshot!(goto(SOURCE, Some(1), 7));
+}
+
+#[test]
+fn folded_operand() {
+    assert_json_snapshot!(goto(SOURCE, Some(1), 4));
+}
+
+#[test]
+fn locals() {
+    assert_json_snapshot!(goto(SOURCE, Some(1), 1));
+}
+
+#[test]
+fn block_end() {
+    // locals: 0x00, `block`: 0x02 0x40, `br 0`: 0x0c 0x00, `end`: 0x0b
+    assert_json_snapshot!(goto(SOURCE, Some(2), 5));
+}
+
+#[test]
+fn module_relative() {
+    let uri = "untitled:test".to_string();
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), SOURCE.into());
+    let binary = service
+        .execute_command(ExecuteCommandParams {
+            command: "wasmLanguageTools.buildWasm".into(),
+            arguments: Some(vec![uri.into()]),
+            work_done_token: Default::default(),
+        })
+        .unwrap();
+    let binary = serde_json::from_value::<Vec<u8>>(binary["binary"].clone()).unwrap();
+    let offset = binary.iter().position(|byte| *byte == 0x6a).unwrap() as u32;
+    assert_eq!(goto(SOURCE, None, offset), goto(SOURCE, Some(1), 7));
+}
+
+#[test]
+fn out_of_bounds() {
+    assert!(goto(SOURCE, Some(1), 100).is_null());
+    assert!(goto(SOURCE, Some(0), 0).is_null());
+    assert!(goto(SOURCE, None, 0).is_null());
+}
+
+#[test]
+fn invalid_module() {
+    let source = r#"
+(module
+  (func
+    (call $missing)))
+"#;
+    assert!(goto(source, Some(0), 1).is_null());
+}
@@ -1 +1,4 @@
+mod build_wasm;
 mod generate_cfg_dot;
+mod goto_code_offset;
+mod resolve_binary_offset;
@@ -0,0 +1,63 @@
+use insta::assert_json_snapshot;
+use lspt::ExecuteCommandParams;
+use wat_service::LanguageService;
+
+const COMMAND: &str = "wasmLanguageTools.resolveBinaryOffset";
+
+fn resolve(service: &LanguageService, uri: &str, offset: u32) -> serde_json::Value {
+    service
+        .execute_command(ExecuteCommandParams {
+            command: COMMAND.into(),
+            arguments: Some(vec![uri.into(), offset.into()]),
+            work_done_token: Default::default(),
+        })
+        .unwrap_or_default()
+}
+
+#[rustfmt::skip]
+const BINARY: [u8; 41] = [
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    // type section
+    0x01, 0x07, 0x01, 0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f,
+    // func section
+    0x03, 0x02, 0x01, 0x00,
+    // export section
+    0x07, 0x07, 0x01, 0x03, b'a', b'd', b'd', 0x00, 0x00,
+    // code section
+    0x0a, 0x09, 0x01, 0x07, 0x00, 0x20, 0x00, 0x20, 0x01, 0x6a, 0x0b,
+];
+
+#[test]
+fn instr() {
+    let uri = "untitled:test.wasm";
+    let mut service = LanguageService::default();
+    service.disassemble(uri.into(), &BINARY).unwrap();
+    // `i32.add`
+    assert_json_snapshot!(resolve(&service, uri, 0x27));
+}
+
+#[test]
+fn module_field() {
+    let uri = "untitled:test.wasm";
+    let mut service = LanguageService::default();
+    service.disassemble(uri.into(), &BINARY).unwrap();
+    // `(export "add" ...)`
+    assert_json_snapshot!(resolve(&service, uri, 0x18));
+}
+
+#[test]
+fn before_first_field() {
+    let uri = "untitled:test.wasm";
+    let mut service = LanguageService::default();
+    service.disassemble(uri.into(), &BINARY).unwrap();
+    assert!(resolve(&service, uri, 2).is_null());
+}
+
+#[test]
+fn edited() {
+    let uri = "untitled:test.wasm";
+    let mut service = LanguageService::default();
+    service.disassemble(uri.into(), &BINARY).unwrap();
+    service.commit(uri.into(), "(module)".into());
+    assert!(resolve(&service, uri, 0x27).is_null());
+}
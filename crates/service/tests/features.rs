@@ -4,6 +4,7 @@ mod code_lens;
 mod completion;
 mod definition;
 mod diagnostics;
+mod disassemble;
 mod document_highlight;
 mod document_symbol;
 mod execute_command;
@@ -13,7 +14,9 @@ mod hover;
 mod inlay_hint;
 mod references;
 mod rename;
+mod report;
 mod selection_range;
 mod semantic_tokens;
 mod signature_help;
 mod type_hierarchy;
+mod workspace_symbol;
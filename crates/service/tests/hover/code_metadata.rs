@@ -0,0 +1,86 @@
+use super::create_params;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+#[test]
+fn branch_hint() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (func (param i32)
+    (@metadata.code.branch_hint "\01")
+    (if (local.get 0)
+      (then))
+    (block
+      (@metadata.code.branch_hint "\00")
+      (br_if 0 (local.get 0)))))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri.clone(), 3, 10));
+    assert_json_snapshot!(response);
+    let response = service.hover(create_params(uri, 7, 36));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn instr_freq() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func
+    (@metadata.code.instr_freq (freq 5))
+    (nop)))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri, 3, 34));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn call_targets() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (type $t (func))
+  (table 1 funcref)
+  (func $f
+    (@metadata.code.call_targets (target $f 70) (target 1 30))
+    (call_indirect (type $t) (i32.const 0)))
+  (func))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri, 5, 10));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn compilation_priority() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (@metadata.code.compilation_priority (compilation 1) (optimization 2) (run_once))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri, 2, 12));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn malformed() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (func (param i32)
+    (@metadata.code.branch_hint "\02")
+    (if (local.get 0)
+      (then))))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri, 3, 10));
+    assert!(response.is_none());
+}
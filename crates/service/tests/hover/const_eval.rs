@@ -0,0 +1,88 @@
+use super::create_params;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+#[test]
+fn extended_const() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (global $a i32 (i32.const 40))
+  (global $b i32 (i32.add (global.get $a) (i32.const 2)))
+  (global $c i64 i64.const 3 i64.const -4 i64.mul i64.const 1 i64.sub))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri.clone(), 3, 12));
+    assert_json_snapshot!(response);
+    let response = service.hover(create_params(uri, 4, 12));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn global_get_mutable() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (global $a (mut i32) (i32.const 1))
+  (global $b i32 (global.get $a)))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri.clone(), 2, 12));
+    assert_json_snapshot!(response);
+    let response = service.hover(create_params(uri, 3, 12));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn global_get_imported() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (import "" "" (global $a i32))
+  (global $b i32 (global.get $a)))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri, 3, 12));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn references() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (type $s (struct (field i32) (field i64) (field (ref null any))))
+  (global $null funcref (ref.null func))
+  (global $i31 i31ref (ref.i31 (i32.const -1)))
+  (global $struct (ref $s) (struct.new $s (i32.const 1) (i64.const 2) (ref.null any))))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri.clone(), 3, 12));
+    assert_json_snapshot!(response);
+    let response = service.hover(create_params(uri.clone(), 4, 12));
+    assert_json_snapshot!(response);
+    let response = service.hover(create_params(uri, 5, 12));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn offset() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (global $base i32 (i32.const 1024))
+  (memory 1)
+  (data (offset (i32.add (global.get $base) (i32.const 16))) "")
+  (data (i32.const 8) ""))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri.clone(), 4, 10));
+    assert_json_snapshot!(response);
+    let response = service.hover(create_params(uri, 5, 11));
+    assert_json_snapshot!(response);
+}
@@ -2,9 +2,12 @@ use insta::assert_json_snapshot;
 use lspt::{HoverParams, Position, TextDocumentIdentifier};
 use wat_service::LanguageService;
 
+mod code_metadata;
+mod const_eval;
 mod doc_comment;
 mod instr;
 mod memo;
+mod name_annot;
 
 fn create_params(uri: String, line: u32, character: u32) -> HoverParams {
     HoverParams {
@@ -1144,3 +1147,20 @@ fn i64_const() {
     let response = service.hover(create_params(uri, 3, 16));
     assert_json_snapshot!(response);
 }
+
+#[test]
+fn invoke_export() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (func (export "add") (param $a i32) (param $b i32) (result i32)
+    (i32.add
+      (local.get $a)
+      (local.get $b))))
+(assert_return (invoke "add" (i32.const 1) (i32.const 2)) (i32.const 3))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri, 6, 25));
+    assert_json_snapshot!(response);
+}
@@ -0,0 +1,68 @@
+use super::create_params;
+use insta::assert_json_snapshot;
+use wat_service::LanguageService;
+
+#[test]
+fn func() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (func (@name "my func") (param i32) (result i32)
+    local.get 0)
+  (func
+    i32.const 0
+    call 0
+    drop))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri, 6, 9));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn imported_func() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (import "env" "f" (func (@name "imported") (param i32)))
+  (func
+    i32.const 0
+    call 0))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri, 5, 9));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn local() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (func (local (@name "tmp") i32)
+    local.get 0
+    drop))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri, 3, 15));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn ident_preferred() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (global $g (@name "g") i32 (i32.const 0))
+  (func
+    global.get 0
+    drop))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.hover(create_params(uri, 4, 15));
+    assert_json_snapshot!(response);
+}
@@ -235,6 +235,7 @@ fn field_with_struct_changed() {
                 types: true,
                 ending: false,
                 index: false,
+                code_offset: false,
             },
             ..Default::default()
         }),
@@ -291,6 +292,7 @@ fn types_only() {
                 types: true,
                 ending: false,
                 index: false,
+                code_offset: false,
             },
             ..Default::default()
         }),
@@ -319,6 +321,7 @@ fn ending_only() {
                 types: false,
                 ending: true,
                 index: false,
+                code_offset: false,
             },
             ..Default::default()
         }),
@@ -348,6 +351,7 @@ fn index_only() {
                 types: false,
                 ending: false,
                 index: true,
+                code_offset: false,
             },
             ..Default::default()
         }),
@@ -355,3 +359,40 @@ fn index_only() {
     let response = service.inlay_hint(create_params(uri, 7, 0));
     assert_json_snapshot!(response);
 }
+
+#[test]
+fn code_offset_only() {
+    let uri = "untitled:test".to_string();
+    let source = "
+(module
+  (func (param i32) (result i32)
+    (local i64)
+    (if (result i32)
+      (local.get 0)
+      (then
+        (i32.const 1))
+      (else
+        (i32.const 2))))
+  (func
+    block
+      nop
+      br 0
+    end))
+";
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    service.set_config(
+        uri.clone(),
+        Some(ServiceConfig {
+            inlay_hint: InlayHintOptions {
+                types: false,
+                ending: false,
+                index: false,
+                code_offset: true,
+            },
+            ..Default::default()
+        }),
+    );
+    let response = service.inlay_hint(create_params(uri, 15, 0));
+    assert_json_snapshot!(response);
+}
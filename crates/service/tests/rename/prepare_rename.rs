@@ -45,3 +45,19 @@ fn ident() {
     let response = service.prepare_rename(create_params(uri, 2, 14));
     assert_json_snapshot!(response);
 }
+
+#[test]
+fn name_annotation() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+    (func (@name "f"))
+    (@other "f")
+)
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.prepare_rename(create_params(uri.clone(), 2, 19));
+    assert_json_snapshot!(response);
+    assert!(service.prepare_rename(create_params(uri, 3, 13)).is_none());
+}
@@ -681,3 +681,49 @@ fn elem_ref() {
     let response = service.rename(create_params(uri, 4, 18, "$e"));
     assert_json_snapshot!(response);
 }
+
+#[test]
+fn name_annotation_in_sync() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (func $f (@name "f")
+    call $f)
+  (func $g (@name "other")
+    call $g))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.rename(create_params(uri.clone(), 2, 10, "my func"));
+    assert_json_snapshot!(response);
+    let response = service.rename(create_params(uri, 4, 10, "$h"));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn name_annotation_only() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (func (@name "f")
+    call 0))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.rename(create_params(uri, 2, 17, "$new"));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn name_annotation_with_ident() {
+    let uri = "untitled:test".to_string();
+    let source = r#"
+(module
+  (func $f (@name "other")
+    call $f))
+"#;
+    let mut service = LanguageService::default();
+    service.commit(uri.clone(), source.into());
+    let response = service.rename(create_params(uri, 2, 20, "g"));
+    assert_json_snapshot!(response);
+}
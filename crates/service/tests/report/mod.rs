@@ -0,0 +1,54 @@
+use insta::assert_snapshot;
+use wat_service::{FileDiagnostics, LanguageService, ReportFormat, serialize_diagnostics};
+
+fn report(format: ReportFormat) -> String {
+    let uri = "untitled:test.wat".to_string();
+    let mut service = LanguageService::default();
+    service.commit(
+        uri.clone(),
+        r#"
+(module
+  (func $f (param $p i32) (param $p i32)
+    call $missing
+    return
+    nop)
+  (func (export "a,b"))
+  (global $g (mut i32)
+    i32.const 0))
+"#
+        .into(),
+    );
+    let diagnostics = service.publish_diagnostics(uri).diagnostics;
+    serialize_diagnostics(
+        format,
+        &[FileDiagnostics {
+            path: "src/test.wat",
+            diagnostics: &diagnostics,
+        }],
+    )
+}
+
+#[test]
+fn json() {
+    assert_snapshot!(report(ReportFormat::Json));
+}
+
+#[test]
+fn sarif() {
+    assert_snapshot!(report(ReportFormat::Sarif));
+}
+
+#[test]
+fn github() {
+    assert_snapshot!(report(ReportFormat::GitHub));
+}
+
+#[test]
+fn checkstyle() {
+    assert_snapshot!(report(ReportFormat::Checkstyle));
+}
+
+#[test]
+fn empty() {
+    assert_snapshot!(serialize_diagnostics(ReportFormat::Checkstyle, &[]));
+}
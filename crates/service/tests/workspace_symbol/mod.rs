@@ -0,0 +1,68 @@
+use insta::assert_json_snapshot;
+use lspt::WorkspaceSymbolParams;
+use wat_service::LanguageService;
+
+fn create_params(query: &str) -> WorkspaceSymbolParams {
+    WorkspaceSymbolParams {
+        query: query.into(),
+        work_done_token: Default::default(),
+        partial_result_token: Default::default(),
+    }
+}
+
+fn create_service() -> LanguageService {
+    let mut service = LanguageService::default();
+    service.commit(
+        "untitled:alloc.wat".into(),
+        r#"
+(module $alloc
+  (memory $heap 1)
+  (global $heap_top (mut i32)
+    i32.const 0)
+  (func $alloc (export "malloc") (param i32) (result i32)
+    unreachable)
+  (func (export "free") (param i32)))
+"#
+        .into(),
+    );
+    service.commit(
+        "untitled:main.wat".into(),
+        r#"
+(module
+  (type $AllocFn (func (param i32) (result i32)))
+  (func $main
+    (local $allocated i32))
+  (@deprecated)
+  (func $realloc))
+"#
+        .into(),
+    );
+    service
+}
+
+#[test]
+fn all() {
+    let service = create_service();
+    let response = service.workspace_symbol(create_params(""));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn fuzzy() {
+    let service = create_service();
+    let response = service.workspace_symbol(create_params("alc"));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn export_name() {
+    let service = create_service();
+    let response = service.workspace_symbol(create_params("FREE"));
+    assert_json_snapshot!(response);
+}
+
+#[test]
+fn no_match() {
+    let service = create_service();
+    assert_eq!(service.workspace_symbol(create_params("xyz")), Some(vec![]));
+}
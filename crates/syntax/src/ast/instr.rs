@@ -252,6 +252,7 @@ pub enum BlockInstr<'a> {
     Loop(BlockLoop<'a>),
     If(BlockIf<'a>),
     TryTable(BlockTryTable<'a>),
+    Try(BlockTry<'a>),
 }
 impl<'a> AstNode<'a> for BlockInstr<'a> {
     #[inline]
@@ -261,7 +262,11 @@ impl<'a> AstNode<'a> for BlockInstr<'a> {
     {
         matches!(
             kind,
-            SyntaxKind::BLOCK_BLOCK | SyntaxKind::BLOCK_LOOP | SyntaxKind::BLOCK_IF | SyntaxKind::BLOCK_TRY_TABLE
+            SyntaxKind::BLOCK_BLOCK
+                | SyntaxKind::BLOCK_LOOP
+                | SyntaxKind::BLOCK_IF
+                | SyntaxKind::BLOCK_TRY_TABLE
+                | SyntaxKind::BLOCK_TRY
         )
     }
     #[inline]
@@ -274,6 +279,7 @@ impl<'a> AstNode<'a> for BlockInstr<'a> {
             SyntaxKind::BLOCK_LOOP => Some(BlockInstr::Loop(BlockLoop { syntax })),
             SyntaxKind::BLOCK_IF => Some(BlockInstr::If(BlockIf { syntax })),
             SyntaxKind::BLOCK_TRY_TABLE => Some(BlockInstr::TryTable(BlockTryTable { syntax })),
+            SyntaxKind::BLOCK_TRY => Some(BlockInstr::Try(BlockTry { syntax })),
             _ => None,
         }
     }
@@ -284,6 +290,7 @@ impl<'a> AstNode<'a> for BlockInstr<'a> {
             BlockInstr::Loop(it) => it.syntax(),
             BlockInstr::If(it) => it.syntax(),
             BlockInstr::TryTable(it) => it.syntax(),
+            BlockInstr::Try(it) => it.syntax(),
         }
     }
 }
@@ -353,6 +360,275 @@ impl<'a> AstNode<'a> for BlockLoop<'a> {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlockTry<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> BlockTry<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn ident_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::IDENT)
+    }
+    #[inline]
+    pub fn type_use(&self) -> Option<TypeUse<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn do_block(&self) -> Option<BlockTryDo<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn catch_blocks(&self) -> AstChildren<'a, BlockTryCatch<'a>> {
+        children(&self.syntax)
+    }
+    #[inline]
+    pub fn catch_all_block(&self) -> Option<BlockTryCatchAll<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn delegate(&self) -> Option<BlockTryDelegate<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+    #[inline]
+    pub fn end_keyword(&self) -> Option<SyntaxToken<'a>> {
+        self.syntax
+            .tokens_by_kind(SyntaxKind::KEYWORD)
+            .find(|token| token.text() == "end")
+    }
+    #[inline]
+    pub fn end_ident_token(&self) -> Option<SyntaxToken<'a>> {
+        self.syntax.tokens_by_kind(SyntaxKind::IDENT).nth(1)
+    }
+}
+impl<'a> AstNode<'a> for BlockTry<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::BLOCK_TRY
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(BlockTry { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlockTryCatch<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> BlockTryCatch<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn tag_index(&self) -> Option<Index<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn instrs(&self) -> AstChildren<'a, Instr<'a>> {
+        children(&self.syntax)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for BlockTryCatch<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::BLOCK_TRY_CATCH
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(BlockTryCatch { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlockTryCatchAll<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> BlockTryCatchAll<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn instrs(&self) -> AstChildren<'a, Instr<'a>> {
+        children(&self.syntax)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for BlockTryCatchAll<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::BLOCK_TRY_CATCH_ALL
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(BlockTryCatchAll { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlockTryDelegate<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> BlockTryDelegate<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn label_index(&self) -> Option<Index<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for BlockTryDelegate<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::BLOCK_TRY_DELEGATE
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(BlockTryDelegate { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlockTryDo<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> BlockTryDo<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn instrs(&self) -> AstChildren<'a, Instr<'a>> {
+        children(&self.syntax)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for BlockTryDo<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::BLOCK_TRY_DO
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(BlockTryDo { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BlockTryTable<'a> {
     syntax: SyntaxNode<'a>,
@@ -571,6 +847,7 @@ impl<'a> AstNode<'a> for Instr<'a> {
                 | SyntaxKind::BLOCK_LOOP
                 | SyntaxKind::BLOCK_IF
                 | SyntaxKind::BLOCK_TRY_TABLE
+                | SyntaxKind::BLOCK_TRY
         )
     }
     #[inline]
@@ -584,6 +861,7 @@ impl<'a> AstNode<'a> for Instr<'a> {
             SyntaxKind::BLOCK_LOOP => Some(Instr::Block(BlockInstr::Loop(BlockLoop { syntax }))),
             SyntaxKind::BLOCK_IF => Some(Instr::Block(BlockInstr::If(BlockIf { syntax }))),
             SyntaxKind::BLOCK_TRY_TABLE => Some(Instr::Block(BlockInstr::TryTable(BlockTryTable { syntax }))),
+            SyntaxKind::BLOCK_TRY => Some(Instr::Block(BlockInstr::Try(BlockTry { syntax }))),
             _ => None,
         }
     }
@@ -1,12 +1,13 @@
 //! Abstract Syntax Tree, layered on top of untyped `SyntaxNode`s.
 
 use self::support::children;
-pub use self::{instr::*, module::*, ty::*};
+pub use self::{instr::*, module::*, script::*, ty::*};
 use crate::{SyntaxKind, SyntaxNode, SyntaxNodeChildren};
 use std::{iter, marker::PhantomData};
 
 mod instr;
 mod module;
+mod script;
 mod ty;
 
 pub trait AstNode<'a> {
@@ -65,6 +66,10 @@ impl<'a> Root<'a> {
     pub fn modules(&self) -> AstChildren<'a, Module<'a>> {
         children(&self.syntax)
     }
+    #[inline]
+    pub fn commands(&self) -> AstChildren<'a, Command<'a>> {
+        children(&self.syntax)
+    }
 }
 impl<'a> AstNode<'a> for Root<'a> {
     #[inline]
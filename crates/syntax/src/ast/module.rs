@@ -819,6 +819,108 @@ impl<'a> AstNode<'a> for Module<'a> {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ModuleBinary<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> ModuleBinary<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn ident_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::IDENT)
+    }
+    #[inline]
+    pub fn string_tokens(&self) -> impl Iterator<Item = SyntaxToken<'a>> {
+        self.syntax.tokens_by_kind(SyntaxKind::STRING)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for ModuleBinary<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::MODULE_BINARY
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(ModuleBinary { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ModuleQuote<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> ModuleQuote<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn ident_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::IDENT)
+    }
+    #[inline]
+    pub fn string_tokens(&self) -> impl Iterator<Item = SyntaxToken<'a>> {
+        self.syntax.tokens_by_kind(SyntaxKind::STRING)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for ModuleQuote<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::MODULE_QUOTE
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(ModuleQuote { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ModuleField<'a> {
     Data(ModuleFieldData<'a>),
@@ -0,0 +1,582 @@
+use super::{
+    AstChildren, AstNode,
+    instr::Instr,
+    module::{Module, ModuleBinary, ModuleQuote, Name},
+    support::*,
+};
+use crate::{SyntaxKind, SyntaxNode, SyntaxToken};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Action<'a> {
+    Get(ActionGet<'a>),
+    Invoke(ActionInvoke<'a>),
+}
+impl<'a> AstNode<'a> for Action<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        matches!(kind, SyntaxKind::ACTION_GET | SyntaxKind::ACTION_INVOKE)
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        match syntax.kind() {
+            SyntaxKind::ACTION_GET => Some(Action::Get(ActionGet { syntax })),
+            SyntaxKind::ACTION_INVOKE => Some(Action::Invoke(ActionInvoke { syntax })),
+            _ => None,
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        match self {
+            Action::Get(it) => it.syntax(),
+            Action::Invoke(it) => it.syntax(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionGet<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> ActionGet<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn ident_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::IDENT)
+    }
+    #[inline]
+    pub fn name(&self) -> Option<Name<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for ActionGet<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::ACTION_GET
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(ActionGet { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionInvoke<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> ActionInvoke<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn ident_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::IDENT)
+    }
+    #[inline]
+    pub fn name(&self) -> Option<Name<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn args(&self) -> AstChildren<'a, Instr<'a>> {
+        children(&self.syntax)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for ActionInvoke<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::ACTION_INVOKE
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(ActionInvoke { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssertExhaustion<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> AssertExhaustion<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn action(&self) -> Option<Action<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn message_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::STRING)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for AssertExhaustion<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::ASSERT_EXHAUSTION
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(AssertExhaustion { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssertInvalid<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> AssertInvalid<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn module(&self) -> Option<Module<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn message_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::STRING)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for AssertInvalid<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::ASSERT_INVALID
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(AssertInvalid { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssertMalformed<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> AssertMalformed<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn module(&self) -> Option<Module<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn message_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::STRING)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for AssertMalformed<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::ASSERT_MALFORMED
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(AssertMalformed { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssertReturn<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> AssertReturn<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn action(&self) -> Option<Action<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn results(&self) -> AstChildren<'a, Instr<'a>> {
+        children(&self.syntax)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for AssertReturn<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::ASSERT_RETURN
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(AssertReturn { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssertTrap<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> AssertTrap<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn action(&self) -> Option<Action<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn module(&self) -> Option<Module<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn message_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::STRING)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for AssertTrap<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::ASSERT_TRAP
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(AssertTrap { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssertUnlinkable<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> AssertUnlinkable<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn module(&self) -> Option<Module<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn message_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::STRING)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for AssertUnlinkable<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::ASSERT_UNLINKABLE
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(AssertUnlinkable { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Register<'a> {
+    syntax: SyntaxNode<'a>,
+}
+impl<'a> Register<'a> {
+    #[inline]
+    pub fn l_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::L_PAREN)
+    }
+    #[inline]
+    pub fn keyword(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::KEYWORD)
+    }
+    #[inline]
+    pub fn name(&self) -> Option<Name<'a>> {
+        child(&self.syntax)
+    }
+    #[inline]
+    pub fn ident_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::IDENT)
+    }
+    #[inline]
+    pub fn r_paren_token(&self) -> Option<SyntaxToken<'a>> {
+        token(&self.syntax, SyntaxKind::R_PAREN)
+    }
+}
+impl<'a> AstNode<'a> for Register<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == SyntaxKind::REGISTER
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind()) {
+            Some(Register { syntax })
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        &self.syntax
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Top-level command of a `.wast` script, including modules.
+pub enum Command<'a> {
+    Module(Module<'a>),
+    ModuleBinary(ModuleBinary<'a>),
+    ModuleQuote(ModuleQuote<'a>),
+    Register(Register<'a>),
+    Action(Action<'a>),
+    AssertReturn(AssertReturn<'a>),
+    AssertTrap(AssertTrap<'a>),
+    AssertExhaustion(AssertExhaustion<'a>),
+    AssertInvalid(AssertInvalid<'a>),
+    AssertMalformed(AssertMalformed<'a>),
+    AssertUnlinkable(AssertUnlinkable<'a>),
+}
+impl<'a> AstNode<'a> for Command<'a> {
+    #[inline]
+    fn can_cast(kind: SyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        matches!(
+            kind,
+            SyntaxKind::MODULE
+                | SyntaxKind::MODULE_BINARY
+                | SyntaxKind::MODULE_QUOTE
+                | SyntaxKind::REGISTER
+                | SyntaxKind::ACTION_GET
+                | SyntaxKind::ACTION_INVOKE
+                | SyntaxKind::ASSERT_RETURN
+                | SyntaxKind::ASSERT_TRAP
+                | SyntaxKind::ASSERT_EXHAUSTION
+                | SyntaxKind::ASSERT_INVALID
+                | SyntaxKind::ASSERT_MALFORMED
+                | SyntaxKind::ASSERT_UNLINKABLE
+        )
+    }
+    #[inline]
+    fn cast(syntax: SyntaxNode<'a>) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        match syntax.kind() {
+            SyntaxKind::MODULE => Module::cast(syntax).map(Command::Module),
+            SyntaxKind::MODULE_BINARY => ModuleBinary::cast(syntax).map(Command::ModuleBinary),
+            SyntaxKind::MODULE_QUOTE => ModuleQuote::cast(syntax).map(Command::ModuleQuote),
+            SyntaxKind::REGISTER => Some(Command::Register(Register { syntax })),
+            SyntaxKind::ACTION_GET | SyntaxKind::ACTION_INVOKE => Action::cast(syntax).map(Command::Action),
+            SyntaxKind::ASSERT_RETURN => Some(Command::AssertReturn(AssertReturn { syntax })),
+            SyntaxKind::ASSERT_TRAP => Some(Command::AssertTrap(AssertTrap { syntax })),
+            SyntaxKind::ASSERT_EXHAUSTION => Some(Command::AssertExhaustion(AssertExhaustion { syntax })),
+            SyntaxKind::ASSERT_INVALID => Some(Command::AssertInvalid(AssertInvalid { syntax })),
+            SyntaxKind::ASSERT_MALFORMED => Some(Command::AssertMalformed(AssertMalformed { syntax })),
+            SyntaxKind::ASSERT_UNLINKABLE => Some(Command::AssertUnlinkable(AssertUnlinkable { syntax })),
+            _ => None,
+        }
+    }
+    #[inline]
+    fn syntax(&self) -> &SyntaxNode<'a> {
+        match self {
+            Command::Module(it) => it.syntax(),
+            Command::ModuleBinary(it) => it.syntax(),
+            Command::ModuleQuote(it) => it.syntax(),
+            Command::Register(it) => it.syntax(),
+            Command::Action(it) => it.syntax(),
+            Command::AssertReturn(it) => it.syntax(),
+            Command::AssertTrap(it) => it.syntax(),
+            Command::AssertExhaustion(it) => it.syntax(),
+            Command::AssertInvalid(it) => it.syntax(),
+            Command::AssertMalformed(it) => it.syntax(),
+            Command::AssertUnlinkable(it) => it.syntax(),
+        }
+    }
+}
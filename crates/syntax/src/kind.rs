@@ -53,6 +53,11 @@ pub enum SyntaxKind {
     BLOCK_IF_THEN,
     BLOCK_IF_ELSE,
     BLOCK_TRY_TABLE,
+    BLOCK_TRY,
+    BLOCK_TRY_DO,
+    BLOCK_TRY_CATCH,
+    BLOCK_TRY_CATCH_ALL,
+    BLOCK_TRY_DELEGATE,
     CATCH,
     CATCH_ALL,
     MEM_ARG,
@@ -84,6 +89,8 @@ pub enum SyntaxKind {
     TABLE_USE,
     DATA,
     MODULE,
+    MODULE_BINARY,
+    MODULE_QUOTE,
     MODULE_FIELD_DATA,
     MODULE_FIELD_ELEM,
     MODULE_FIELD_EXPORT,
@@ -96,6 +103,15 @@ pub enum SyntaxKind {
     MODULE_FIELD_TAG,
     TYPE_DEF,
     REC_TYPE,
+    ACTION_GET,
+    ACTION_INVOKE,
+    ASSERT_EXHAUSTION,
+    ASSERT_INVALID,
+    ASSERT_MALFORMED,
+    ASSERT_RETURN,
+    ASSERT_TRAP,
+    ASSERT_UNLINKABLE,
+    REGISTER,
     ROOT,
 }
 
@@ -35,6 +35,7 @@ export default defineConfig({
               },
             ],
           },
+          { text: 'Command Line', link: '/guide/command-line' },
           { text: 'Deprecation', link: '/guide/deprecation' },
         ],
       },
@@ -58,6 +59,8 @@ export default defineConfig({
             ],
           },
           { text: 'Inlay Hint', link: '/config/inlay-hint' },
+          { text: 'Imports', link: '/config/imports' },
+          { text: 'Features', link: '/config/features' },
         ],
       },
       {